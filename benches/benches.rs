@@ -1,5 +1,11 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
-use predictive_shuffle::Shuffle;
+use predictive_shuffle::ordered::{
+    gen_crypto_predictive_shuffle_from_seed_into, gen_crypto_predictive_shuffle_from_seed_ordered,
+    gen_predictive_shuffle_from_seed_into, gen_predictive_shuffle_from_seed_ordered,
+};
+use predictive_shuffle::{
+    gen_crypto_predictive_shuffle_from_seed_ref, gen_predictive_shuffle_from_seed_ref, Shuffle,
+};
 
 fn bench_fastrand(c: &mut Criterion) {
     let size = 100_000;
@@ -22,13 +28,13 @@ fn bench_fastrand(c: &mut Criterion) {
     group.bench_with_input(
         BenchmarkId::new("Modern Shuffle w/ Seed", i.to_owned()),
         i,
-        |b, _i| b.iter(|| vec.clone().modern_shuffle_from_seed(seed.clone())),
+        |b, _i| b.iter(|| vec.clone().modern_shuffle_from_seed_ref(&seed)),
     );
 
     group.bench_with_input(
         BenchmarkId::new("Predictive Shuffle", i.to_owned()),
         i,
-        |b, _i| b.iter(|| vec.clone().predictive_shuffle(positions.clone())),
+        |b, _i| b.iter(|| vec.clone().predictive_shuffle_ref(&positions)),
     );
 
     group.bench_with_input(
@@ -37,7 +43,7 @@ fn bench_fastrand(c: &mut Criterion) {
         |b, _i| {
             b.iter(|| {
                 vec.clone()
-                    .predictive_shuffle_from_seed(positions.clone(), seed.clone())
+                    .predictive_shuffle_from_seed_ref(&positions, &seed)
             })
         },
     );
@@ -45,12 +51,7 @@ fn bench_fastrand(c: &mut Criterion) {
     group.bench_with_input(
         BenchmarkId::new("Batch Predictive Shuffle", i.to_owned()),
         i,
-        |b, _i| {
-            b.iter(|| {
-                vec.clone()
-                    .batch_predictive_shuffle(batch, positions.clone())
-            })
-        },
+        |b, _i| b.iter(|| vec.clone().batch_predictive_shuffle_ref(batch, &positions)),
     );
 
     group.bench_with_input(
@@ -58,11 +59,8 @@ fn bench_fastrand(c: &mut Criterion) {
         i,
         |b, _i| {
             b.iter(|| {
-                vec.clone().batch_predictive_shuffle_from_seed(
-                    batch,
-                    positions.clone(),
-                    seed.clone(),
-                )
+                vec.clone()
+                    .batch_predictive_shuffle_from_seed_ref(batch, &positions, &seed)
             })
         },
     );
@@ -91,13 +89,13 @@ fn bench_chacha(c: &mut Criterion) {
     group.bench_with_input(
         BenchmarkId::new("Crypto Modern Shuffle w/ Seed", i.to_owned()),
         i,
-        |b, _i| b.iter(|| vec.clone().crypto_modern_shuffle_from_seed(seed.clone())),
+        |b, _i| b.iter(|| vec.clone().crypto_modern_shuffle_from_seed_ref(&seed)),
     );
 
     group.bench_with_input(
         BenchmarkId::new("Crypto Predictive Shuffle", i.to_owned()),
         i,
-        |b, _i| b.iter(|| vec.clone().crypto_predictive_shuffle(positions.clone())),
+        |b, _i| b.iter(|| vec.clone().crypto_predictive_shuffle_ref(&positions)),
     );
 
     group.bench_with_input(
@@ -106,7 +104,7 @@ fn bench_chacha(c: &mut Criterion) {
         |b, _i| {
             b.iter(|| {
                 vec.clone()
-                    .crypto_predictive_shuffle_from_seed(positions.clone(), seed.clone())
+                    .crypto_predictive_shuffle_from_seed_ref(&positions, &seed)
             })
         },
     );
@@ -117,7 +115,7 @@ fn bench_chacha(c: &mut Criterion) {
         |b, _i| {
             b.iter(|| {
                 vec.clone()
-                    .crypto_batch_predictive_shuffle(batch, positions.clone())
+                    .crypto_batch_predictive_shuffle_ref(batch, &positions)
             })
         },
     );
@@ -127,11 +125,8 @@ fn bench_chacha(c: &mut Criterion) {
         i,
         |b, _i| {
             b.iter(|| {
-                vec.clone().crypto_batch_predictive_shuffle_from_seed(
-                    batch,
-                    positions.clone(),
-                    seed.clone(),
-                )
+                vec.clone()
+                    .crypto_batch_predictive_shuffle_from_seed_ref(batch, &positions, &seed)
             })
         },
     );
@@ -139,5 +134,64 @@ fn bench_chacha(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_fastrand, bench_chacha);
+fn bench_predictive_output(c: &mut Criterion) {
+    let size = 100_000;
+    let positions: Vec<usize> = (0..1_000).map(|i| i * 100).collect();
+    let seed = b"love item".to_vec();
+    let mut out = vec![0; positions.len()];
+
+    let mut group: criterion::BenchmarkGroup<'_, criterion::measurement::WallTime> =
+        c.benchmark_group("Predictive Output");
+
+    let i = &100u64;
+
+    group.bench_with_input(
+        BenchmarkId::new("Predictive Shuffle w/ Seed HashMap", i.to_owned()),
+        i,
+        |b, _i| b.iter(|| gen_predictive_shuffle_from_seed_ref(size, &positions, &seed)),
+    );
+
+    group.bench_with_input(
+        BenchmarkId::new("Predictive Shuffle w/ Seed Slice", i.to_owned()),
+        i,
+        |b, _i| b.iter(|| gen_predictive_shuffle_from_seed_into(size, &positions, &seed, &mut out)),
+    );
+
+    group.bench_with_input(
+        BenchmarkId::new("Predictive Shuffle w/ Seed BTreeMap", i.to_owned()),
+        i,
+        |b, _i| b.iter(|| gen_predictive_shuffle_from_seed_ordered(size, &positions, &seed)),
+    );
+
+    group.bench_with_input(
+        BenchmarkId::new("Crypto Predictive Shuffle w/ Seed HashMap", i.to_owned()),
+        i,
+        |b, _i| b.iter(|| gen_crypto_predictive_shuffle_from_seed_ref(size, &positions, &seed)),
+    );
+
+    group.bench_with_input(
+        BenchmarkId::new("Crypto Predictive Shuffle w/ Seed Slice", i.to_owned()),
+        i,
+        |b, _i| {
+            b.iter(|| {
+                gen_crypto_predictive_shuffle_from_seed_into(size, &positions, &seed, &mut out)
+            })
+        },
+    );
+
+    group.bench_with_input(
+        BenchmarkId::new("Crypto Predictive Shuffle w/ Seed BTreeMap", i.to_owned()),
+        i,
+        |b, _i| b.iter(|| gen_crypto_predictive_shuffle_from_seed_ordered(size, &positions, &seed)),
+    );
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_fastrand,
+    bench_chacha,
+    bench_predictive_output
+);
 criterion_main!(benches);
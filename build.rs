@@ -0,0 +1,17 @@
+//! Sets `cfg(entropy)` when the crate can seed from operating system entropy: it needs the `std`
+//! feature, and on `wasm32-unknown-unknown` the `js` feature to reach `crypto.getRandomValues`
+
+use std::env;
+
+fn main() {
+    println!("cargo::rustc-check-cfg=cfg(entropy)");
+    println!("cargo::rerun-if-changed=build.rs");
+
+    let arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    let os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let std = env::var_os("CARGO_FEATURE_STD").is_some();
+    let js = env::var_os("CARGO_FEATURE_JS").is_some();
+    if std && os != "none" && !(arch == "wasm32" && os == "unknown" && !js) {
+        println!("cargo::rustc-cfg=entropy");
+    }
+}
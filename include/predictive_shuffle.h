@@ -0,0 +1,188 @@
+#ifndef PREDICTIVE_SHUFFLE_H
+#define PREDICTIVE_SHUFFLE_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Outcome of a call
+typedef enum PsStatus {
+  // The output buffer was filled
+  PS_STATUS_OK = 0,
+  // A pointer was null while its length was not zero
+  PS_STATUS_NULL_POINTER = 1,
+  // A position is not below the size, or is repeated
+  PS_STATUS_INVALID_POSITION = 2,
+  // A batch size is zero, or larger than the size
+  PS_STATUS_INVALID_BATCH = 3,
+  // The call failed unexpectedly, and the output buffer is unspecified
+  PS_STATUS_INTERNAL = 4,
+} PsStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Static, nul-terminated description of a status
+const char *ps_status_message(enum PsStatus status);
+
+// [`crate::gen_fastrand_shuffle_from_seed_ref`] into `out`, which holds `size` items
+//
+// # Safety
+// `seed` must be valid for `seed_len` reads and `out` for `size` writes.
+enum PsStatus ps_gen_fastrand_shuffle_from_seed(size_t size,
+                                                const uint8_t *seed,
+                                                size_t seed_len,
+                                                size_t *out);
+
+// [`crate::gen_modern_shuffle_from_seed_ref`] into `out`, which holds `size` items
+//
+// # Safety
+// `seed` must be valid for `seed_len` reads and `out` for `size` writes.
+enum PsStatus ps_gen_modern_shuffle_from_seed(size_t size,
+                                              const uint8_t *seed,
+                                              size_t seed_len,
+                                              size_t *out);
+
+// [`crate::gen_crypto_modern_shuffle_from_seed_ref`] into `out`, which holds `size` items
+//
+// # Safety
+// `seed` must be valid for `seed_len` reads and `out` for `size` writes.
+enum PsStatus ps_gen_crypto_modern_shuffle_from_seed(size_t size,
+                                                     const uint8_t *seed,
+                                                     size_t seed_len,
+                                                     size_t *out);
+
+// [`crate::constant_time::gen_constant_time_shuffle_from_seed`] into `out`, which holds `size`
+// items
+//
+// # Safety
+// `seed` must be valid for `seed_len` reads and `out` for `size` writes.
+enum PsStatus ps_gen_constant_time_shuffle_from_seed(size_t size,
+                                                     const uint8_t *seed,
+                                                     size_t seed_len,
+                                                     size_t *out);
+
+// [`crate::ordered::gen_predictive_shuffle_into`]
+//
+// # Safety
+// `positions` must be valid for `positions_len` reads and `out` for `positions_len` writes.
+enum PsStatus ps_gen_predictive_shuffle(size_t size,
+                                        const size_t *positions,
+                                        size_t positions_len,
+                                        size_t *out);
+
+// [`crate::ordered::gen_predictive_shuffle_from_seed_into`]
+//
+// # Safety
+// `positions` must be valid for `positions_len` reads, `seed` for `seed_len` reads and `out` for
+// `positions_len` writes.
+enum PsStatus ps_gen_predictive_shuffle_from_seed(size_t size,
+                                                  const size_t *positions,
+                                                  size_t positions_len,
+                                                  const uint8_t *seed,
+                                                  size_t seed_len,
+                                                  size_t *out);
+
+// [`crate::ordered::gen_crypto_predictive_shuffle_into`]
+//
+// # Safety
+// `positions` must be valid for `positions_len` reads and `out` for `positions_len` writes.
+enum PsStatus ps_gen_crypto_predictive_shuffle(size_t size,
+                                               const size_t *positions,
+                                               size_t positions_len,
+                                               size_t *out);
+
+// [`crate::ordered::gen_crypto_predictive_shuffle_from_seed_into`]
+//
+// # Safety
+// `positions` must be valid for `positions_len` reads, `seed` for `seed_len` reads and `out` for
+// `positions_len` writes.
+enum PsStatus ps_gen_crypto_predictive_shuffle_from_seed(size_t size,
+                                                         const size_t *positions,
+                                                         size_t positions_len,
+                                                         const uint8_t *seed,
+                                                         size_t seed_len,
+                                                         size_t *out);
+
+// [`crate::ordered::gen_batch_predictive_shuffle_into`]
+//
+// # Safety
+// `positions` must be valid for `positions_len` reads and `out` for `positions_len` writes.
+enum PsStatus ps_gen_batch_predictive_shuffle(size_t size,
+                                              size_t batch,
+                                              const size_t *positions,
+                                              size_t positions_len,
+                                              size_t *out);
+
+// [`crate::ordered::gen_batch_predictive_shuffle_from_seed_into`]
+//
+// # Safety
+// `positions` must be valid for `positions_len` reads, `seed` for `seed_len` reads and `out` for
+// `positions_len` writes.
+enum PsStatus ps_gen_batch_predictive_shuffle_from_seed(size_t size,
+                                                        size_t batch,
+                                                        const size_t *positions,
+                                                        size_t positions_len,
+                                                        const uint8_t *seed,
+                                                        size_t seed_len,
+                                                        size_t *out);
+
+// [`crate::ordered::gen_crypto_batch_predictive_shuffle_into`]
+//
+// # Safety
+// `positions` must be valid for `positions_len` reads and `out` for `positions_len` writes.
+enum PsStatus ps_gen_crypto_batch_predictive_shuffle(size_t size,
+                                                     size_t batch,
+                                                     const size_t *positions,
+                                                     size_t positions_len,
+                                                     size_t *out);
+
+// [`crate::ordered::gen_crypto_batch_predictive_shuffle_from_seed_into`]
+//
+// # Safety
+// `positions` must be valid for `positions_len` reads, `seed` for `seed_len` reads and `out` for
+// `positions_len` writes.
+enum PsStatus ps_gen_crypto_batch_predictive_shuffle_from_seed(size_t size,
+                                                               size_t batch,
+                                                               const size_t *positions,
+                                                               size_t positions_len,
+                                                               const uint8_t *seed,
+                                                               size_t seed_len,
+                                                               size_t *out);
+
+// [`crate::constant_time::gen_constant_time_predictive_shuffle`]
+//
+// Positions are not checked, since that would branch on their values. Out of range positions
+// map to `size`, as in the Rust function.
+//
+// # Safety
+// `positions` must be valid for `positions_len` reads and `out` for `positions_len` writes.
+enum PsStatus ps_gen_constant_time_predictive_shuffle(size_t size,
+                                                      const size_t *positions,
+                                                      size_t positions_len,
+                                                      size_t *out);
+
+// [`crate::constant_time::gen_constant_time_predictive_shuffle_from_seed`]
+//
+// Positions are not checked, as in [`ps_gen_constant_time_predictive_shuffle`].
+//
+// # Safety
+// `positions` must be valid for `positions_len` reads, `seed` for `seed_len` reads and `out` for
+// `positions_len` writes.
+enum PsStatus ps_gen_constant_time_predictive_shuffle_from_seed(size_t size,
+                                                                const size_t *positions,
+                                                                size_t positions_len,
+                                                                const uint8_t *seed,
+                                                                size_t seed_len,
+                                                                size_t *out);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* PREDICTIVE_SHUFFLE_H */
@@ -0,0 +1,440 @@
+//! # axis
+//! Shuffles of [`ndarray`](::ndarray) arrays along an axis, such as the rows of an `Array2`.
+//!
+//! [`ShuffleAxis`] moves whole subviews along an axis the way [`crate::Shuffle`] moves the items
+//! of a `Vec`, so shuffling the rows of an array from a seed puts row `i` where the same seed
+//! puts item `i` of a vector. Its predictions are read off that same permutation, so they give
+//! the positions the shuffle moves subviews to, unlike the `gen_*_predictive_shuffle` functions,
+//! which run their own algorithm. To keep several arrays aligned, such as features and labels,
+//! the `co_shuffle_axis*` functions apply one permutation to all of them.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! use ndarray::{array, Axis};
+//! use predictive_shuffle::axis::co_shuffle_axis_from_seed_ref;
+//!
+//! let mut features = array![[0., 0.], [1., 1.], [2., 2.], [3., 3.]];
+//! let mut labels = array![0, 1, 2, 3];
+//! co_shuffle_axis_from_seed_ref(&mut [&mut features, &mut labels], Axis(0), b"seed phrase")
+//!     .unwrap();
+//! for (row, label) in features.rows().into_iter().zip(&labels) {
+//!     assert_eq!(row[0], *label as f64);
+//! }
+//! ```
+
+use crate::co_shuffle::{co_permute_with, CoShuffleError};
+#[cfg(all(feature = "chacha", entropy))]
+use crate::gen_crypto_modern_shuffle;
+#[cfg(feature = "chacha")]
+use crate::gen_crypto_modern_shuffle_from_seed_ref;
+#[cfg(all(feature = "fastrand", entropy))]
+use crate::gen_modern_shuffle;
+#[cfg(feature = "fastrand")]
+use crate::gen_modern_shuffle_from_seed_ref;
+use ndarray::{Array, ArrayBase, Axis, Data, RemoveAxis};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+/// Shuffles of the subviews of an array along an axis
+///
+/// Every method panics if `axis` is out of bounds, as [`ArrayBase::len_of`] does.
+pub trait ShuffleAxis<A, D> {
+    #[cfg(all(feature = "fastrand", entropy))]
+    fn modern_shuffle_axis(&self, axis: Axis) -> Array<A, D>;
+    #[cfg(feature = "fastrand")]
+    fn modern_shuffle_axis_from_seed_ref(&self, axis: Axis, seed: impl AsRef<[u8]>) -> Array<A, D>;
+    #[cfg(all(feature = "chacha", entropy))]
+    fn crypto_modern_shuffle_axis(&self, axis: Axis) -> Array<A, D>;
+    #[cfg(feature = "chacha")]
+    fn crypto_modern_shuffle_axis_from_seed_ref(
+        &self,
+        axis: Axis,
+        seed: impl AsRef<[u8]>,
+    ) -> Array<A, D>;
+    #[cfg(all(feature = "fastrand", entropy))]
+    fn predictive_shuffle_axis_ref(
+        &self,
+        axis: Axis,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize>;
+    #[cfg(feature = "fastrand")]
+    fn predictive_shuffle_axis_from_seed_ref(
+        &self,
+        axis: Axis,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize>;
+    #[cfg(all(feature = "chacha", entropy))]
+    fn crypto_predictive_shuffle_axis_ref(
+        &self,
+        axis: Axis,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize>;
+    #[cfg(feature = "chacha")]
+    fn crypto_predictive_shuffle_axis_from_seed_ref(
+        &self,
+        axis: Axis,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize>;
+}
+
+impl<A, S, D> ShuffleAxis<A, D> for ArrayBase<S, D>
+where
+    A: Clone,
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+{
+    /// Shuffle Along an Axis
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use ndarray::{array, Axis};
+    /// use predictive_shuffle::axis::ShuffleAxis;
+    ///
+    /// let array = array![[0, 1], [2, 3], [4, 5]];
+    /// let shuffled_array = array.modern_shuffle_axis(Axis(0));
+    /// ```
+    #[cfg(all(feature = "fastrand", entropy))]
+    fn modern_shuffle_axis(&self, axis: Axis) -> Array<A, D> {
+        self.select(axis, &gen_modern_shuffle(self.len_of(axis)))
+    }
+
+    /// Shuffle Along an Axis from Seed
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use ndarray::{array, Axis};
+    /// use predictive_shuffle::axis::ShuffleAxis;
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let array = array![[0, 1], [2, 3], [4, 5]];
+    /// let seed = b"seed phrase";
+    /// let shuffled_array = array.modern_shuffle_axis_from_seed_ref(Axis(0), seed);
+    ///
+    /// let shuffled_vec = vec![0, 1, 2].modern_shuffle_from_seed_ref(seed);
+    /// assert_eq!(shuffled_array.column(0).to_vec(), shuffled_vec.iter().map(|i| 2 * i).collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "fastrand")]
+    fn modern_shuffle_axis_from_seed_ref(&self, axis: Axis, seed: impl AsRef<[u8]>) -> Array<A, D> {
+        self.select(
+            axis,
+            &gen_modern_shuffle_from_seed_ref(self.len_of(axis), seed),
+        )
+    }
+
+    /// Cryptographically Shuffle Along an Axis
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use ndarray::{array, Axis};
+    /// use predictive_shuffle::axis::ShuffleAxis;
+    ///
+    /// let array = array![[0, 1], [2, 3], [4, 5]];
+    /// let shuffled_array = array.crypto_modern_shuffle_axis(Axis(1));
+    /// ```
+    #[cfg(all(feature = "chacha", entropy))]
+    fn crypto_modern_shuffle_axis(&self, axis: Axis) -> Array<A, D> {
+        self.select(axis, &gen_crypto_modern_shuffle(self.len_of(axis)))
+    }
+
+    /// Cryptographically Shuffle Along an Axis from Seed
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use ndarray::{array, Axis};
+    /// use predictive_shuffle::axis::ShuffleAxis;
+    ///
+    /// let array = array![[0, 1], [2, 3], [4, 5]];
+    /// let seed = b"seed phrase";
+    /// let shuffled_array = array.crypto_modern_shuffle_axis_from_seed_ref(Axis(1), seed);
+    /// ```
+    #[cfg(feature = "chacha")]
+    fn crypto_modern_shuffle_axis_from_seed_ref(
+        &self,
+        axis: Axis,
+        seed: impl AsRef<[u8]>,
+    ) -> Array<A, D> {
+        self.select(
+            axis,
+            &gen_crypto_modern_shuffle_from_seed_ref(self.len_of(axis), seed),
+        )
+    }
+
+    /// Predict Shuffled Position of Subviews Along an Axis
+    ///
+    /// The permutation is drawn from entropy and not returned, so the prediction does not
+    /// describe a separate call to [`ShuffleAxis::modern_shuffle_axis`].
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use ndarray::{Array2, Axis};
+    /// use predictive_shuffle::axis::ShuffleAxis;
+    ///
+    /// let array = Array2::<f64>::zeros((100, 3));
+    /// let new_positions = array.predictive_shuffle_axis_ref(Axis(0), [1, 5]);
+    /// ```
+    #[cfg(all(feature = "fastrand", entropy))]
+    fn predictive_shuffle_axis_ref(
+        &self,
+        axis: Axis,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize> {
+        predict(&gen_modern_shuffle(self.len_of(axis)), positions)
+    }
+
+    /// Predict Shuffled Position of Subviews Along an Axis from Seed
+    ///
+    /// These are the positions [`ShuffleAxis::modern_shuffle_axis_from_seed_ref`] moves the
+    /// subviews to with the same seed. Panics if a position is out of range.
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use ndarray::{Array2, Axis};
+    /// use predictive_shuffle::axis::ShuffleAxis;
+    ///
+    /// let array = Array2::<f64>::zeros((100, 3));
+    /// let seed = b"seed phrase";
+    /// let new_positions = array.predictive_shuffle_axis_from_seed_ref(Axis(0), [1, 5], seed);
+    ///
+    /// let shuffled_array = array.modern_shuffle_axis_from_seed_ref(Axis(0), seed);
+    /// assert_eq!(shuffled_array.row(new_positions[&1]), array.row(1));
+    /// ```
+    #[cfg(feature = "fastrand")]
+    fn predictive_shuffle_axis_from_seed_ref(
+        &self,
+        axis: Axis,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize> {
+        predict(
+            &gen_modern_shuffle_from_seed_ref(self.len_of(axis), seed),
+            positions,
+        )
+    }
+
+    /// Cryptographically Predict Shuffled Position of Subviews Along an Axis
+    ///
+    /// The permutation is drawn from entropy and not returned, so the prediction does not
+    /// describe a separate call to [`ShuffleAxis::crypto_modern_shuffle_axis`].
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use ndarray::{Array2, Axis};
+    /// use predictive_shuffle::axis::ShuffleAxis;
+    ///
+    /// let array = Array2::<f64>::zeros((100, 3));
+    /// let new_positions = array.crypto_predictive_shuffle_axis_ref(Axis(0), [1, 5]);
+    /// ```
+    #[cfg(all(feature = "chacha", entropy))]
+    fn crypto_predictive_shuffle_axis_ref(
+        &self,
+        axis: Axis,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize> {
+        predict(&gen_crypto_modern_shuffle(self.len_of(axis)), positions)
+    }
+
+    /// Cryptographically Predict Shuffled Position of Subviews Along an Axis from Seed
+    ///
+    /// These are the positions [`ShuffleAxis::crypto_modern_shuffle_axis_from_seed_ref`] moves
+    /// the subviews to with the same seed. Panics if a position is out of range.
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use ndarray::{Array2, Axis};
+    /// use predictive_shuffle::axis::ShuffleAxis;
+    ///
+    /// let array = Array2::<f64>::zeros((100, 3));
+    /// let seed = b"seed phrase";
+    /// let new_positions = array.crypto_predictive_shuffle_axis_from_seed_ref(Axis(0), [1, 5], seed);
+    /// ```
+    #[cfg(feature = "chacha")]
+    fn crypto_predictive_shuffle_axis_from_seed_ref(
+        &self,
+        axis: Axis,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize> {
+        predict(
+            &gen_crypto_modern_shuffle_from_seed_ref(self.len_of(axis), seed),
+            positions,
+        )
+    }
+}
+
+/// Shuffled position of every item of `positions` in `permutation`, which holds the original
+/// position of every shuffled item
+fn predict(
+    permutation: &[usize],
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+) -> HashMap<usize, usize> {
+    let mut new_positions = vec![0; permutation.len()];
+    for (new_position, &position) in permutation.iter().enumerate() {
+        new_positions[position] = new_position;
+    }
+    positions
+        .into_iter()
+        .map(|position| (*position.borrow(), new_positions[*position.borrow()]))
+        .collect()
+}
+
+/// An owned array whose subviews along an axis can be reordered in place, so arrays of
+/// different element types and dimensions can be co-shuffled together
+pub trait PermuteAxis {
+    /// Length along `axis`, or `None` if it is out of bounds
+    fn axis_len(&self, axis: Axis) -> Option<usize>;
+
+    /// Move subview `permutation[k]` along `axis` to position `k`
+    ///
+    /// `permutation` must be a permutation of `0..len`, where `len` is the length along `axis`.
+    fn permute_axis(&mut self, axis: Axis, permutation: &[usize]);
+}
+
+impl<A: Clone, D: RemoveAxis> PermuteAxis for Array<A, D> {
+    fn axis_len(&self, axis: Axis) -> Option<usize> {
+        (axis.index() < self.ndim()).then(|| self.len_of(axis))
+    }
+
+    fn permute_axis(&mut self, axis: Axis, permutation: &[usize]) {
+        *self = self.select(axis, permutation);
+    }
+}
+
+/// Co-shuffle Arrays Along an Axis
+///
+/// Every array is checked before any is shuffled, so on error they are all left as they were.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use ndarray::{array, Axis};
+/// use predictive_shuffle::axis::co_shuffle_axis;
+///
+/// let mut features = array![[0., 0.], [1., 1.], [2., 2.]];
+/// let mut labels = array![0, 1, 2];
+/// co_shuffle_axis(&mut [&mut features, &mut labels], Axis(0)).unwrap();
+/// ```
+#[cfg(all(feature = "fastrand", entropy))]
+pub fn co_shuffle_axis(
+    arrays: &mut [&mut dyn PermuteAxis],
+    axis: Axis,
+) -> Result<(), CoShuffleError> {
+    co_permute_axis(arrays, axis, gen_modern_shuffle)
+}
+
+/// Co-shuffle Arrays Along an Axis from Seed
+///
+/// Every array is moved by [`crate::gen_modern_shuffle_from_seed_ref`], and checked before any
+/// is shuffled, so on error they are all left as they were.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use ndarray::{array, Axis};
+/// use predictive_shuffle::axis::co_shuffle_axis_from_seed_ref;
+/// use predictive_shuffle::co_shuffle::CoShuffleError;
+///
+/// let mut features = array![[0., 0.], [1., 1.], [2., 2.]];
+/// let mut labels = array![0, 1];
+/// let result = co_shuffle_axis_from_seed_ref(&mut [&mut features, &mut labels], Axis(0), b"seed phrase");
+/// assert_eq!(result, Err(CoShuffleError::LengthMismatch { expected: 3, found: 2 }));
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn co_shuffle_axis_from_seed_ref(
+    arrays: &mut [&mut dyn PermuteAxis],
+    axis: Axis,
+    seed: impl AsRef<[u8]>,
+) -> Result<(), CoShuffleError> {
+    co_permute_axis(arrays, axis, |len| {
+        gen_modern_shuffle_from_seed_ref(len, seed)
+    })
+}
+
+/// Cryptographically Co-shuffle Arrays Along an Axis
+///
+/// Every array is checked before any is shuffled, so on error they are all left as they were.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use ndarray::{array, Axis};
+/// use predictive_shuffle::axis::crypto_co_shuffle_axis;
+///
+/// let mut features = array![[0., 0.], [1., 1.], [2., 2.]];
+/// let mut labels = array![0, 1, 2];
+/// crypto_co_shuffle_axis(&mut [&mut features, &mut labels], Axis(0)).unwrap();
+/// ```
+#[cfg(all(feature = "chacha", entropy))]
+pub fn crypto_co_shuffle_axis(
+    arrays: &mut [&mut dyn PermuteAxis],
+    axis: Axis,
+) -> Result<(), CoShuffleError> {
+    co_permute_axis(arrays, axis, gen_crypto_modern_shuffle)
+}
+
+/// Cryptographically Co-shuffle Arrays Along an Axis from Seed
+///
+/// Every array is moved by [`crate::gen_crypto_modern_shuffle_from_seed_ref`], and checked
+/// before any is shuffled, so on error they are all left as they were.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use ndarray::{array, Axis};
+/// use predictive_shuffle::axis::crypto_co_shuffle_axis_from_seed_ref;
+///
+/// let mut features = array![[0., 0.], [1., 1.], [2., 2.]];
+/// let mut labels = array![0, 1, 2];
+/// let seed = b"seed phrase";
+/// crypto_co_shuffle_axis_from_seed_ref(&mut [&mut features, &mut labels], Axis(0), seed).unwrap();
+/// ```
+#[cfg(feature = "chacha")]
+pub fn crypto_co_shuffle_axis_from_seed_ref(
+    arrays: &mut [&mut dyn PermuteAxis],
+    axis: Axis,
+    seed: impl AsRef<[u8]>,
+) -> Result<(), CoShuffleError> {
+    co_permute_axis(arrays, axis, |len| {
+        gen_crypto_modern_shuffle_from_seed_ref(len, seed)
+    })
+}
+
+fn co_permute_axis(
+    arrays: &mut [&mut dyn PermuteAxis],
+    axis: Axis,
+    gen: impl FnOnce(usize) -> Vec<usize>,
+) -> Result<(), CoShuffleError> {
+    co_permute_with(
+        arrays,
+        |array| {
+            array
+                .axis_len(axis)
+                .ok_or(CoShuffleError::AxisOutOfBounds(axis.index()))
+        },
+        gen,
+        |array, permutation| array.permute_axis(axis, permutation),
+    )
+}
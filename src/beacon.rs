@@ -0,0 +1,393 @@
+//! # beacon
+//! Seeds derived from public randomness beacon rounds.
+//!
+//! A [`BeaconRound`] is parsed from a recorded beacon response, either a drand-style JSON round
+//! or a NIST Beacon 1.0 style XML record, and checked for internal consistency:
+//!
+//! - drand: `randomness` must be the SHA-256 of `signature`
+//! - NIST: `outputValue` must be the SHA-512 of `signatureValue`
+//!
+//! NIST records are authenticated by [`BeaconRound::from_nist_xml_verified`], which checks the
+//! record's RSA signature against the beacon's [`NistKey`], taken from the certificate the beacon
+//! publishes. drand rounds are **not authenticated**: their BLS12-381 signatures need a pairing
+//! library, which the crate does not depend on, so anyone can make up a round that passes
+//! [`BeaconRound::check_randomness`] by hashing a made-up signature. Verify drand rounds with
+//! drand's own client before trusting them. The derived [`Seed`] is bound to the beacon source
+//! and round number, so the same randomness can never be replayed as a different round.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! # #[cfg(feature = "chacha")]
+//! # {
+//! use predictive_shuffle::beacon::BeaconRound;
+//! use predictive_shuffle::gen_crypto_predictive_shuffle_from_seed_ref;
+//!
+//! let json = r#"{
+//!     "round": 1,
+//!     "randomness": "ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb",
+//!     "signature": "61"
+//! }"#;
+//! let round = BeaconRound::from_drand_json(json).unwrap();
+//! let seed = round.seed();
+//! let new_positions = gen_crypto_predictive_shuffle_from_seed_ref(100, [1, 5], &seed);
+//! # }
+//! ```
+
+use crate::{hash, hex, Seed};
+use num_bigint::BigUint;
+use serde_json::Value;
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Errors returned when loading or checking a beacon round
+#[derive(Debug)]
+pub enum BeaconError {
+    /// The record could not be read
+    Io(io::Error),
+    /// The record is malformed or missing a field
+    Parse(String),
+    /// The record's randomness does not match its signature
+    Verification,
+    /// The record's signature does not verify against the beacon's public key
+    Signature,
+}
+
+impl fmt::Display for BeaconError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BeaconError::Io(e) => write!(f, "failed to read beacon record: {}", e),
+            BeaconError::Parse(reason) => write!(f, "malformed beacon record: {}", reason),
+            BeaconError::Verification => {
+                write!(f, "beacon randomness does not match its signature")
+            }
+            BeaconError::Signature => {
+                write!(f, "beacon signature does not verify against the public key")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BeaconError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BeaconError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BeaconError {
+    fn from(e: io::Error) -> Self {
+        BeaconError::Io(e)
+    }
+}
+
+/// Beacon a round was published by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BeaconSource {
+    /// drand-style JSON rounds
+    Drand,
+    /// NIST Beacon 1.0 style XML records
+    Nist,
+}
+
+impl BeaconSource {
+    fn name(&self) -> &'static str {
+        match self {
+            BeaconSource::Drand => "drand",
+            BeaconSource::Nist => "nist",
+        }
+    }
+}
+
+impl fmt::Display for BeaconSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for BeaconSource {
+    type Err = BeaconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drand" => Ok(BeaconSource::Drand),
+            "nist" => Ok(BeaconSource::Nist),
+            other => Err(BeaconError::Parse(format!("unknown beacon {:?}", other))),
+        }
+    }
+}
+
+/// RSA public key of a NIST beacon, to authenticate its records with
+///
+/// The modulus and exponent are those of the certificate the beacon signs its records with, as
+/// printed by `openssl x509 -noout -modulus` and `openssl x509 -noout -text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NistKey {
+    modulus: BigUint,
+    exponent: BigUint,
+}
+
+impl NistKey {
+    /// Key from its big-endian modulus and exponent
+    pub fn new(modulus: &[u8], exponent: &[u8]) -> Self {
+        NistKey {
+            modulus: BigUint::from_bytes_be(modulus),
+            exponent: BigUint::from_bytes_be(exponent),
+        }
+    }
+
+    /// Key from its modulus and exponent in hex
+    pub fn from_hex(modulus: &str, exponent: &str) -> Result<Self, BeaconError> {
+        Ok(NistKey::new(&parse_hex(modulus)?, &parse_hex(exponent)?))
+    }
+
+    /// Check an RSASSA-PKCS1-v1_5 signature with SHA-512 over `message`
+    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        let len = (self.modulus.bits() as usize).div_ceil(8);
+        let signature = BigUint::from_bytes_be(signature);
+        if len < SHA512_DIGEST_INFO.len() + 64 + 11 || signature >= self.modulus {
+            return false;
+        }
+
+        let decoded = signature
+            .modpow(&self.exponent, &self.modulus)
+            .to_bytes_be();
+        let mut encoded = vec![0; len - decoded.len()];
+        encoded.extend_from_slice(&decoded);
+
+        let mut expected = vec![0x00, 0x01];
+        expected.resize(len - SHA512_DIGEST_INFO.len() - 64 - 1, 0xff);
+        expected.push(0x00);
+        expected.extend_from_slice(&SHA512_DIGEST_INFO);
+        expected.extend_from_slice(&Sha512::digest(message));
+        encoded == expected
+    }
+}
+
+/// DER prefix of a SHA-512 `DigestInfo`, from RFC 8017
+const SHA512_DIGEST_INFO: [u8; 19] = [
+    0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03, 0x05,
+    0x00, 0x04, 0x40,
+];
+
+/// A single published beacon round
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BeaconRound {
+    /// Beacon the round was published by
+    pub source: BeaconSource,
+    /// Round number, or the pulse timestamp for NIST records
+    pub round: u64,
+    /// Published randomness
+    pub randomness: Vec<u8>,
+    /// Beacon signature the randomness is derived from
+    pub signature: Vec<u8>,
+}
+
+impl BeaconRound {
+    /// Parse a drand-style JSON round with `round`, `randomness` and `signature`, and check its
+    /// randomness
+    pub fn from_drand_json(text: &str) -> Result<Self, BeaconError> {
+        let fields: Value = serde_json::from_str(text)
+            .map_err(|e| BeaconError::Parse(format!("invalid JSON: {}", e)))?;
+        let field = |key: &str| {
+            fields
+                .get(key)
+                .ok_or_else(|| BeaconError::Parse(format!("missing {}", key)))
+        };
+        let hex_field = |key: &str| {
+            field(key)?
+                .as_str()
+                .ok_or_else(|| BeaconError::Parse(format!("invalid {}", key)))
+                .and_then(parse_hex)
+        };
+
+        let beacon = BeaconRound {
+            source: BeaconSource::Drand,
+            round: field("round")?
+                .as_u64()
+                .ok_or_else(|| BeaconError::Parse("invalid round".to_string()))?,
+            randomness: hex_field("randomness")?,
+            signature: hex_field("signature")?,
+        };
+        beacon.check_randomness()?;
+        Ok(beacon)
+    }
+
+    /// Parse a NIST-style XML record with `timeStamp`, `signatureValue` and `outputValue`, and
+    /// check its randomness
+    ///
+    /// The record's signature is not checked, see [`BeaconRound::from_nist_xml_verified`].
+    pub fn from_nist_xml(text: &str) -> Result<Self, BeaconError> {
+        let field = |tag: &str| {
+            xml_element(text, tag).ok_or_else(|| BeaconError::Parse(format!("missing {}", tag)))
+        };
+
+        let beacon = BeaconRound {
+            source: BeaconSource::Nist,
+            round: parse_round(field("timeStamp")?)?,
+            randomness: parse_hex(field("outputValue")?)?,
+            signature: parse_hex(field("signatureValue")?)?,
+        };
+        beacon.check_randomness()?;
+        Ok(beacon)
+    }
+
+    /// Parse a NIST Beacon 1.0 XML record, check its randomness and verify its signature with
+    /// the beacon's `key`
+    ///
+    /// The signature covers `version`, `frequency`, `timeStamp`, `seedValue`,
+    /// `previousOutputValue` and `statusCode`, and is published with its bytes reversed.
+    pub fn from_nist_xml_verified(text: &str, key: &NistKey) -> Result<Self, BeaconError> {
+        let beacon = BeaconRound::from_nist_xml(text)?;
+        let field = |tag: &str| {
+            xml_element(text, tag).ok_or_else(|| BeaconError::Parse(format!("missing {}", tag)))
+        };
+        let number = |tag: &str| {
+            let value = field(tag)?;
+            value
+                .parse::<u32>()
+                .map_err(|_| BeaconError::Parse(format!("invalid {} {:?}", tag, value)))
+        };
+        let digest = |tag: &str| {
+            let value = parse_hex(field(tag)?)?;
+            if value.len() != 64 {
+                return Err(BeaconError::Parse(format!("invalid {}", tag)));
+            }
+            Ok(value)
+        };
+
+        let mut message = field("version")?.as_bytes().to_vec();
+        message.extend_from_slice(&number("frequency")?.to_be_bytes());
+        message.extend_from_slice(&beacon.round.to_be_bytes());
+        message.extend_from_slice(&digest("seedValue")?);
+        message.extend_from_slice(&digest("previousOutputValue")?);
+        message.extend_from_slice(&number("statusCode")?.to_be_bytes());
+
+        let mut signature = beacon.signature.clone();
+        signature.reverse();
+        if !key.verify(&message, &signature) {
+            return Err(BeaconError::Signature);
+        }
+        Ok(beacon)
+    }
+
+    /// Parse a NIST Beacon 1.0 XML record from a file and verify it, see
+    /// [`BeaconRound::from_nist_xml_verified`]
+    pub fn from_nist_file_verified<P: AsRef<Path>>(
+        path: P,
+        key: &NistKey,
+    ) -> Result<Self, BeaconError> {
+        let mut text = String::new();
+        File::open(path)?.read_to_string(&mut text)?;
+        BeaconRound::from_nist_xml_verified(&text, key)
+    }
+
+    /// Parse and check a round from a reader, detecting JSON or XML from the first character
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, BeaconError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        match text.trim_start().chars().next() {
+            Some('{') => BeaconRound::from_drand_json(&text),
+            Some('<') => BeaconRound::from_nist_xml(&text),
+            _ => Err(BeaconError::Parse("unknown record format".to_string())),
+        }
+    }
+
+    /// Parse and check a round recorded in a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, BeaconError> {
+        BeaconRound::from_reader(File::open(path)?)
+    }
+
+    /// Check that the randomness is derived from the signature
+    ///
+    /// The signature itself is not verified against the beacon's public key, so this does not
+    /// authenticate the round, see [`BeaconRound::from_nist_xml_verified`].
+    pub fn check_randomness(&self) -> Result<(), BeaconError> {
+        let expected = match self.source {
+            BeaconSource::Drand => Sha256::digest(&self.signature).to_vec(),
+            BeaconSource::Nist => Sha512::digest(&self.signature).to_vec(),
+        };
+        if expected != self.randomness {
+            return Err(BeaconError::Verification);
+        }
+        Ok(())
+    }
+
+    /// Seed bound to the beacon source, round number and randomness
+    pub fn seed(&self) -> Seed {
+        let name = self.source.name().as_bytes();
+        let mut preimage = (name.len() as u64).to_be_bytes().to_vec();
+        preimage.extend_from_slice(name);
+        preimage.extend_from_slice(&self.round.to_be_bytes());
+        preimage.extend_from_slice(&self.randomness);
+        Seed::new(hash(&preimage))
+    }
+}
+
+impl fmt::Display for BeaconRound {
+    /// `source round randomness signature`, as recorded in transcripts
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.source,
+            self.round,
+            hex::encode(&self.randomness),
+            hex::encode(&self.signature)
+        )
+    }
+}
+
+impl FromStr for BeaconRound {
+    type Err = BeaconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let [source, round, randomness, signature] = parts[..] else {
+            return Err(BeaconError::Parse(format!("invalid beacon {:?}", s)));
+        };
+        let beacon = BeaconRound {
+            source: source.parse()?,
+            round: parse_round(round)?,
+            randomness: parse_hex(randomness)?,
+            signature: parse_hex(signature)?,
+        };
+        beacon.check_randomness()?;
+        Ok(beacon)
+    }
+}
+
+fn parse_round(value: &str) -> Result<u64, BeaconError> {
+    value
+        .parse()
+        .map_err(|_| BeaconError::Parse(format!("invalid round {:?}", value)))
+}
+
+fn parse_hex(value: &str) -> Result<Vec<u8>, BeaconError> {
+    hex::decode(&value.to_ascii_lowercase())
+        .ok_or_else(|| BeaconError::Parse(format!("invalid hex {:?}", value)))
+}
+
+/// Text of the first `<tag>...</tag>` element, ignoring namespace prefixes and attributes
+fn xml_element<'a>(text: &'a str, tag: &str) -> Option<&'a str> {
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let end = rest.find('>')?;
+        let name = rest[..end].split_whitespace().next().unwrap_or("");
+        let name = name.rsplit(':').next().unwrap_or(name);
+        rest = &rest[end + 1..];
+        if name == tag {
+            let close = rest.find("</")?;
+            return Some(rest[..close].trim());
+        }
+    }
+    None
+}
@@ -0,0 +1,283 @@
+//! # predictive-shuffle
+//! Command line wrapper around the library, for shuffling lines, generating permutations,
+//! predicting positions and verifying published draws.
+//!
+//! Every command calls the same functions as the library, so a seeded result printed here is
+//! identical to the result of the matching library call. `gen` and `predict` print a
+//! [`ShuffleRecord`] with `--json`, which `verify` can later recompute.
+//!
+//! ```text
+//! predictive-shuffle shuffle names.txt --seed "seed phrase" --algo crypto
+//! predictive-shuffle gen --size 10 --seed "seed phrase" --json > record.json
+//! predictive-shuffle predict --size 100 --positions 1,5,9 --seed "seed phrase"
+//! predictive-shuffle verify record.json
+//! predictive-shuffle verify transcript.txt --entrants entrants.txt
+//! ```
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use predictive_shuffle::constant_time::{
+    gen_constant_time_shuffle, gen_constant_time_shuffle_from_seed,
+};
+use predictive_shuffle::record::{
+    Algorithm, SeedDescriptor, ShuffleRecord, ShuffleRequest, ShuffleResult,
+};
+use predictive_shuffle::transcript::{verify_transcript, Transcript};
+use predictive_shuffle::Shuffle;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "predictive-shuffle", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Shuffle the lines of a file, or of stdin
+    Shuffle {
+        /// File to read lines from, stdin when omitted or `-`
+        file: Option<String>,
+        #[command(flatten)]
+        options: Options,
+    },
+    /// Generate a permutation of `0..size`
+    Gen {
+        /// Number of items
+        #[arg(long)]
+        size: usize,
+        #[command(flatten)]
+        options: Options,
+    },
+    /// Predict the shuffled positions of some items of `0..size`
+    Predict {
+        /// Number of items
+        #[arg(long)]
+        size: usize,
+        /// Comma separated positions to predict
+        #[arg(long, value_delimiter = ',', required = true)]
+        positions: Vec<usize>,
+        /// Batch size, for the batch predictive shuffles
+        #[arg(long)]
+        batch: Option<usize>,
+        #[command(flatten)]
+        options: Options,
+    },
+    /// Verify a JSON shuffle record, or a transcript against its entrant list
+    Verify {
+        /// File to read the record or transcript from, stdin when omitted or `-`
+        file: Option<String>,
+        /// File listing the entrants of a transcript, one per line
+        #[arg(long)]
+        entrants: Option<String>,
+        /// Print the outcome as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Args)]
+struct Options {
+    /// Seed phrase, entropy when omitted
+    #[arg(long)]
+    seed: Option<String>,
+    /// Shuffling algorithm
+    #[arg(long, value_enum, default_value_t = Algo::Crypto)]
+    algo: Algo,
+    /// Print the result as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+impl Options {
+    fn seed(&self) -> SeedDescriptor {
+        match &self.seed {
+            Some(seed) => SeedDescriptor::Seed(seed.as_bytes().to_vec()),
+            None => SeedDescriptor::Entropy,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Algo {
+    /// `fastrand` based shuffle, not predictive
+    Fastrand,
+    /// Non-cryptographic shuffle
+    Modern,
+    /// ChaCha20 based shuffle
+    Crypto,
+    /// ChaCha20 based shuffle that runs in constant time
+    ConstantTime,
+}
+
+impl Algo {
+    fn shuffle(self) -> Algorithm {
+        match self {
+            Algo::Fastrand => Algorithm::FastrandShuffle,
+            Algo::Modern => Algorithm::ModernShuffle,
+            Algo::Crypto => Algorithm::CryptoModernShuffle,
+            Algo::ConstantTime => Algorithm::ConstantTimeShuffle,
+        }
+    }
+
+    fn predictive(self, batch: bool) -> Result<Algorithm, String> {
+        match (self, batch) {
+            (Algo::Modern, false) => Ok(Algorithm::PredictiveShuffle),
+            (Algo::Modern, true) => Ok(Algorithm::BatchPredictiveShuffle),
+            (Algo::Crypto, false) => Ok(Algorithm::CryptoPredictiveShuffle),
+            (Algo::Crypto, true) => Ok(Algorithm::CryptoBatchPredictiveShuffle),
+            (Algo::ConstantTime, false) => Ok(Algorithm::ConstantTimePredictiveShuffle),
+            (Algo::ConstantTime, true) => Err("constant-time has no batch variant".to_string()),
+            (Algo::Fastrand, _) => Err("fastrand has no predictive variant".to_string()),
+        }
+    }
+}
+
+fn read_input(file: Option<&str>) -> io::Result<String> {
+    match file {
+        None | Some("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => fs::read_to_string(path),
+    }
+}
+
+fn shuffle_lines(mut lines: Vec<String>, options: &Options) -> Vec<String> {
+    let seed = options.seed.as_ref().map(|seed| seed.as_bytes());
+    match (options.algo, seed) {
+        (Algo::Fastrand, None) => {
+            lines.fastrand_shuffle();
+            lines
+        }
+        (Algo::Fastrand, Some(seed)) => {
+            lines.fastrand_shuffle_from_seed_ref(seed);
+            lines
+        }
+        (Algo::Modern, None) => lines.modern_shuffle(),
+        (Algo::Modern, Some(seed)) => lines.modern_shuffle_from_seed_ref(seed),
+        (Algo::Crypto, None) => lines.crypto_modern_shuffle(),
+        (Algo::Crypto, Some(seed)) => lines.crypto_modern_shuffle_from_seed_ref(seed),
+        (Algo::ConstantTime, seed) => {
+            // lines are not `ConditionallySelectable`, so shuffle their indices instead
+            let permutation = match seed {
+                None => gen_constant_time_shuffle(lines.len()),
+                Some(seed) => gen_constant_time_shuffle_from_seed(lines.len(), seed),
+            };
+            let mut lines: Vec<Option<String>> = lines.into_iter().map(Some).collect();
+            permutation
+                .into_iter()
+                .map(|i| lines[i].take().expect("permutation repeats an index"))
+                .collect()
+        }
+    }
+}
+
+fn print_record(record: &ShuffleRecord, json: bool) -> Result<(), Box<dyn Error>> {
+    if json {
+        println!("{}", serde_json::to_string(record)?);
+        return Ok(());
+    }
+    match &record.result {
+        ShuffleResult::Permutation(permutation) => {
+            for item in permutation.as_slice() {
+                println!("{}", item);
+            }
+        }
+        ShuffleResult::Positions(positions) => {
+            for (from, to) in positions.iter() {
+                println!("{} {}", from, to);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn verify(file: Option<&str>, entrants: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let input = read_input(file)?;
+    if input.trim_start().starts_with('{') {
+        let record: ShuffleRecord = serde_json::from_str(&input)?;
+        record.verify()?;
+    } else {
+        let transcript: Transcript = input.parse()?;
+        let entrants = entrants.ok_or("transcripts need --entrants")?;
+        let entrants = read_input(Some(entrants))?;
+        let entrants: Vec<&str> = entrants.lines().collect();
+        verify_transcript(&transcript, &entrants)?;
+    }
+    Ok(())
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    match cli.command {
+        Command::Shuffle { file, options } => {
+            let input = read_input(file.as_deref())?;
+            let lines = input.lines().map(str::to_string).collect();
+            let lines = shuffle_lines(lines, &options);
+            if options.json {
+                println!("{}", serde_json::to_string(&lines)?);
+            } else {
+                for line in lines {
+                    println!("{}", line);
+                }
+            }
+        }
+        Command::Gen { size, options } => {
+            let record = ShuffleRecord::new(ShuffleRequest {
+                algorithm: options.algo.shuffle(),
+                size,
+                batch: None,
+                positions: vec![],
+                seed: options.seed(),
+            })?;
+            print_record(&record, options.json)?;
+        }
+        Command::Predict {
+            size,
+            positions,
+            batch,
+            options,
+        } => {
+            let record = ShuffleRecord::new(ShuffleRequest {
+                algorithm: options.algo.predictive(batch.is_some())?,
+                size,
+                batch,
+                positions,
+                seed: options.seed(),
+            })?;
+            print_record(&record, options.json)?;
+        }
+        Command::Verify {
+            file,
+            entrants,
+            json,
+        } => {
+            let outcome = verify(file.as_deref(), entrants.as_deref());
+            match (&outcome, json) {
+                (Ok(()), true) => println!(r#"{{"valid":true}}"#),
+                (Ok(()), false) => println!("ok"),
+                (Err(e), true) => println!(
+                    "{}",
+                    serde_json::json!({ "valid": false, "error": e.to_string() })
+                ),
+                (Err(_), false) => {}
+            }
+            return outcome;
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
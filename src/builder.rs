@@ -0,0 +1,465 @@
+//! # builder
+//! A [`ShuffleBuilder`] collects the options spread across the `gen_*` function names (backend,
+//! seed, batch size, algorithm version and output format) and runs them through one of three
+//! terminals: [`ShuffleBuilder::permutation`], [`ShuffleBuilder::predict`] and
+//! [`ShuffleBuilder::shuffle`].
+//!
+//! [`Version::V1`] dispatches to the existing functions, so a builder gives the same results as
+//! the function its options name. [`Version::V2`] draws with a [`Shuffler`], whose predictions
+//! are the positions its permutations move items to.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! # #[cfg(feature = "chacha")]
+//! # {
+//! use predictive_shuffle::builder::{Backend, Version};
+//! use predictive_shuffle::ShuffleBuilder;
+//!
+//! let builder = ShuffleBuilder::new(100)
+//!     .backend(Backend::ChaCha)
+//!     .version(Version::V2)
+//!     .seed(b"seed phrase");
+//! let shuffled_vec = builder.permutation().unwrap();
+//! let new_positions = builder.predict(&[1, 5]).unwrap();
+//! let new_positions = new_positions.as_aligned().unwrap();
+//! assert_eq!(shuffled_vec[new_positions[0]], 1);
+//! assert_eq!(shuffled_vec[new_positions[1]], 5);
+//! # }
+//! ```
+
+use crate::ordered;
+use crate::shuffler::ShuffleRng;
+#[cfg(feature = "chacha")]
+use crate::CryptoRng;
+use crate::{check_batch, check_positions, Seed, Shuffler};
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Errors returned by the terminals of a [`ShuffleBuilder`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuilderError {
+    /// A position is not below the size, or is repeated
+    InvalidPosition(usize),
+    /// The batch size is zero or larger than the size
+    InvalidBatch(usize),
+    /// A batch size was given for a permutation, a constant time prediction or [`Version::V2`]
+    UnsupportedBatch,
+    /// [`Version::V2`] was asked for with the constant time backend
+    UnsupportedVersion,
+    /// No seed was given, and this build has no source of entropy
+    EntropyUnavailable,
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuilderError::InvalidPosition(position) => {
+                write!(f, "position {} is out of range or repeated", position)
+            }
+            BuilderError::InvalidBatch(batch) => {
+                write!(f, "batch size {} is zero or larger than the size", batch)
+            }
+            BuilderError::UnsupportedBatch => write!(
+                f,
+                "batch sizes only apply to version 1 predictions of the fastrand and chacha backends"
+            ),
+            BuilderError::UnsupportedVersion => {
+                write!(f, "the constant time backend only has version 1")
+            }
+            BuilderError::EntropyUnavailable => {
+                write!(
+                    f,
+                    "unseeded builders need the `js` feature on this target"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for BuilderError {}
+
+/// Random number generator a [`ShuffleBuilder`] draws with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Backend {
+    /// [`fastrand::Rng`], as in the `modern_*`, `predictive_*` and `batch_predictive_*` functions
+    #[cfg(feature = "fastrand")]
+    #[cfg_attr(feature = "fastrand", default)]
+    Fastrand,
+    /// [`CryptoRng`], as in the `crypto_*` functions
+    #[cfg(feature = "chacha")]
+    #[cfg_attr(not(feature = "fastrand"), default)]
+    ChaCha,
+    /// [`CryptoRng`] through the data-oblivious [`crate::constant_time`] functions
+    #[cfg(feature = "chacha")]
+    ConstantTime,
+}
+
+/// Algorithm version a [`ShuffleBuilder`] runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Version {
+    /// The `gen_*` functions: `modern_*` permutations and `predictive_*` predictions, which are
+    /// separate algorithms
+    #[default]
+    V1,
+    /// A [`Shuffler`], whose predictions match its permutations from the same seed
+    V2,
+}
+
+/// Shape of the result of [`ShuffleBuilder::predict`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// New positions aligned with the requested positions, as in the `ordered::*_into` functions
+    #[default]
+    Aligned,
+    /// A `BTreeMap` from position to new position, as in the `ordered::*_ordered` functions
+    Ordered,
+    /// A `HashMap` from position to new position, as in the `gen_*_predictive_shuffle` functions
+    #[cfg(feature = "std")]
+    HashMap,
+}
+
+/// New positions predicted by a [`ShuffleBuilder`], in the requested [`Format`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prediction {
+    /// See [`Format::Aligned`]
+    Aligned(Vec<usize>),
+    /// See [`Format::Ordered`]
+    Ordered(BTreeMap<usize, usize>),
+    /// See [`Format::HashMap`]
+    #[cfg(feature = "std")]
+    HashMap(HashMap<usize, usize>),
+}
+
+impl Prediction {
+    /// New positions aligned with the requested positions, if asked for
+    pub fn as_aligned(&self) -> Option<&[usize]> {
+        match self {
+            Prediction::Aligned(new_positions) => Some(new_positions),
+            _ => None,
+        }
+    }
+
+    /// New positions ordered by position, if asked for
+    pub fn as_ordered(&self) -> Option<&BTreeMap<usize, usize>> {
+        match self {
+            Prediction::Ordered(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// New positions by position, if asked for
+    #[cfg(feature = "std")]
+    pub fn as_hash_map(&self) -> Option<&HashMap<usize, usize>> {
+        match self {
+            Prediction::HashMap(map) => Some(map),
+            _ => None,
+        }
+    }
+}
+
+impl Format {
+    fn collect(self, positions: &[usize], new_positions: Vec<usize>) -> Prediction {
+        match self {
+            Format::Aligned => Prediction::Aligned(new_positions),
+            Format::Ordered => {
+                Prediction::Ordered(positions.iter().copied().zip(new_positions).collect())
+            }
+            #[cfg(feature = "std")]
+            Format::HashMap => {
+                Prediction::HashMap(positions.iter().copied().zip(new_positions).collect())
+            }
+        }
+    }
+}
+
+/// Options for a shuffle of `size` items, or of a vector of data
+///
+/// Options left unset default to [`Backend::default`], entropy, no batch, [`Version::V1`] and
+/// [`Format::Aligned`]. Terminals check their options and return a [`BuilderError`] rather than
+/// panic.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// # #[cfg(all(feature = "std", feature = "fastrand"))]
+/// # {
+/// use predictive_shuffle::builder::{Backend, Format};
+/// use predictive_shuffle::{gen_batch_predictive_shuffle_from_seed_ref, ShuffleBuilder};
+///
+/// let new_positions = ShuffleBuilder::new(100)
+///     .backend(Backend::Fastrand)
+///     .seed(b"seed phrase")
+///     .batch(4)
+///     .format(Format::HashMap)
+///     .predict(&[1, 5])
+///     .unwrap();
+/// let expected = gen_batch_predictive_shuffle_from_seed_ref(100, 4, [1, 5], b"seed phrase");
+/// assert_eq!(new_positions.as_hash_map(), Some(&expected));
+///
+/// let shuffled = ShuffleBuilder::from_data(vec!["a", "b", "c"])
+///     .seed(b"seed phrase")
+///     .shuffle()
+///     .unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShuffleBuilder<T = usize> {
+    size: usize,
+    items: Items<T>,
+    backend: Backend,
+    seed: Option<Seed>,
+    batch: Option<usize>,
+    version: Version,
+    format: Format,
+}
+
+/// Items a [`ShuffleBuilder`] shuffles
+#[derive(Debug, Clone)]
+enum Items<T> {
+    /// `0..size`, made by the function only when shuffled, so that [`ShuffleBuilder::new`] does
+    /// not allocate
+    Range(fn(usize) -> T),
+    /// The items passed to [`ShuffleBuilder::from_data`]
+    Data(Vec<T>),
+}
+
+impl ShuffleBuilder<usize> {
+    /// Shuffle `0..size`
+    pub fn new(size: usize) -> Self {
+        ShuffleBuilder::with_items(size, Items::Range(|i| i))
+    }
+}
+
+impl<T> ShuffleBuilder<T> {
+    /// Shuffle the items of `data`
+    pub fn from_data(data: Vec<T>) -> Self {
+        ShuffleBuilder::with_items(data.len(), Items::Data(data))
+    }
+
+    fn with_items(size: usize, items: Items<T>) -> Self {
+        ShuffleBuilder {
+            size,
+            items,
+            backend: Backend::default(),
+            seed: None,
+            batch: None,
+            version: Version::default(),
+            format: Format::default(),
+        }
+    }
+
+    /// Draw with `backend`
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Seed the same way as the `*_from_seed_ref` functions, instead of from entropy
+    pub fn seed(mut self, seed: impl AsRef<[u8]>) -> Self {
+        self.seed = Some(Seed::new(seed.as_ref().to_vec()));
+        self
+    }
+
+    /// Predict with the `batch_predictive_*` functions, drawing `size / batch` random numbers
+    pub fn batch(mut self, batch: usize) -> Self {
+        self.batch = Some(batch);
+        self
+    }
+
+    /// Run the algorithms of `version`
+    pub fn version(mut self, version: Version) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Return predictions as `format`
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Number of items shuffled
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Generate a shuffled vector of `0..size`
+    pub fn permutation(&self) -> Result<Vec<usize>, BuilderError> {
+        let size = self.size();
+        if self.batch.is_some() {
+            return Err(BuilderError::UnsupportedBatch);
+        }
+        let seed = self.seed_bytes()?;
+
+        let permutation = match (self.version, self.backend, seed) {
+            #[cfg(all(feature = "fastrand", entropy))]
+            (Version::V1, Backend::Fastrand, None) => crate::gen_modern_shuffle(size),
+            #[cfg(feature = "fastrand")]
+            (Version::V1, Backend::Fastrand, Some(seed)) => {
+                crate::gen_modern_shuffle_from_seed_ref(size, seed)
+            }
+            #[cfg(all(feature = "chacha", entropy))]
+            (Version::V1, Backend::ChaCha, None) => crate::gen_crypto_modern_shuffle(size),
+            #[cfg(feature = "chacha")]
+            (Version::V1, Backend::ChaCha, Some(seed)) => {
+                crate::gen_crypto_modern_shuffle_from_seed_ref(size, seed)
+            }
+            #[cfg(all(feature = "chacha", entropy))]
+            (Version::V1, Backend::ConstantTime, None) => {
+                crate::constant_time::gen_constant_time_shuffle(size)
+            }
+            #[cfg(feature = "chacha")]
+            (Version::V1, Backend::ConstantTime, Some(seed)) => {
+                crate::constant_time::gen_constant_time_shuffle_from_seed(size, seed)
+            }
+            #[cfg(feature = "fastrand")]
+            (Version::V2, Backend::Fastrand, seed) => shuffler::<fastrand::Rng>(seed).gen(size),
+            #[cfg(feature = "chacha")]
+            (Version::V2, Backend::ChaCha, seed) => shuffler::<CryptoRng>(seed).gen(size),
+            #[cfg(feature = "chacha")]
+            (Version::V2, Backend::ConstantTime, _) => {
+                return Err(BuilderError::UnsupportedVersion)
+            }
+            #[cfg(not(entropy))]
+            (Version::V1, _, None) => {
+                unreachable!("unseeded builders are rejected by `seed_bytes`")
+            }
+        };
+        Ok(permutation)
+    }
+
+    /// Predict the shuffled positions of `positions` in [`ShuffleBuilder::permutation`] for
+    /// [`Version::V2`], or in the predictive function named by the options for [`Version::V1`]
+    pub fn predict(&self, positions: &[usize]) -> Result<Prediction, BuilderError> {
+        let size = self.size();
+        check_positions(size, positions).map_err(BuilderError::InvalidPosition)?;
+        if let Some(batch) = self.batch {
+            check_batch(size.max(1), batch).map_err(BuilderError::InvalidBatch)?;
+        }
+        let batch = self.batch;
+        let seed = self.seed_bytes()?;
+
+        let mut out = vec![0; positions.len()];
+        match (self.version, self.backend, batch) {
+            #[cfg(feature = "fastrand")]
+            (Version::V1, Backend::Fastrand, None) => match seed {
+                #[cfg(entropy)]
+                None => ordered::gen_predictive_shuffle_into(size, positions, &mut out),
+                #[cfg(not(entropy))]
+                None => unreachable!("unseeded builders are rejected by `seed_bytes`"),
+                Some(seed) => {
+                    ordered::gen_predictive_shuffle_from_seed_into(size, positions, seed, &mut out)
+                }
+            },
+            #[cfg(feature = "fastrand")]
+            (Version::V1, Backend::Fastrand, Some(batch)) => match seed {
+                #[cfg(entropy)]
+                None => {
+                    ordered::gen_batch_predictive_shuffle_into(size, batch, positions, &mut out)
+                }
+                #[cfg(not(entropy))]
+                None => unreachable!("unseeded builders are rejected by `seed_bytes`"),
+                Some(seed) => ordered::gen_batch_predictive_shuffle_from_seed_into(
+                    size, batch, positions, seed, &mut out,
+                ),
+            },
+            #[cfg(feature = "chacha")]
+            (Version::V1, Backend::ChaCha, None) => match seed {
+                #[cfg(entropy)]
+                None => ordered::gen_crypto_predictive_shuffle_into(size, positions, &mut out),
+                #[cfg(not(entropy))]
+                None => unreachable!("unseeded builders are rejected by `seed_bytes`"),
+                Some(seed) => ordered::gen_crypto_predictive_shuffle_from_seed_into(
+                    size, positions, seed, &mut out,
+                ),
+            },
+            #[cfg(feature = "chacha")]
+            (Version::V1, Backend::ChaCha, Some(batch)) => match seed {
+                #[cfg(entropy)]
+                None => ordered::gen_crypto_batch_predictive_shuffle_into(
+                    size, batch, positions, &mut out,
+                ),
+                #[cfg(not(entropy))]
+                None => unreachable!("unseeded builders are rejected by `seed_bytes`"),
+                Some(seed) => ordered::gen_crypto_batch_predictive_shuffle_from_seed_into(
+                    size, batch, positions, seed, &mut out,
+                ),
+            },
+            #[cfg(feature = "chacha")]
+            (Version::V1, Backend::ConstantTime, None) => {
+                out = match seed {
+                    #[cfg(entropy)]
+                    None => {
+                        crate::constant_time::gen_constant_time_predictive_shuffle(size, positions)
+                    }
+                    #[cfg(not(entropy))]
+                    None => unreachable!("unseeded builders are rejected by `seed_bytes`"),
+                    Some(seed) => {
+                        crate::constant_time::gen_constant_time_predictive_shuffle_from_seed(
+                            size, positions, seed,
+                        )
+                    }
+                }
+            }
+            #[cfg(feature = "chacha")]
+            (Version::V1, Backend::ConstantTime, Some(_)) => {
+                return Err(BuilderError::UnsupportedBatch)
+            }
+            #[cfg(feature = "chacha")]
+            (Version::V2, Backend::ConstantTime, _) => {
+                return Err(BuilderError::UnsupportedVersion)
+            }
+            (Version::V2, _, Some(_)) => return Err(BuilderError::UnsupportedBatch),
+            #[cfg(feature = "fastrand")]
+            (Version::V2, Backend::Fastrand, None) => {
+                out = shuffler::<fastrand::Rng>(seed).predict(size, positions)
+            }
+            #[cfg(feature = "chacha")]
+            (Version::V2, Backend::ChaCha, None) => {
+                out = shuffler::<CryptoRng>(seed).predict(size, positions)
+            }
+        }
+        Ok(self.format.collect(positions, out))
+    }
+
+    /// Shuffle the data, moving each item to its place in [`ShuffleBuilder::permutation`]
+    pub fn shuffle(self) -> Result<Vec<T>, BuilderError> {
+        let permutation = self.permutation()?;
+        let data = match self.items {
+            Items::Range(item) => return Ok(permutation.into_iter().map(item).collect()),
+            Items::Data(data) => data,
+        };
+        let mut items: Vec<Option<T>> = data.into_iter().map(Some).collect();
+        Ok(permutation
+            .into_iter()
+            .map(|i| items[i].take().expect("permutations move each item once"))
+            .collect())
+    }
+
+    fn seed_bytes(&self) -> Result<Option<&[u8]>, BuilderError> {
+        match &self.seed {
+            Some(seed) => Ok(Some(seed.as_bytes())),
+            #[cfg(entropy)]
+            None => Ok(None),
+            #[cfg(not(entropy))]
+            None => Err(BuilderError::EntropyUnavailable),
+        }
+    }
+}
+
+fn shuffler<R: ShuffleRng>(seed: Option<&[u8]>) -> Shuffler<R> {
+    match seed {
+        Some(seed) => Shuffler::from_seed(seed),
+        #[cfg(entropy)]
+        None => Shuffler::from_entropy(),
+        #[cfg(not(entropy))]
+        None => unreachable!("unseeded builders are rejected by `seed_bytes`"),
+    }
+}
@@ -0,0 +1,258 @@
+//! # canonical
+//! Seeded shuffles of collections without a stable iteration order, such as `HashMap` and
+//! `HashSet`.
+//!
+//! A `HashMap` visits its entries in an order that changes between processes, so collecting it
+//! into a `Vec` before a seeded shuffle gives a different result on every run. These helpers
+//! first sort the items into a canonical order, by [`Ord`] or by a key, and then apply the
+//! seeded Fisher-Yates of [`crate::Shuffle`], so the same contents and seed always give the same
+//! order. Items with equal keys are ordered among themselves by [`Ord`], so duplicate keys do not
+//! let the visiting order through.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! # #[cfg(feature = "fastrand")]
+//! # {
+//! use predictive_shuffle::canonical::shuffle_keys_from_seed;
+//! use std::collections::HashMap;
+//!
+//! let map: HashMap<&str, u32> = [("a", 1), ("b", 2), ("c", 3), ("d", 4)].into();
+//! let keys = shuffle_keys_from_seed(&map, b"seed phrase");
+//!
+//! let same_map: HashMap<&str, u32> = [("d", 4), ("c", 3), ("b", 2), ("a", 1)].into();
+//! assert_eq!(shuffle_keys_from_seed(&same_map, b"seed phrase"), keys);
+//! # }
+//! ```
+
+use crate::Shuffle;
+use std::collections::{HashMap, HashSet};
+
+/// Shuffle Items in Canonical Order from Seed
+///
+/// Sorts the items, then shuffles them with [`crate::Shuffle::modern_shuffle_from_seed_ref`].
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::canonical::shuffle_canonical_from_seed;
+/// use predictive_shuffle::Shuffle;
+///
+/// let seed = b"seed phrase";
+/// let shuffled_vec = shuffle_canonical_from_seed([3, 1, 2, 0], seed);
+/// assert_eq!(shuffled_vec, vec![0, 1, 2, 3].modern_shuffle_from_seed_ref(seed));
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn shuffle_canonical_from_seed<T: Ord>(
+    items: impl IntoIterator<Item = T>,
+    seed: impl AsRef<[u8]>,
+) -> Vec<T> {
+    let mut vec: Vec<T> = items.into_iter().collect();
+    vec.sort();
+    vec.modern_shuffle_from_seed_ref(seed)
+}
+
+/// Shuffle Items in Canonical Order of a Key from Seed
+///
+/// Sorts the items by `key`, and items with equal keys by [`Ord`], then shuffles them with
+/// [`crate::Shuffle::modern_shuffle_from_seed_ref`].
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::canonical::shuffle_canonical_by_key_from_seed;
+///
+/// let users = vec![("bob", 50), ("alice", 25)];
+/// let shuffled_vec = shuffle_canonical_by_key_from_seed(users, |user| user.0, b"seed phrase");
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn shuffle_canonical_by_key_from_seed<T: Ord, K: Ord>(
+    items: impl IntoIterator<Item = T>,
+    mut key: impl FnMut(&T) -> K,
+    seed: impl AsRef<[u8]>,
+) -> Vec<T> {
+    let mut vec: Vec<T> = items.into_iter().collect();
+    vec.sort_by(|a, b| key(a).cmp(&key(b)).then_with(|| a.cmp(b)));
+    vec.modern_shuffle_from_seed_ref(seed)
+}
+
+/// Shuffle the Keys of a Map from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::canonical::shuffle_keys_from_seed;
+/// use std::collections::HashMap;
+///
+/// let map: HashMap<u32, &str> = [(1, "a"), (2, "b"), (3, "c")].into();
+/// let keys = shuffle_keys_from_seed(&map, b"seed phrase");
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn shuffle_keys_from_seed<K: Ord, V, S>(
+    map: &HashMap<K, V, S>,
+    seed: impl AsRef<[u8]>,
+) -> Vec<&K> {
+    shuffle_canonical_from_seed(map.keys(), seed)
+}
+
+/// Shuffle the Entries of a Map from Seed, in the order of [`shuffle_keys_from_seed`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::canonical::{shuffle_entries_from_seed, shuffle_keys_from_seed};
+/// use std::collections::HashMap;
+///
+/// let map: HashMap<u32, &str> = [(1, "a"), (2, "b"), (3, "c")].into();
+/// let seed = b"seed phrase";
+/// let entries = shuffle_entries_from_seed(&map, seed);
+/// let keys: Vec<&u32> = entries.iter().map(|(key, _)| *key).collect();
+/// assert_eq!(keys, shuffle_keys_from_seed(&map, seed));
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn shuffle_entries_from_seed<K: Ord, V, S>(
+    map: &HashMap<K, V, S>,
+    seed: impl AsRef<[u8]>,
+) -> Vec<(&K, &V)> {
+    // map keys are distinct, so sorting by them alone is canonical
+    let mut entries: Vec<(&K, &V)> = map.iter().collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries.modern_shuffle_from_seed_ref(seed)
+}
+
+/// Shuffle the Items of a Set from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::canonical::shuffle_set_from_seed;
+/// use std::collections::HashSet;
+///
+/// let set: HashSet<&str> = ["a", "b", "c"].into();
+/// let items = shuffle_set_from_seed(&set, b"seed phrase");
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn shuffle_set_from_seed<T: Ord, S>(set: &HashSet<T, S>, seed: impl AsRef<[u8]>) -> Vec<&T> {
+    shuffle_canonical_from_seed(set.iter(), seed)
+}
+
+/// Cryptographically Shuffle Items in Canonical Order from Seed
+///
+/// Sorts the items, then shuffles them with
+/// [`crate::Shuffle::crypto_modern_shuffle_from_seed_ref`].
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::canonical::crypto_shuffle_canonical_from_seed;
+/// use predictive_shuffle::Shuffle;
+///
+/// let seed = b"seed phrase";
+/// let shuffled_vec = crypto_shuffle_canonical_from_seed([3, 1, 2, 0], seed);
+/// assert_eq!(shuffled_vec, vec![0, 1, 2, 3].crypto_modern_shuffle_from_seed_ref(seed));
+/// ```
+#[cfg(feature = "chacha")]
+pub fn crypto_shuffle_canonical_from_seed<T: Ord>(
+    items: impl IntoIterator<Item = T>,
+    seed: impl AsRef<[u8]>,
+) -> Vec<T> {
+    let mut vec: Vec<T> = items.into_iter().collect();
+    vec.sort();
+    vec.crypto_modern_shuffle_from_seed_ref(seed)
+}
+
+/// Cryptographically Shuffle Items in Canonical Order of a Key from Seed
+///
+/// Sorts the items by `key`, and items with equal keys by [`Ord`], then shuffles them with
+/// [`crate::Shuffle::crypto_modern_shuffle_from_seed_ref`].
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::canonical::crypto_shuffle_canonical_by_key_from_seed;
+///
+/// let users = vec![("bob", 50), ("alice", 25)];
+/// let shuffled_vec = crypto_shuffle_canonical_by_key_from_seed(users, |user| user.0, b"seed phrase");
+/// ```
+#[cfg(feature = "chacha")]
+pub fn crypto_shuffle_canonical_by_key_from_seed<T: Ord, K: Ord>(
+    items: impl IntoIterator<Item = T>,
+    mut key: impl FnMut(&T) -> K,
+    seed: impl AsRef<[u8]>,
+) -> Vec<T> {
+    let mut vec: Vec<T> = items.into_iter().collect();
+    vec.sort_by(|a, b| key(a).cmp(&key(b)).then_with(|| a.cmp(b)));
+    vec.crypto_modern_shuffle_from_seed_ref(seed)
+}
+
+/// Cryptographically Shuffle the Keys of a Map from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::canonical::crypto_shuffle_keys_from_seed;
+/// use std::collections::HashMap;
+///
+/// let map: HashMap<u32, &str> = [(1, "a"), (2, "b"), (3, "c")].into();
+/// let keys = crypto_shuffle_keys_from_seed(&map, b"seed phrase");
+/// ```
+#[cfg(feature = "chacha")]
+pub fn crypto_shuffle_keys_from_seed<K: Ord, V, S>(
+    map: &HashMap<K, V, S>,
+    seed: impl AsRef<[u8]>,
+) -> Vec<&K> {
+    crypto_shuffle_canonical_from_seed(map.keys(), seed)
+}
+
+/// Cryptographically Shuffle the Entries of a Map from Seed, in the order of
+/// [`crypto_shuffle_keys_from_seed`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::canonical::crypto_shuffle_entries_from_seed;
+/// use std::collections::HashMap;
+///
+/// let map: HashMap<u32, &str> = [(1, "a"), (2, "b"), (3, "c")].into();
+/// let entries = crypto_shuffle_entries_from_seed(&map, b"seed phrase");
+/// ```
+#[cfg(feature = "chacha")]
+pub fn crypto_shuffle_entries_from_seed<K: Ord, V, S>(
+    map: &HashMap<K, V, S>,
+    seed: impl AsRef<[u8]>,
+) -> Vec<(&K, &V)> {
+    // map keys are distinct, so sorting by them alone is canonical
+    let mut entries: Vec<(&K, &V)> = map.iter().collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries.crypto_modern_shuffle_from_seed_ref(seed)
+}
+
+/// Cryptographically Shuffle the Items of a Set from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::canonical::crypto_shuffle_set_from_seed;
+/// use std::collections::HashSet;
+///
+/// let set: HashSet<&str> = ["a", "b", "c"].into();
+/// let items = crypto_shuffle_set_from_seed(&set, b"seed phrase");
+/// ```
+#[cfg(feature = "chacha")]
+pub fn crypto_shuffle_set_from_seed<T: Ord, S>(
+    set: &HashSet<T, S>,
+    seed: impl AsRef<[u8]>,
+) -> Vec<&T> {
+    crypto_shuffle_canonical_from_seed(set.iter(), seed)
+}
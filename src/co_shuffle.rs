@@ -0,0 +1,320 @@
+//! # co_shuffle
+//! Shuffles of several parallel slices, such as ids, features and labels, with one permutation.
+//!
+//! Shuffling each slice with the same seed only lines them up while their lengths agree. The
+//! `co_shuffle*` functions draw a single permutation, check every slice against it before moving
+//! any item, and return a [`CoShuffleError`] instead of shuffling some slices and not others.
+//! Items move as in [`crate::Shuffle`], so co-shuffling from a seed gives each slice the order
+//! that shuffling it alone from that seed would.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! # #[cfg(feature = "chacha")]
+//! # {
+//! use predictive_shuffle::co_shuffle::crypto_co_shuffle_from_seed_ref;
+//!
+//! let mut ids: Vec<u64> = (0..10).collect();
+//! let mut labels: Vec<String> = (0..10).map(|i| format!("label {}", i)).collect();
+//! crypto_co_shuffle_from_seed_ref(&mut [&mut ids, &mut labels], b"seed phrase").unwrap();
+//! for (id, label) in ids.iter().zip(&labels) {
+//!     assert_eq!(label, &format!("label {}", id));
+//! }
+//! # }
+//! ```
+
+#[cfg(all(feature = "chacha", entropy))]
+use crate::constant_time::gen_constant_time_shuffle;
+#[cfg(feature = "chacha")]
+use crate::constant_time::gen_constant_time_shuffle_from_seed;
+#[cfg(all(feature = "chacha", entropy))]
+use crate::gen_crypto_modern_shuffle;
+#[cfg(feature = "chacha")]
+use crate::gen_crypto_modern_shuffle_from_seed_ref;
+#[cfg(all(feature = "fastrand", entropy))]
+use crate::gen_modern_shuffle;
+#[cfg(feature = "fastrand")]
+use crate::gen_modern_shuffle_from_seed_ref;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Errors returned when co-shuffling slices, or arrays along an axis
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoShuffleError {
+    /// The slices or arrays differ in length
+    LengthMismatch {
+        /// Length of the first slice or array
+        expected: usize,
+        /// Length of the one that differs
+        found: usize,
+    },
+    /// The axis is out of bounds for one of the arrays
+    AxisOutOfBounds(usize),
+}
+
+impl fmt::Display for CoShuffleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoShuffleError::LengthMismatch { expected, found } => {
+                write!(f, "lengths differ ({} and {})", expected, found)
+            }
+            CoShuffleError::AxisOutOfBounds(axis) => write!(f, "axis {} is out of bounds", axis),
+        }
+    }
+}
+
+impl core::error::Error for CoShuffleError {}
+
+/// Items that can be reordered in place, so slices of different item types can be co-shuffled
+/// together
+pub trait Permute {
+    /// Number of items
+    fn item_count(&self) -> usize;
+
+    /// Move item `permutation[k]` to position `k`
+    ///
+    /// `permutation` must be a permutation of `0..self.item_count()`. Panics if it has another
+    /// length, an index out of range or a repeated index.
+    fn permute(&mut self, permutation: &[usize]);
+}
+
+impl<T> Permute for [T] {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+
+    fn permute(&mut self, permutation: &[usize]) {
+        assert_eq!(
+            self.len(),
+            permutation.len(),
+            "permutation must have one index per item"
+        );
+
+        let mut seen = vec![false; self.len()];
+        for &index in permutation {
+            assert!(!seen[index], "permutation repeats index {}", index);
+            seen[index] = true;
+        }
+
+        // follow each cycle of the permutation once, pulling every item into place
+        for start in 0..self.len() {
+            let mut i = start;
+            while seen[i] {
+                seen[i] = false;
+                let next = permutation[i];
+                if next == start {
+                    break;
+                }
+                self.swap(i, next);
+                i = next;
+            }
+        }
+    }
+}
+
+impl<T> Permute for Vec<T> {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+
+    fn permute(&mut self, permutation: &[usize]) {
+        self.as_mut_slice().permute(permutation)
+    }
+}
+
+impl<T, const N: usize> Permute for [T; N] {
+    fn item_count(&self) -> usize {
+        N
+    }
+
+    fn permute(&mut self, permutation: &[usize]) {
+        self.as_mut_slice().permute(permutation)
+    }
+}
+
+impl<T> Permute for &mut [T] {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+
+    fn permute(&mut self, permutation: &[usize]) {
+        (**self).permute(permutation)
+    }
+}
+
+/// Co-shuffle Slices
+///
+/// Every slice is moved by one [`crate::gen_modern_shuffle`] permutation.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::co_shuffle::co_shuffle;
+///
+/// let mut ids: Vec<u64> = (0..10).collect();
+/// let mut labels: Vec<u8> = (0..10).collect();
+/// co_shuffle(&mut [&mut ids, &mut labels]).unwrap();
+/// ```
+#[cfg(all(feature = "fastrand", entropy))]
+pub fn co_shuffle(items: &mut [&mut dyn Permute]) -> Result<(), CoShuffleError> {
+    co_permute(items, gen_modern_shuffle)
+}
+
+/// Co-shuffle Slices from Seed
+///
+/// Every slice is moved by one [`crate::gen_modern_shuffle_from_seed_ref`] permutation.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::co_shuffle::{co_shuffle_from_seed_ref, CoShuffleError};
+/// use predictive_shuffle::Shuffle;
+///
+/// let seed = b"seed phrase";
+/// let mut ids: Vec<u64> = (0..10).collect();
+/// let mut labels: Vec<u8> = (0..10).collect();
+/// co_shuffle_from_seed_ref(&mut [&mut ids, &mut labels], seed).unwrap();
+/// assert_eq!(ids, (0..10).collect::<Vec<u64>>().modern_shuffle_from_seed_ref(seed));
+///
+/// let mut short: Vec<u8> = (0..9).collect();
+/// let result = co_shuffle_from_seed_ref(&mut [&mut ids, &mut short], seed);
+/// assert_eq!(result, Err(CoShuffleError::LengthMismatch { expected: 10, found: 9 }));
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn co_shuffle_from_seed_ref(
+    items: &mut [&mut dyn Permute],
+    seed: impl AsRef<[u8]>,
+) -> Result<(), CoShuffleError> {
+    co_permute(items, |size| gen_modern_shuffle_from_seed_ref(size, seed))
+}
+
+/// Cryptographically Co-shuffle Slices
+///
+/// Every slice is moved by one [`crate::gen_crypto_modern_shuffle`] permutation.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::co_shuffle::crypto_co_shuffle;
+///
+/// let mut ids: Vec<u64> = (0..10).collect();
+/// let mut labels: Vec<u8> = (0..10).collect();
+/// crypto_co_shuffle(&mut [&mut ids, &mut labels]).unwrap();
+/// ```
+#[cfg(all(feature = "chacha", entropy))]
+pub fn crypto_co_shuffle(items: &mut [&mut dyn Permute]) -> Result<(), CoShuffleError> {
+    co_permute(items, gen_crypto_modern_shuffle)
+}
+
+/// Cryptographically Co-shuffle Slices from Seed
+///
+/// Every slice is moved by one [`crate::gen_crypto_modern_shuffle_from_seed_ref`] permutation.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::co_shuffle::crypto_co_shuffle_from_seed_ref;
+///
+/// let mut ids: Vec<u64> = (0..10).collect();
+/// let mut labels = [0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+/// crypto_co_shuffle_from_seed_ref(&mut [&mut ids, &mut labels], b"seed phrase").unwrap();
+/// ```
+#[cfg(feature = "chacha")]
+pub fn crypto_co_shuffle_from_seed_ref(
+    items: &mut [&mut dyn Permute],
+    seed: impl AsRef<[u8]>,
+) -> Result<(), CoShuffleError> {
+    co_permute(items, |size| {
+        gen_crypto_modern_shuffle_from_seed_ref(size, seed)
+    })
+}
+
+/// Co-shuffle Slices with a Constant Time Permutation
+///
+/// Every slice is moved by one [`crate::constant_time::gen_constant_time_shuffle`] permutation.
+/// The permutation is drawn obliviously, but moving the items by it is not constant time.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::co_shuffle::constant_time_co_shuffle;
+///
+/// let mut ids: Vec<u64> = (0..10).collect();
+/// let mut labels: Vec<u8> = (0..10).collect();
+/// constant_time_co_shuffle(&mut [&mut ids, &mut labels]).unwrap();
+/// ```
+#[cfg(all(feature = "chacha", entropy))]
+pub fn constant_time_co_shuffle(items: &mut [&mut dyn Permute]) -> Result<(), CoShuffleError> {
+    co_permute(items, gen_constant_time_shuffle)
+}
+
+/// Co-shuffle Slices with a Constant Time Permutation from Seed
+///
+/// Every slice is moved by one [`crate::constant_time::gen_constant_time_shuffle_from_seed`]
+/// permutation. The permutation is drawn obliviously, but moving the items by it is not
+/// constant time.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::co_shuffle::constant_time_co_shuffle_from_seed_ref;
+///
+/// let mut ids: Vec<u64> = (0..10).collect();
+/// let mut labels: Vec<u8> = (0..10).collect();
+/// constant_time_co_shuffle_from_seed_ref(&mut [&mut ids, &mut labels], b"seed phrase").unwrap();
+/// ```
+#[cfg(feature = "chacha")]
+pub fn constant_time_co_shuffle_from_seed_ref(
+    items: &mut [&mut dyn Permute],
+    seed: impl AsRef<[u8]>,
+) -> Result<(), CoShuffleError> {
+    co_permute(items, |size| {
+        gen_constant_time_shuffle_from_seed(size, seed.as_ref())
+    })
+}
+
+fn co_permute(
+    items: &mut [&mut dyn Permute],
+    gen: impl FnOnce(usize) -> Vec<usize>,
+) -> Result<(), CoShuffleError> {
+    co_permute_with(
+        items,
+        |slice| Ok(slice.item_count()),
+        gen,
+        |slice, permutation| slice.permute(permutation),
+    )
+}
+
+/// Check that every item has the same length, then draw one permutation of that length with
+/// `gen` and apply it to each of them, so on error they are all left as they were
+pub(crate) fn co_permute_with<P: ?Sized>(
+    items: &mut [&mut P],
+    len: impl Fn(&P) -> Result<usize, CoShuffleError>,
+    gen: impl FnOnce(usize) -> Vec<usize>,
+    permute: impl Fn(&mut P, &[usize]),
+) -> Result<(), CoShuffleError> {
+    let mut size = None;
+    for item in items.iter() {
+        let found = len(item)?;
+        match size {
+            Some(expected) if expected != found => {
+                return Err(CoShuffleError::LengthMismatch { expected, found })
+            }
+            _ => size = Some(found),
+        }
+    }
+
+    let permutation = gen(size.unwrap_or(0));
+    for item in items.iter_mut() {
+        permute(item, &permutation);
+    }
+    Ok(())
+}
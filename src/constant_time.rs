@@ -0,0 +1,236 @@
+//! # constant_time
+//! Constant-time cryptographic shuffles for secret-dependent workloads such as card dealing.
+//!
+//! The modern Fisher-Yates functions draw indices with rejection sampling and move items with
+//! data-dependent `swap_remove` calls, and the predictive functions branch on whether a slot is
+//! tracked, so their running time leaks information about the shuffle and the requested
+//! positions. The functions in this module instead tag every item with a 128-bit random key from
+//! ['rand_chacha::ChaCha20Rng'] and sort by key with a bitonic sorting network built from
+//! branch-free compare-and-swap operations. The sequence of operations and memory accesses
+//! depends only on the number of items (and, for the predictive functions, the number of
+//! positions), never on the keys, the items, or the values of the positions.
+//!
+//! Results differ from the other `crypto_*` functions for the same seed, but seeds are turned
+//! into a generator the same way, see [`CryptoRng::from_seed_ref`].
+
+use crate::CryptoRng;
+use alloc::vec::Vec;
+use rand::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater};
+
+/// An item tagged with its random sort key
+#[derive(Clone, Copy)]
+struct Slot<T> {
+    // padding slots sort after every real item
+    pad: u64,
+    hi: u64,
+    lo: u64,
+    value: T,
+}
+
+impl<T: ConditionallySelectable> Slot<T> {
+    fn greater(&self, other: &Self) -> Choice {
+        let hi = self.hi.ct_gt(&other.hi) | (self.hi.ct_eq(&other.hi) & self.lo.ct_gt(&other.lo));
+        self.pad.ct_gt(&other.pad) | (self.pad.ct_eq(&other.pad) & hi)
+    }
+
+    fn swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        u64::conditional_swap(&mut a.pad, &mut b.pad, choice);
+        u64::conditional_swap(&mut a.hi, &mut b.hi, choice);
+        u64::conditional_swap(&mut a.lo, &mut b.lo, choice);
+        T::conditional_swap(&mut a.value, &mut b.value, choice);
+    }
+}
+
+/// Shuffle a slice in constant time with ['rand_chacha::ChaCha20Rng']
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::constant_time::constant_time_shuffle;
+///
+/// let mut deck: Vec<u8> = (0..52).collect();
+/// constant_time_shuffle(&mut deck);
+/// ```
+#[cfg(entropy)]
+pub fn constant_time_shuffle<T: ConditionallySelectable>(items: &mut [T]) {
+    let mut rng = CryptoRng::from_entropy();
+    oblivious_shuffle(items, &mut rng);
+}
+
+/// Shuffle a slice in constant time from a seed with ['rand_chacha::ChaCha20Rng']
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::constant_time::constant_time_shuffle_from_seed;
+///
+/// let mut deck: Vec<u8> = (0..52).collect();
+/// let seed = b"seed phrase";
+/// constant_time_shuffle_from_seed(&mut deck, seed);
+/// ```
+pub fn constant_time_shuffle_from_seed<T: ConditionallySelectable>(
+    items: &mut [T],
+    seed: impl AsRef<[u8]>,
+) {
+    let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+    oblivious_shuffle(items, &mut rng);
+}
+
+/// Generate a shuffled vector in constant time with ['rand_chacha::ChaCha20Rng']
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::constant_time::gen_constant_time_shuffle;
+///
+/// let size = 10;
+/// let shuffled_vec = gen_constant_time_shuffle(size);
+/// ```
+#[cfg(entropy)]
+pub fn gen_constant_time_shuffle(size: usize) -> Vec<usize> {
+    let mut rng = CryptoRng::from_entropy();
+    oblivious_permutation(size, &mut rng)
+}
+
+/// Generate a shuffled vector in constant time from a seed with ['rand_chacha::ChaCha20Rng']
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::constant_time::gen_constant_time_shuffle_from_seed;
+///
+/// let size = 10;
+/// let seed = b"seed phrase";
+/// let shuffled_vec = gen_constant_time_shuffle_from_seed(size, seed);
+/// ```
+pub fn gen_constant_time_shuffle_from_seed(size: usize, seed: impl AsRef<[u8]>) -> Vec<usize> {
+    let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+    oblivious_permutation(size, &mut rng)
+}
+
+/// Predict shuffled positions in constant time with ['rand_chacha::ChaCha20Rng']
+///
+/// The result is aligned with `positions`, since building a `HashMap` would itself branch on the
+/// position values. Out of range positions map to `size`.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::constant_time::gen_constant_time_predictive_shuffle;
+///
+/// let size = 100;
+/// let positions = vec![1,5];
+/// let new_positions = gen_constant_time_predictive_shuffle(size, &positions);
+/// ```
+#[cfg(entropy)]
+pub fn gen_constant_time_predictive_shuffle(size: usize, positions: &[usize]) -> Vec<usize> {
+    let mut rng = CryptoRng::from_entropy();
+    let permutation = oblivious_permutation(size, &mut rng);
+    oblivious_lookup(&permutation, positions)
+}
+
+/// Predict shuffled positions in constant time from a seed with ['rand_chacha::ChaCha20Rng']
+///
+/// The result is aligned with `positions`, and agrees with
+/// [`gen_constant_time_shuffle_from_seed`] for the same seed. Out of range positions map to
+/// `size`.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::constant_time::{
+///     gen_constant_time_predictive_shuffle_from_seed, gen_constant_time_shuffle_from_seed,
+/// };
+///
+/// let size = 100;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let new_positions = gen_constant_time_predictive_shuffle_from_seed(size, &positions, seed);
+/// let shuffled_vec = gen_constant_time_shuffle_from_seed(size, seed);
+/// assert_eq!(shuffled_vec[new_positions[0]], 1);
+/// assert_eq!(shuffled_vec[new_positions[1]], 5);
+/// ```
+pub fn gen_constant_time_predictive_shuffle_from_seed(
+    size: usize,
+    positions: &[usize],
+    seed: impl AsRef<[u8]>,
+) -> Vec<usize> {
+    let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+    let permutation = oblivious_permutation(size, &mut rng);
+    oblivious_lookup(&permutation, positions)
+}
+
+/// Constant-time shuffle of `0..size`, drawing from `rng`
+pub(crate) fn oblivious_permutation(size: usize, rng: &mut CryptoRng) -> Vec<usize> {
+    let mut vec: Vec<u64> = (0..size as u64).collect();
+    oblivious_shuffle(&mut vec, rng);
+    vec.into_iter().map(|item| item as usize).collect()
+}
+
+/// Find the index of every position in the permutation by scanning all of it
+pub(crate) fn oblivious_lookup(permutation: &[usize], positions: &[usize]) -> Vec<usize> {
+    positions
+        .iter()
+        .map(|&position| {
+            let position = position as u64;
+            let mut found = permutation.len() as u64;
+            for (i, &item) in permutation.iter().enumerate() {
+                found.conditional_assign(&(i as u64), (item as u64).ct_eq(&position));
+            }
+            found as usize
+        })
+        .collect()
+}
+
+fn oblivious_shuffle<T: ConditionallySelectable>(items: &mut [T], rng: &mut CryptoRng) {
+    if items.len() < 2 {
+        return;
+    }
+
+    // pad to a power of two, reusing the first item as filler for the padding slots
+    let filler = items[0];
+    let mut slots: Vec<Slot<T>> = (0..items.len().next_power_of_two())
+        .map(|i| Slot {
+            pad: (i >= items.len()) as u64,
+            hi: rng.next_u64(),
+            lo: rng.next_u64(),
+            value: items.get(i).copied().unwrap_or(filler),
+        })
+        .collect();
+
+    bitonic_sort(&mut slots);
+    for (item, slot) in items.iter_mut().zip(slots) {
+        *item = slot.value;
+    }
+}
+
+/// Ascending bitonic sort of a power-of-two length slice, whose comparisons depend only on the
+/// slice length
+fn bitonic_sort<T: ConditionallySelectable>(slots: &mut [Slot<T>]) {
+    let size = slots.len();
+    let mut k = 2;
+    while k <= size {
+        let mut j = k / 2;
+        while j > 0 {
+            for i in 0..size {
+                let l = i ^ j;
+                if l > i {
+                    let (low, high) = slots.split_at_mut(l);
+                    let (a, b) = (&mut low[i], &mut high[0]);
+                    let descending = Choice::from((i & k != 0) as u8);
+                    let swap = a.greater(b) ^ descending;
+                    Slot::swap(a, b, swap);
+                }
+            }
+            j /= 2;
+        }
+        k *= 2;
+    }
+}
@@ -0,0 +1,342 @@
+//! # external
+//! An external-memory shuffle for record files that do not fit in memory.
+//!
+//! Records are read from any [`Read`] source, scattered into temporary bucket files by seeded
+//! random keys, and each bucket is then shuffled in memory with the modern Fisher-Yates algorithm
+//! before being written to the [`Write`] sink. Buckets that would exceed the memory budget are
+//! scattered again, and the read and write buffers are sized from the budget too, so peak memory
+//! stays bounded regardless of the input size. Enabled with the `external` feature.
+
+use crate::rng::Draw;
+use crate::CryptoRng;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+/// Default memory budget for a single in-memory bucket shuffle (256 MiB)
+pub const DEFAULT_MEMORY_BUDGET: usize = 256 * 1024 * 1024;
+
+/// Default number of temporary buckets records are scattered into per pass
+pub const DEFAULT_FAN_OUT: usize = 64;
+
+/// Bytes of bookkeeping counted against the memory budget for every record held in memory
+const RECORD_OVERHEAD: usize = std::mem::size_of::<Vec<u8>>();
+
+/// Smallest read or write buffer the fan-out is reduced to keep
+const MIN_BUFFER: usize = 512;
+
+/// Largest read or write buffer
+const MAX_BUFFER: usize = 64 * 1024;
+
+/// Buffers alive besides the bucket write buffers: the input or bucket being read, and the output
+const OTHER_BUFFERS: usize = 2;
+
+/// How records are delimited in the input source and the output sink
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// Newline (`\n`) terminated records. The terminator is not part of the record, and every
+    /// record is written back with a trailing newline.
+    Lines,
+    /// Records of exactly this many bytes
+    Fixed(usize),
+    /// Records prefixed by their length as a little-endian `u64`
+    LengthPrefixed,
+}
+
+/// External-memory shuffle of a record stream
+///
+/// The output for a given seed is reproducible as long as the input, the record format, the
+/// memory budget and the fan-out are the same.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::external::{ExternalShuffle, RecordFormat};
+///
+/// let input = b"a\nb\nc\nd\n".to_vec();
+/// let mut output = vec![];
+/// let seed = b"seed phrase";
+/// let records = ExternalShuffle::new(RecordFormat::Lines)
+///     .memory_budget(1024)
+///     .shuffle_from_seed(input.as_slice(), &mut output, seed)
+///     .unwrap();
+/// assert_eq!(records, 4);
+/// assert_eq!(output.len(), input.len());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExternalShuffle {
+    format: RecordFormat,
+    memory_budget: usize,
+    fan_out: usize,
+    temp_dir: Option<PathBuf>,
+}
+
+impl ExternalShuffle {
+    /// Create an external shuffle for the given record format, with the default memory budget,
+    /// fan-out and the system temporary directory
+    pub fn new(format: RecordFormat) -> Self {
+        ExternalShuffle {
+            format,
+            memory_budget: DEFAULT_MEMORY_BUDGET,
+            fan_out: DEFAULT_FAN_OUT,
+            temp_dir: None,
+        }
+    }
+
+    /// Set the maximum number of bytes held in memory at once, counting the read and write
+    /// buffers as well as a bucket shuffled in memory
+    ///
+    /// Budgets below one byte per buffer of a two-bucket pass are raised to that.
+    pub fn memory_budget(mut self, bytes: usize) -> Self {
+        self.memory_budget = bytes.max(2 + OTHER_BUFFERS);
+        self
+    }
+
+    /// Set the number of temporary bucket files written per scatter pass
+    ///
+    /// Fewer buckets are used if the write buffers of that many would not fit the memory budget
+    /// with at least 512 bytes each.
+    pub fn fan_out(mut self, buckets: usize) -> Self {
+        self.fan_out = buckets.max(2);
+        self
+    }
+
+    /// Set the directory temporary bucket files are created in
+    pub fn temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = Some(dir.into());
+        self
+    }
+
+    /// Shuffle all records from `reader` into `writer` with ['rand_chacha::ChaCha20Rng'],
+    /// returning the number of records written
+    #[cfg(entropy)]
+    pub fn shuffle<R: Read, W: Write>(&self, reader: R, writer: W) -> io::Result<u64> {
+        let rng = CryptoRng::from_entropy();
+        self.run(reader, writer, rng)
+    }
+
+    /// Shuffle all records from `reader` into `writer` from a seed with
+    /// ['rand_chacha::ChaCha20Rng'], returning the number of records written
+    pub fn shuffle_from_seed<R: Read, W: Write>(
+        &self,
+        reader: R,
+        writer: W,
+        seed: impl AsRef<[u8]>,
+    ) -> io::Result<u64> {
+        let rng = CryptoRng::from_seed_ref(seed.as_ref());
+        self.run(reader, writer, rng)
+    }
+
+    fn run<R: Read, W: Write>(&self, reader: R, writer: W, mut rng: CryptoRng) -> io::Result<u64> {
+        let capacity = self.buffer_capacity();
+        let mut reader = BufReader::with_capacity(capacity, reader);
+        let mut writer = BufWriter::with_capacity(capacity, writer);
+
+        let buckets = self.scatter(&mut reader, self.format, &mut rng)?;
+        drop(reader);
+
+        let mut records = 0;
+        for bucket in buckets {
+            records += self.drain(bucket, &mut writer, &mut rng)?;
+        }
+
+        writer.flush()?;
+        Ok(records)
+    }
+
+    /// Shuffle a single bucket into the writer, scattering it again if it exceeds the budget
+    fn drain<W: Write>(
+        &self,
+        bucket: Spilled,
+        writer: &mut W,
+        rng: &mut CryptoRng,
+    ) -> io::Result<u64> {
+        if bucket.records == 0 {
+            return Ok(0);
+        }
+        let records = bucket.records;
+        let capacity = self.buffer_capacity();
+        // the bucket shares the budget with its read buffer and the output buffer
+        let fits = bucket.bytes.saturating_add(OTHER_BUFFERS * capacity) <= self.memory_budget;
+        let mut reader = BufReader::with_capacity(capacity, bucket.file);
+
+        if fits || records == 1 {
+            let mut items = Vec::with_capacity(records as usize);
+            while let Some(record) = read_record(&mut reader, RecordFormat::LengthPrefixed)? {
+                items.push(record);
+            }
+
+            for i in (0..items.len()).rev() {
+                let x: usize = rng.index_to(i);
+
+                write_record(writer, &items.swap_remove(x), self.format)?;
+            }
+            return Ok(records);
+        }
+
+        let buckets = self.scatter(&mut reader, RecordFormat::LengthPrefixed, rng)?;
+        drop(reader);
+
+        let mut written = 0;
+        for bucket in buckets {
+            written += self.drain(bucket, writer, rng)?;
+        }
+        Ok(written)
+    }
+
+    /// Scatter every record of `reader` into new buckets, releasing their write buffers once done
+    /// so that only one pass holds buffers at a time
+    fn scatter<R: BufRead>(
+        &self,
+        reader: &mut R,
+        format: RecordFormat,
+        rng: &mut CryptoRng,
+    ) -> io::Result<Vec<Spilled>> {
+        let mut buckets = self.new_buckets()?;
+        while let Some(record) = read_record(reader, format)? {
+            let x: usize = rng.index_below(buckets.len());
+            buckets[x].push(&record)?;
+        }
+        buckets.into_iter().map(Bucket::spill).collect()
+    }
+
+    fn new_buckets(&self) -> io::Result<Vec<Bucket>> {
+        let capacity = self.buffer_capacity();
+        (0..self.bucket_count())
+            .map(|_| {
+                let file = match &self.temp_dir {
+                    Some(dir) => tempfile::tempfile_in(dir)?,
+                    None => tempfile::tempfile()?,
+                };
+                Ok(Bucket {
+                    file: BufWriter::with_capacity(capacity, file),
+                    records: 0,
+                    bytes: 0,
+                })
+            })
+            .collect()
+    }
+
+    /// Buckets per scatter pass, fewer than the fan-out if their write buffers would otherwise
+    /// drop below [`MIN_BUFFER`]
+    fn bucket_count(&self) -> usize {
+        let fitting = (self.memory_budget / MIN_BUFFER).saturating_sub(OTHER_BUFFERS);
+        self.fan_out.min(fitting).max(2)
+    }
+
+    /// Size of every read and write buffer, so that the buffers of a scatter pass together stay
+    /// within the memory budget
+    fn buffer_capacity(&self) -> usize {
+        let buffers = self.bucket_count() + OTHER_BUFFERS;
+        (self.memory_budget / buffers).clamp(1, MAX_BUFFER)
+    }
+}
+
+/// A temporary file holding length-prefixed records
+struct Bucket {
+    file: BufWriter<File>,
+    records: u64,
+    bytes: usize,
+}
+
+impl Bucket {
+    fn push(&mut self, record: &[u8]) -> io::Result<()> {
+        write_record(&mut self.file, record, RecordFormat::LengthPrefixed)?;
+        self.records += 1;
+        self.bytes = self.bytes.saturating_add(record.len() + RECORD_OVERHEAD);
+        Ok(())
+    }
+
+    fn spill(self) -> io::Result<Spilled> {
+        let mut file = self.file.into_inner().map_err(|e| e.into_error())?;
+        file.seek(SeekFrom::Start(0))?;
+        Ok(Spilled {
+            file,
+            records: self.records,
+            bytes: self.bytes,
+        })
+    }
+}
+
+/// A bucket whose records are all written, rewound to be read back
+struct Spilled {
+    file: File,
+    records: u64,
+    bytes: usize,
+}
+
+fn read_record<R: BufRead>(reader: &mut R, format: RecordFormat) -> io::Result<Option<Vec<u8>>> {
+    match format {
+        RecordFormat::Lines => {
+            let mut record = vec![];
+            if reader.read_until(b'\n', &mut record)? == 0 {
+                return Ok(None);
+            }
+            if record.last() == Some(&b'\n') {
+                record.pop();
+            }
+            Ok(Some(record))
+        }
+        RecordFormat::Fixed(0) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "fixed record size must be non-zero",
+        )),
+        RecordFormat::Fixed(size) => {
+            let mut record = vec![0; size];
+            read_exact_or_eof(reader, &mut record)
+                .map(|complete| if complete { Some(record) } else { None })
+        }
+        RecordFormat::LengthPrefixed => {
+            let mut prefix = [0; 8];
+            if !read_exact_or_eof(reader, &mut prefix)? {
+                return Ok(None);
+            }
+            let len = usize::try_from(u64::from_le_bytes(prefix))
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "record too large"))?;
+            let mut record = vec![0; len];
+            reader.read_exact(&mut record)?;
+            Ok(Some(record))
+        }
+    }
+}
+
+/// Fill `buf` completely, returning `false` on a clean end of input and an error on a partial
+/// record
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    match filled {
+        0 if !buf.is_empty() => Ok(false),
+        n if n == buf.len() => Ok(true),
+        n => Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!(
+                "truncated record: expected {} bytes but read {}",
+                buf.len(),
+                n
+            ),
+        )),
+    }
+}
+
+fn write_record<W: Write>(writer: &mut W, record: &[u8], format: RecordFormat) -> io::Result<()> {
+    match format {
+        RecordFormat::Lines => {
+            writer.write_all(record)?;
+            writer.write_all(b"\n")
+        }
+        RecordFormat::Fixed(_) => writer.write_all(record),
+        RecordFormat::LengthPrefixed => {
+            writer.write_all(&(record.len() as u64).to_le_bytes())?;
+            writer.write_all(record)
+        }
+    }
+}
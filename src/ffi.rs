@@ -0,0 +1,407 @@
+//! # ffi
+//! C ABI over the seeded and predictive shuffles, for callers that must agree with Rust services
+//! on every result.
+//!
+//! Every function writes into a buffer allocated by the caller and returns a [`PsStatus`] instead
+//! of panicking. Permutations need room for `size` items, and predictions are written aligned
+//! with `positions`, as in [`crate::ordered`], so they need room for `positions_len` items.
+//! Pointers may be null when their length is zero. Seeds are raw bytes, so a seed passed here
+//! gives the same result as the same bytes passed to the Rust function of the same name.
+//!
+//! The header is generated with cbindgen into `include/predictive_shuffle.h`, and the library is
+//! built as a static or dynamic library on request, so that dependents only build the rlib:
+//!
+//! ```text
+//! cbindgen --config cbindgen.toml --output include/predictive_shuffle.h
+//! cargo rustc --lib --crate-type staticlib --release --features ffi
+//! ```
+
+use crate::constant_time::{
+    gen_constant_time_predictive_shuffle, gen_constant_time_predictive_shuffle_from_seed,
+    gen_constant_time_shuffle_from_seed,
+};
+use crate::ordered::{
+    gen_batch_predictive_shuffle_from_seed_into, gen_batch_predictive_shuffle_into,
+    gen_crypto_batch_predictive_shuffle_from_seed_into, gen_crypto_batch_predictive_shuffle_into,
+    gen_crypto_predictive_shuffle_from_seed_into, gen_crypto_predictive_shuffle_into,
+    gen_predictive_shuffle_from_seed_into, gen_predictive_shuffle_into,
+};
+use crate::{
+    gen_crypto_modern_shuffle_from_seed_ref, gen_fastrand_shuffle_from_seed_ref,
+    gen_modern_shuffle_from_seed_ref,
+};
+use std::ffi::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::slice;
+
+/// Outcome of a call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PsStatus {
+    /// The output buffer was filled
+    Ok = 0,
+    /// A pointer was null while its length was not zero
+    NullPointer = 1,
+    /// A position is not below the size, or is repeated
+    InvalidPosition = 2,
+    /// A batch size is zero, or larger than the size
+    InvalidBatch = 3,
+    /// The call failed unexpectedly, and the output buffer is unspecified
+    Internal = 4,
+}
+
+/// Static, nul-terminated description of a status
+#[no_mangle]
+pub extern "C" fn ps_status_message(status: PsStatus) -> *const c_char {
+    let message: &'static [u8] = match status {
+        PsStatus::Ok => b"ok\0",
+        PsStatus::NullPointer => b"null pointer with a non-zero length\0",
+        PsStatus::InvalidPosition => b"position is out of range or repeated\0",
+        PsStatus::InvalidBatch => b"batch size is zero or larger than the size\0",
+        PsStatus::Internal => b"internal error\0",
+    };
+    message.as_ptr().cast()
+}
+
+unsafe fn input<'a, T>(ptr: *const T, len: usize) -> Result<&'a [T], PsStatus> {
+    match (ptr.is_null(), len) {
+        (_, 0) => Ok(&[]),
+        (true, _) => Err(PsStatus::NullPointer),
+        (false, _) => Ok(slice::from_raw_parts(ptr, len)),
+    }
+}
+
+unsafe fn output<'a, T>(ptr: *mut T, len: usize) -> Result<&'a mut [T], PsStatus> {
+    match (ptr.is_null(), len) {
+        (_, 0) => Ok(&mut []),
+        (true, _) => Err(PsStatus::NullPointer),
+        (false, _) => Ok(slice::from_raw_parts_mut(ptr, len)),
+    }
+}
+
+fn check_positions(size: usize, positions: &[usize]) -> Result<(), PsStatus> {
+    crate::check_positions(size, positions).map_err(|_| PsStatus::InvalidPosition)
+}
+
+fn check_batch(size: usize, batch: usize) -> Result<(), PsStatus> {
+    crate::check_batch(size, batch).map_err(|_| PsStatus::InvalidBatch)
+}
+
+/// Run `f`, turning a panic into [`PsStatus::Internal`] so it never unwinds into C
+fn status<F: FnOnce() -> Result<(), PsStatus>>(f: F) -> PsStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => PsStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => PsStatus::Internal,
+    }
+}
+
+/// [`crate::gen_fastrand_shuffle_from_seed_ref`] into `out`, which holds `size` items
+///
+/// # Safety
+/// `seed` must be valid for `seed_len` reads and `out` for `size` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_fastrand_shuffle_from_seed(
+    size: usize,
+    seed: *const u8,
+    seed_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let seed = input(seed, seed_len)?;
+        let out = output(out, size)?;
+        out.copy_from_slice(&gen_fastrand_shuffle_from_seed_ref(size, seed));
+        Ok(())
+    })
+}
+
+/// [`crate::gen_modern_shuffle_from_seed_ref`] into `out`, which holds `size` items
+///
+/// # Safety
+/// `seed` must be valid for `seed_len` reads and `out` for `size` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_modern_shuffle_from_seed(
+    size: usize,
+    seed: *const u8,
+    seed_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let seed = input(seed, seed_len)?;
+        let out = output(out, size)?;
+        out.copy_from_slice(&gen_modern_shuffle_from_seed_ref(size, seed));
+        Ok(())
+    })
+}
+
+/// [`crate::gen_crypto_modern_shuffle_from_seed_ref`] into `out`, which holds `size` items
+///
+/// # Safety
+/// `seed` must be valid for `seed_len` reads and `out` for `size` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_crypto_modern_shuffle_from_seed(
+    size: usize,
+    seed: *const u8,
+    seed_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let seed = input(seed, seed_len)?;
+        let out = output(out, size)?;
+        out.copy_from_slice(&gen_crypto_modern_shuffle_from_seed_ref(size, seed));
+        Ok(())
+    })
+}
+
+/// [`crate::constant_time::gen_constant_time_shuffle_from_seed`] into `out`, which holds `size`
+/// items
+///
+/// # Safety
+/// `seed` must be valid for `seed_len` reads and `out` for `size` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_constant_time_shuffle_from_seed(
+    size: usize,
+    seed: *const u8,
+    seed_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let seed = input(seed, seed_len)?;
+        let out = output(out, size)?;
+        out.copy_from_slice(&gen_constant_time_shuffle_from_seed(size, seed));
+        Ok(())
+    })
+}
+
+/// [`crate::ordered::gen_predictive_shuffle_into`]
+///
+/// # Safety
+/// `positions` must be valid for `positions_len` reads and `out` for `positions_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_predictive_shuffle(
+    size: usize,
+    positions: *const usize,
+    positions_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let positions = input(positions, positions_len)?;
+        let out = output(out, positions_len)?;
+        check_positions(size, positions)?;
+        gen_predictive_shuffle_into(size, positions, out);
+        Ok(())
+    })
+}
+
+/// [`crate::ordered::gen_predictive_shuffle_from_seed_into`]
+///
+/// # Safety
+/// `positions` must be valid for `positions_len` reads, `seed` for `seed_len` reads and `out` for
+/// `positions_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_predictive_shuffle_from_seed(
+    size: usize,
+    positions: *const usize,
+    positions_len: usize,
+    seed: *const u8,
+    seed_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let positions = input(positions, positions_len)?;
+        let seed = input(seed, seed_len)?;
+        let out = output(out, positions_len)?;
+        check_positions(size, positions)?;
+        gen_predictive_shuffle_from_seed_into(size, positions, seed, out);
+        Ok(())
+    })
+}
+
+/// [`crate::ordered::gen_crypto_predictive_shuffle_into`]
+///
+/// # Safety
+/// `positions` must be valid for `positions_len` reads and `out` for `positions_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_crypto_predictive_shuffle(
+    size: usize,
+    positions: *const usize,
+    positions_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let positions = input(positions, positions_len)?;
+        let out = output(out, positions_len)?;
+        check_positions(size, positions)?;
+        gen_crypto_predictive_shuffle_into(size, positions, out);
+        Ok(())
+    })
+}
+
+/// [`crate::ordered::gen_crypto_predictive_shuffle_from_seed_into`]
+///
+/// # Safety
+/// `positions` must be valid for `positions_len` reads, `seed` for `seed_len` reads and `out` for
+/// `positions_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_crypto_predictive_shuffle_from_seed(
+    size: usize,
+    positions: *const usize,
+    positions_len: usize,
+    seed: *const u8,
+    seed_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let positions = input(positions, positions_len)?;
+        let seed = input(seed, seed_len)?;
+        let out = output(out, positions_len)?;
+        check_positions(size, positions)?;
+        gen_crypto_predictive_shuffle_from_seed_into(size, positions, seed, out);
+        Ok(())
+    })
+}
+
+/// [`crate::ordered::gen_batch_predictive_shuffle_into`]
+///
+/// # Safety
+/// `positions` must be valid for `positions_len` reads and `out` for `positions_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_batch_predictive_shuffle(
+    size: usize,
+    batch: usize,
+    positions: *const usize,
+    positions_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let positions = input(positions, positions_len)?;
+        let out = output(out, positions_len)?;
+        check_batch(size, batch)?;
+        check_positions(size, positions)?;
+        gen_batch_predictive_shuffle_into(size, batch, positions, out);
+        Ok(())
+    })
+}
+
+/// [`crate::ordered::gen_batch_predictive_shuffle_from_seed_into`]
+///
+/// # Safety
+/// `positions` must be valid for `positions_len` reads, `seed` for `seed_len` reads and `out` for
+/// `positions_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_batch_predictive_shuffle_from_seed(
+    size: usize,
+    batch: usize,
+    positions: *const usize,
+    positions_len: usize,
+    seed: *const u8,
+    seed_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let positions = input(positions, positions_len)?;
+        let seed = input(seed, seed_len)?;
+        let out = output(out, positions_len)?;
+        check_batch(size, batch)?;
+        check_positions(size, positions)?;
+        gen_batch_predictive_shuffle_from_seed_into(size, batch, positions, seed, out);
+        Ok(())
+    })
+}
+
+/// [`crate::ordered::gen_crypto_batch_predictive_shuffle_into`]
+///
+/// # Safety
+/// `positions` must be valid for `positions_len` reads and `out` for `positions_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_crypto_batch_predictive_shuffle(
+    size: usize,
+    batch: usize,
+    positions: *const usize,
+    positions_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let positions = input(positions, positions_len)?;
+        let out = output(out, positions_len)?;
+        check_batch(size, batch)?;
+        check_positions(size, positions)?;
+        gen_crypto_batch_predictive_shuffle_into(size, batch, positions, out);
+        Ok(())
+    })
+}
+
+/// [`crate::ordered::gen_crypto_batch_predictive_shuffle_from_seed_into`]
+///
+/// # Safety
+/// `positions` must be valid for `positions_len` reads, `seed` for `seed_len` reads and `out` for
+/// `positions_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_crypto_batch_predictive_shuffle_from_seed(
+    size: usize,
+    batch: usize,
+    positions: *const usize,
+    positions_len: usize,
+    seed: *const u8,
+    seed_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let positions = input(positions, positions_len)?;
+        let seed = input(seed, seed_len)?;
+        let out = output(out, positions_len)?;
+        check_batch(size, batch)?;
+        check_positions(size, positions)?;
+        gen_crypto_batch_predictive_shuffle_from_seed_into(size, batch, positions, seed, out);
+        Ok(())
+    })
+}
+
+/// [`crate::constant_time::gen_constant_time_predictive_shuffle`]
+///
+/// Positions are not checked, since that would branch on their values. Out of range positions
+/// map to `size`, as in the Rust function.
+///
+/// # Safety
+/// `positions` must be valid for `positions_len` reads and `out` for `positions_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_constant_time_predictive_shuffle(
+    size: usize,
+    positions: *const usize,
+    positions_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let positions = input(positions, positions_len)?;
+        let out = output(out, positions_len)?;
+        out.copy_from_slice(&gen_constant_time_predictive_shuffle(size, positions));
+        Ok(())
+    })
+}
+
+/// [`crate::constant_time::gen_constant_time_predictive_shuffle_from_seed`]
+///
+/// Positions are not checked, as in [`ps_gen_constant_time_predictive_shuffle`].
+///
+/// # Safety
+/// `positions` must be valid for `positions_len` reads, `seed` for `seed_len` reads and `out` for
+/// `positions_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn ps_gen_constant_time_predictive_shuffle_from_seed(
+    size: usize,
+    positions: *const usize,
+    positions_len: usize,
+    seed: *const u8,
+    seed_len: usize,
+    out: *mut usize,
+) -> PsStatus {
+    status(|| {
+        let positions = input(positions, positions_len)?;
+        let seed = input(seed, seed_len)?;
+        let out = output(out, positions_len)?;
+        out.copy_from_slice(&gen_constant_time_predictive_shuffle_from_seed(
+            size, positions, seed,
+        ));
+        Ok(())
+    })
+}
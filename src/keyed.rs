@@ -0,0 +1,726 @@
+//! # keyed
+//! Shuffles keyed with a server secret, reproducible for the operator but unpredictable to anyone
+//! who only knows the public seed.
+//!
+//! The plain `*_from_seed` functions derive their RNG seed from the SHA-256 [`crate::hash`] of
+//! the seed, so anyone holding the seed can recompute the shuffle. The functions in this module
+//! first derive the seed as
+//!
+//! ```text
+//! HMAC-SHA256(server_key, len(seed) ‖ seed ‖ context)
+//! ```
+//!
+//! where `len(seed)` is the seed length as a big-endian `u64`, so that no seed and context pair
+//! can collide with another. The `crypto_*` and constant-time variants then seed from the HMAC
+//! output exactly as the plain functions seed from a seed, so they give the same results as
+//! passing [`ServerKey::derive_seed`] to the plain `*_from_seed` function. The `fastrand`
+//! variants seed from the first 64 bits of the output, all the state `fastrand::Rng` holds, and
+//! stay predictable to anyone who sees enough of their output. Every result is returned in a
+//! [`Keyed`] carrying the identifier of the [`ServerKey`] it was produced with. A [`Keyring`]
+//! keeps retired keys around after a rotation, so earlier results can still be recomputed from
+//! their key identifier.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! use predictive_shuffle::keyed::{
+//!     gen_keyed_crypto_predictive_shuffle_from_seed, Keyring, ServerKey,
+//! };
+//!
+//! let mut keyring = Keyring::new(ServerKey::new("2023-01", b"first secret".to_vec()));
+//! let seed = b"user seed".to_vec();
+//! let context = b"draw 42";
+//!
+//! let keyed = gen_keyed_crypto_predictive_shuffle_from_seed(
+//!     keyring.current(),
+//!     100,
+//!     vec![1, 5],
+//!     seed.clone(),
+//!     context,
+//! );
+//!
+//! // rotate, then recompute the earlier result from its key identifier
+//! keyring.rotate(ServerKey::new("2023-02", b"second secret".to_vec())).unwrap();
+//! let key = keyring.get(&keyed.key_id).unwrap();
+//! let recomputed =
+//!     gen_keyed_crypto_predictive_shuffle_from_seed(key, 100, vec![1, 5], seed, context);
+//! assert_eq!(recomputed, keyed);
+//! ```
+
+use crate::constant_time::{oblivious_lookup, oblivious_permutation};
+use crate::rng::Draw;
+use crate::{
+    batch_predictive_with, crypto_batch_predictive_with, crypto_predictive_with, modern_with,
+    predictive_with, rng, CryptoRng, Seed,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Errors returned when managing a [`Keyring`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyedError {
+    /// A key with this identifier is already in the keyring
+    DuplicateKey(String),
+    /// No key with this identifier is in the keyring
+    UnknownKey(String),
+    /// The current key cannot be retired
+    CurrentKey(String),
+}
+
+impl fmt::Display for KeyedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyedError::DuplicateKey(id) => write!(f, "key {} is already in the keyring", id),
+            KeyedError::UnknownKey(id) => write!(f, "key {} is not in the keyring", id),
+            KeyedError::CurrentKey(id) => write!(f, "key {} is the current key", id),
+        }
+    }
+}
+
+impl std::error::Error for KeyedError {}
+
+/// A server secret and the identifier it is published under
+///
+/// With the `zeroize` feature enabled, the secret is wiped when the key is dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ServerKey {
+    id: String,
+    secret: Vec<u8>,
+}
+
+impl ServerKey {
+    /// Wrap a secret under an identifier
+    pub fn new(id: impl Into<String>, secret: Vec<u8>) -> Self {
+        ServerKey {
+            id: id.into(),
+            secret,
+        }
+    }
+
+    /// Identifier carried in every [`Keyed`] result produced with this key
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// HMAC output the keyed shuffles of `seed` and `context` draw from
+    pub fn derive_seed(&self, seed: &[u8], context: &[u8]) -> Seed {
+        let mut output = self.hmac(seed, context);
+        let derived = Seed::new(output.to_vec());
+        rng::wipe(&mut output);
+        derived
+    }
+
+    fn hmac(&self, seed: &[u8], context: &[u8]) -> [u8; 32] {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(&(seed.len() as u64).to_be_bytes());
+        mac.update(seed);
+        mac.update(context);
+        mac.finalize().into_bytes().into()
+    }
+
+    fn keyed<T>(&self, value: T) -> Keyed<T> {
+        Keyed {
+            key_id: self.id.clone(),
+            value,
+        }
+    }
+}
+
+impl fmt::Debug for ServerKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerKey")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for ServerKey {
+    fn zeroize(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ServerKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for ServerKey {}
+
+/// The current server key, plus every retired key still needed to recompute earlier results
+#[derive(Debug, Clone)]
+pub struct Keyring {
+    current: String,
+    keys: BTreeMap<String, ServerKey>,
+}
+
+impl Keyring {
+    /// Start a keyring with a single current key
+    pub fn new(key: ServerKey) -> Self {
+        let current = key.id.clone();
+        let mut keys = BTreeMap::new();
+        keys.insert(current.clone(), key);
+        Keyring { current, keys }
+    }
+
+    /// Key new results are produced with
+    pub fn current(&self) -> &ServerKey {
+        &self.keys[&self.current]
+    }
+
+    /// Look up a key by the identifier carried in a [`Keyed`] result
+    pub fn get(&self, id: &str) -> Result<&ServerKey, KeyedError> {
+        self.keys
+            .get(id)
+            .ok_or_else(|| KeyedError::UnknownKey(id.to_string()))
+    }
+
+    /// Make a new key current, keeping the previous keys for recomputation
+    pub fn rotate(&mut self, key: ServerKey) -> Result<(), KeyedError> {
+        if self.keys.contains_key(&key.id) {
+            return Err(KeyedError::DuplicateKey(key.id.clone()));
+        }
+        self.current = key.id.clone();
+        self.keys.insert(key.id.clone(), key);
+        Ok(())
+    }
+
+    /// Drop a retired key once no earlier result needs recomputing with it
+    pub fn retire(&mut self, id: &str) -> Result<ServerKey, KeyedError> {
+        if id == self.current {
+            return Err(KeyedError::CurrentKey(id.to_string()));
+        }
+        self.keys
+            .remove(id)
+            .ok_or_else(|| KeyedError::UnknownKey(id.to_string()))
+    }
+
+    /// Identifiers of every key in the keyring
+    pub fn ids(&self) -> Vec<&str> {
+        self.keys.keys().map(String::as_str).collect()
+    }
+}
+
+/// A shuffle result and the identifier of the [`ServerKey`] it was produced with
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyed<T> {
+    /// Identifier of the key to recompute the result with
+    pub key_id: String,
+    /// The shuffle result
+    pub value: T,
+}
+
+/// [`CryptoRng`] seeded with the HMAC output, as the plain `crypto_*` functions seed from a seed
+fn crypto_rng(key: &ServerKey, seed: &[u8], context: &[u8]) -> CryptoRng {
+    let mut output = key.hmac(seed, context);
+    let rng = CryptoRng::from_seed_ref(&output);
+    rng::wipe(&mut output);
+    rng
+}
+
+/// `fastrand::Rng` seeded with the first 64 bits of the HMAC output
+fn fastrand_rng(key: &ServerKey, seed: &[u8], context: &[u8]) -> fastrand::Rng {
+    let mut output = key.hmac(seed, context);
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&output[..8]);
+    let mut seed_int = u64::from_be_bytes(bytes);
+    let rng = fastrand::Rng::with_seed(seed_int);
+    rng::wipe(&mut output);
+    rng::wipe(&mut bytes);
+    rng::wipe(&mut seed_int);
+    rng
+}
+
+pub trait KeyedShuffle<T> {
+    fn keyed_fastrand_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<()>;
+    fn keyed_modern_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<Vec<T>>;
+    fn keyed_crypto_modern_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<Vec<T>>;
+    fn keyed_predictive_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<HashMap<usize, usize>>;
+    fn keyed_crypto_predictive_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<HashMap<usize, usize>>;
+    fn keyed_batch_predictive_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<HashMap<usize, usize>>;
+    fn keyed_crypto_batch_predictive_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<HashMap<usize, usize>>;
+}
+
+impl<T> KeyedShuffle<T> for Vec<T> {
+    /// Keyed [`crate::Shuffle::fastrand_shuffle_from_seed_ref`]
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::keyed::{KeyedShuffle, ServerKey};
+    ///
+    /// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+    /// let mut vec: Vec<usize> = (0..10).collect();
+    /// let seed = b"seed phrase";
+    /// let keyed = vec.keyed_fastrand_shuffle_from_seed(&key, seed, b"context");
+    /// assert_eq!(keyed.key_id, "2023-01");
+    /// ```
+    fn keyed_fastrand_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<()> {
+        let mut rng = fastrand_rng(key, seed.as_ref(), context);
+        rng.shuffle_slice(self.as_mut_slice());
+        key.keyed(())
+    }
+
+    /// Keyed [`crate::Shuffle::modern_shuffle_from_seed_ref`]
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::keyed::{KeyedShuffle, ServerKey};
+    ///
+    /// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+    /// let mut vec: Vec<usize> = (0..10).collect();
+    /// let seed = b"seed phrase";
+    /// let shuffled_vec = vec.keyed_modern_shuffle_from_seed(&key, seed, b"context");
+    /// ```
+    fn keyed_modern_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<Vec<T>> {
+        let mut rng = fastrand_rng(key, seed.as_ref(), context);
+        key.keyed(modern_with(self, &mut rng))
+    }
+
+    /// Keyed [`crate::Shuffle::crypto_modern_shuffle_from_seed_ref`]
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::keyed::{KeyedShuffle, ServerKey};
+    ///
+    /// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+    /// let mut vec: Vec<usize> = (0..10).collect();
+    /// let seed = b"seed phrase";
+    /// let shuffled_vec = vec.keyed_crypto_modern_shuffle_from_seed(&key, seed, b"context");
+    /// ```
+    fn keyed_crypto_modern_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<Vec<T>> {
+        let mut rng = crypto_rng(key, seed.as_ref(), context);
+        key.keyed(modern_with(self, &mut rng))
+    }
+
+    /// Keyed [`crate::Shuffle::predictive_shuffle_from_seed_ref`]
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::keyed::{KeyedShuffle, ServerKey};
+    ///
+    /// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+    /// let mut vec: Vec<usize> = (0..10).collect();
+    /// let seed = b"seed phrase";
+    /// let positions = vec![1,5];
+    /// let new_positions =
+    ///     vec.keyed_predictive_shuffle_from_seed(&key, positions, seed, b"context");
+    /// ```
+    fn keyed_predictive_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<HashMap<usize, usize>> {
+        let mut rng = fastrand_rng(key, seed.as_ref(), context);
+        key.keyed(predictive_with(self.len(), positions, &mut rng))
+    }
+
+    /// Keyed [`crate::Shuffle::crypto_predictive_shuffle_from_seed_ref`]
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::keyed::{KeyedShuffle, ServerKey};
+    ///
+    /// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+    /// let mut vec: Vec<usize> = (0..10).collect();
+    /// let seed = b"seed phrase";
+    /// let positions = vec![1,5];
+    /// let new_positions =
+    ///     vec.keyed_crypto_predictive_shuffle_from_seed(&key, positions, seed, b"context");
+    /// ```
+    fn keyed_crypto_predictive_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<HashMap<usize, usize>> {
+        let mut rng = crypto_rng(key, seed.as_ref(), context);
+        key.keyed(crypto_predictive_with(self.len(), positions, &mut rng))
+    }
+
+    /// Keyed [`crate::Shuffle::batch_predictive_shuffle_from_seed_ref`]
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::keyed::{KeyedShuffle, ServerKey};
+    ///
+    /// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+    /// let mut vec: Vec<usize> = (0..100).collect();
+    /// let seed = b"seed phrase";
+    /// let batch = 4;
+    /// let positions = vec![1,5];
+    /// let new_positions =
+    ///     vec.keyed_batch_predictive_shuffle_from_seed(&key, batch, positions, seed, b"context");
+    /// ```
+    fn keyed_batch_predictive_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<HashMap<usize, usize>> {
+        let mut rng = fastrand_rng(key, seed.as_ref(), context);
+        key.keyed(batch_predictive_with(
+            self.len(),
+            batch,
+            positions,
+            &mut rng,
+        ))
+    }
+
+    /// Keyed [`crate::Shuffle::crypto_batch_predictive_shuffle_from_seed_ref`]
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::keyed::{KeyedShuffle, ServerKey};
+    ///
+    /// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+    /// let mut vec: Vec<usize> = (0..100).collect();
+    /// let seed = b"seed phrase";
+    /// let batch = 4;
+    /// let positions = vec![1,5];
+    /// let new_positions = vec.keyed_crypto_batch_predictive_shuffle_from_seed(
+    ///     &key, batch, positions, seed, b"context",
+    /// );
+    /// ```
+    fn keyed_crypto_batch_predictive_shuffle_from_seed(
+        &mut self,
+        key: &ServerKey,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+        context: &[u8],
+    ) -> Keyed<HashMap<usize, usize>> {
+        let mut rng = crypto_rng(key, seed.as_ref(), context);
+        key.keyed(crypto_batch_predictive_with(
+            self.len(),
+            batch,
+            positions,
+            &mut rng,
+        ))
+    }
+}
+
+/// Keyed [`crate::gen_fastrand_shuffle_from_seed_ref`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::keyed::{gen_keyed_fastrand_shuffle_from_seed, ServerKey};
+///
+/// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+/// let size = 10;
+/// let seed = b"seed phrase";
+/// let shuffled_vec = gen_keyed_fastrand_shuffle_from_seed(&key, size, seed, b"context");
+/// ```
+pub fn gen_keyed_fastrand_shuffle_from_seed(
+    key: &ServerKey,
+    size: usize,
+    seed: impl AsRef<[u8]>,
+    context: &[u8],
+) -> Keyed<Vec<usize>> {
+    let mut vec: Vec<usize> = (0..size).collect();
+    let mut rng = fastrand_rng(key, seed.as_ref(), context);
+    rng.shuffle_slice(vec.as_mut_slice());
+    key.keyed(vec)
+}
+
+/// Keyed [`crate::gen_modern_shuffle_from_seed_ref`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::keyed::{gen_keyed_modern_shuffle_from_seed, ServerKey};
+///
+/// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+/// let size = 10;
+/// let seed = b"seed phrase";
+/// let shuffled_vec = gen_keyed_modern_shuffle_from_seed(&key, size, seed, b"context");
+/// ```
+pub fn gen_keyed_modern_shuffle_from_seed(
+    key: &ServerKey,
+    size: usize,
+    seed: impl AsRef<[u8]>,
+    context: &[u8],
+) -> Keyed<Vec<usize>> {
+    let mut rng = fastrand_rng(key, seed.as_ref(), context);
+    key.keyed(modern_with(&mut (0..size).collect(), &mut rng))
+}
+
+/// Keyed [`crate::gen_crypto_modern_shuffle_from_seed_ref`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::keyed::{gen_keyed_crypto_modern_shuffle_from_seed, ServerKey};
+///
+/// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+/// let size = 10;
+/// let seed = b"seed phrase";
+/// let shuffled_vec = gen_keyed_crypto_modern_shuffle_from_seed(&key, size, seed, b"context");
+/// ```
+pub fn gen_keyed_crypto_modern_shuffle_from_seed(
+    key: &ServerKey,
+    size: usize,
+    seed: impl AsRef<[u8]>,
+    context: &[u8],
+) -> Keyed<Vec<usize>> {
+    let mut rng = crypto_rng(key, seed.as_ref(), context);
+    key.keyed(modern_with(&mut (0..size).collect(), &mut rng))
+}
+
+/// Keyed [`crate::gen_predictive_shuffle_from_seed_ref`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::keyed::{gen_keyed_predictive_shuffle_from_seed, ServerKey};
+///
+/// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+/// let size = 100;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let new_positions =
+///     gen_keyed_predictive_shuffle_from_seed(&key, size, positions, seed, b"context");
+/// ```
+pub fn gen_keyed_predictive_shuffle_from_seed(
+    key: &ServerKey,
+    size: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    seed: impl AsRef<[u8]>,
+    context: &[u8],
+) -> Keyed<HashMap<usize, usize>> {
+    let mut rng = fastrand_rng(key, seed.as_ref(), context);
+    key.keyed(predictive_with(size, positions, &mut rng))
+}
+
+/// Keyed [`crate::gen_crypto_predictive_shuffle_from_seed_ref`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::keyed::{gen_keyed_crypto_predictive_shuffle_from_seed, ServerKey};
+///
+/// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+/// let size = 100;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let new_positions =
+///     gen_keyed_crypto_predictive_shuffle_from_seed(&key, size, positions, seed, b"context");
+/// ```
+pub fn gen_keyed_crypto_predictive_shuffle_from_seed(
+    key: &ServerKey,
+    size: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    seed: impl AsRef<[u8]>,
+    context: &[u8],
+) -> Keyed<HashMap<usize, usize>> {
+    let mut rng = crypto_rng(key, seed.as_ref(), context);
+    key.keyed(crypto_predictive_with(size, positions, &mut rng))
+}
+
+/// Keyed [`crate::gen_batch_predictive_shuffle_from_seed_ref`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::keyed::{gen_keyed_batch_predictive_shuffle_from_seed, ServerKey};
+///
+/// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+/// let size = 100;
+/// let batch = 4;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let new_positions = gen_keyed_batch_predictive_shuffle_from_seed(
+///     &key,
+///     size,
+///     batch,
+///     positions,
+///     seed,
+///     b"context",
+/// );
+/// ```
+pub fn gen_keyed_batch_predictive_shuffle_from_seed(
+    key: &ServerKey,
+    size: usize,
+    batch: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    seed: impl AsRef<[u8]>,
+    context: &[u8],
+) -> Keyed<HashMap<usize, usize>> {
+    let mut rng = fastrand_rng(key, seed.as_ref(), context);
+    key.keyed(batch_predictive_with(size, batch, positions, &mut rng))
+}
+
+/// Keyed [`crate::gen_crypto_batch_predictive_shuffle_from_seed_ref`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::keyed::{gen_keyed_crypto_batch_predictive_shuffle_from_seed, ServerKey};
+///
+/// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+/// let size = 100;
+/// let batch = 4;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let new_positions = gen_keyed_crypto_batch_predictive_shuffle_from_seed(
+///     &key, size, batch, positions, seed, b"context",
+/// );
+/// ```
+pub fn gen_keyed_crypto_batch_predictive_shuffle_from_seed(
+    key: &ServerKey,
+    size: usize,
+    batch: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    seed: impl AsRef<[u8]>,
+    context: &[u8],
+) -> Keyed<HashMap<usize, usize>> {
+    let mut rng = crypto_rng(key, seed.as_ref(), context);
+    key.keyed(crypto_batch_predictive_with(
+        size, batch, positions, &mut rng,
+    ))
+}
+
+/// Keyed [`crate::constant_time::gen_constant_time_shuffle_from_seed`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::keyed::{gen_keyed_constant_time_shuffle_from_seed, ServerKey};
+///
+/// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+/// let size = 52;
+/// let seed = b"seed phrase";
+/// let shuffled_vec = gen_keyed_constant_time_shuffle_from_seed(&key, size, seed, b"context");
+/// ```
+pub fn gen_keyed_constant_time_shuffle_from_seed(
+    key: &ServerKey,
+    size: usize,
+    seed: impl AsRef<[u8]>,
+    context: &[u8],
+) -> Keyed<Vec<usize>> {
+    let mut rng = crypto_rng(key, seed.as_ref(), context);
+    key.keyed(oblivious_permutation(size, &mut rng))
+}
+
+/// Keyed [`crate::constant_time::gen_constant_time_predictive_shuffle_from_seed`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::keyed::{
+///     gen_keyed_constant_time_predictive_shuffle_from_seed, ServerKey,
+/// };
+///
+/// let key = ServerKey::new("2023-01", b"server secret".to_vec());
+/// let size = 52;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let new_positions = gen_keyed_constant_time_predictive_shuffle_from_seed(
+///     &key, size, &positions, seed, b"context",
+/// );
+/// ```
+pub fn gen_keyed_constant_time_predictive_shuffle_from_seed(
+    key: &ServerKey,
+    size: usize,
+    positions: &[usize],
+    seed: impl AsRef<[u8]>,
+    context: &[u8],
+) -> Keyed<Vec<usize>> {
+    let mut rng = crypto_rng(key, seed.as_ref(), context);
+    let permutation = oblivious_permutation(size, &mut rng);
+    key.keyed(oblivious_lookup(&permutation, positions))
+}
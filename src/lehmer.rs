@@ -0,0 +1,283 @@
+//! # lehmer
+//! Lexicographic rank and unrank of permutations through their Lehmer code.
+//!
+//! A permutation of `0..n` can be stored as a single number below `n!`. Ranks fit in a `u128`
+//! for `n <= 34` ([`MAX_U128_SIZE`]), and in a [`BigUint`] for any size. Enabled with the
+//! `lehmer` feature.
+
+#[cfg(feature = "chacha")]
+use crate::CryptoRng;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use num_bigint::BigUint;
+#[cfg(feature = "chacha")]
+use num_bigint::RandBigInt;
+#[cfg(feature = "chacha")]
+use rand::Rng;
+
+/// Largest permutation size whose ranks all fit in a `u128`
+pub const MAX_U128_SIZE: usize = 34;
+
+/// Errors returned when ranking or unranking a permutation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LehmerError {
+    /// The input is not a permutation of `0..n`
+    NotAPermutation,
+    /// The permutation size exceeds [`MAX_U128_SIZE`]
+    TooLarge(usize),
+    /// The rank is not below `n!`
+    RankOutOfRange,
+}
+
+impl fmt::Display for LehmerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LehmerError::NotAPermutation => write!(f, "input is not a permutation of 0..n"),
+            LehmerError::TooLarge(size) => write!(
+                f,
+                "permutations of size {} do not fit in a u128 rank (max {})",
+                size, MAX_U128_SIZE
+            ),
+            LehmerError::RankOutOfRange => write!(f, "rank is not below n!"),
+        }
+    }
+}
+
+impl core::error::Error for LehmerError {}
+
+/// Compute the Lehmer code of a permutation, where digit `i` counts the later items that are
+/// smaller than item `i`
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::lehmer::lehmer_code;
+///
+/// assert_eq!(lehmer_code(&[2, 0, 3, 1]), Ok(vec![2, 0, 1, 0]));
+/// ```
+pub fn lehmer_code(permutation: &[usize]) -> Result<Vec<usize>, LehmerError> {
+    let size = permutation.len();
+
+    // fenwick tree over the items not yet seen
+    let mut tree = vec![0usize; size + 1];
+    for i in 1..=size {
+        tree[i] += 1;
+        let parent = i + (i & i.wrapping_neg());
+        if parent <= size {
+            tree[parent] += tree[i];
+        }
+    }
+
+    let mut seen = vec![false; size];
+    let mut code = Vec::with_capacity(size);
+    for &item in permutation {
+        if item >= size || seen[item] {
+            return Err(LehmerError::NotAPermutation);
+        }
+        seen[item] = true;
+
+        // count unseen items smaller than this one
+        let mut smaller = 0;
+        let mut i = item;
+        while i > 0 {
+            smaller += tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        code.push(smaller);
+
+        let mut i = item + 1;
+        while i <= size {
+            tree[i] -= 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+    Ok(code)
+}
+
+/// Rebuild a permutation from its Lehmer code
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::lehmer::from_lehmer_code;
+///
+/// assert_eq!(from_lehmer_code(&[2, 0, 1, 0]), Ok(vec![2, 0, 3, 1]));
+/// ```
+pub fn from_lehmer_code(code: &[usize]) -> Result<Vec<usize>, LehmerError> {
+    let size = code.len();
+    let mut remaining: Vec<usize> = (0..size).collect();
+
+    let mut permutation = Vec::with_capacity(size);
+    for &digit in code {
+        if digit >= remaining.len() {
+            return Err(LehmerError::NotAPermutation);
+        }
+        permutation.push(remaining.remove(digit));
+    }
+    Ok(permutation)
+}
+
+/// Lexicographic rank of a permutation of at most [`MAX_U128_SIZE`] items
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_modern_shuffle_from_seed_ref;
+/// use predictive_shuffle::lehmer::{rank_permutation, unrank_permutation};
+///
+/// let seed = b"seed phrase".to_vec();
+/// let shuffled_vec = gen_modern_shuffle_from_seed_ref(10, &seed);
+/// let rank = rank_permutation(&shuffled_vec).unwrap();
+/// assert_eq!(unrank_permutation(10, rank), Ok(shuffled_vec));
+/// ```
+pub fn rank_permutation(permutation: &[usize]) -> Result<u128, LehmerError> {
+    let size = permutation.len();
+    if size > MAX_U128_SIZE {
+        return Err(LehmerError::TooLarge(size));
+    }
+
+    let mut rank: u128 = 0;
+    for (i, digit) in lehmer_code(permutation)?.into_iter().enumerate() {
+        rank = rank * (size - i) as u128 + digit as u128;
+    }
+    Ok(rank)
+}
+
+/// Lexicographic rank of a permutation of any size
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::lehmer::{rank_permutation_big, unrank_permutation_big};
+///
+/// let permutation: Vec<usize> = (0..100).rev().collect();
+/// let rank = rank_permutation_big(&permutation).unwrap();
+/// assert_eq!(unrank_permutation_big(100, &rank), Ok(permutation));
+/// ```
+pub fn rank_permutation_big(permutation: &[usize]) -> Result<BigUint, LehmerError> {
+    let size = permutation.len();
+
+    let mut rank = BigUint::default();
+    for (i, digit) in lehmer_code(permutation)?.into_iter().enumerate() {
+        rank = rank * (size - i) + digit;
+    }
+    Ok(rank)
+}
+
+/// Permutation of `0..size` with the given lexicographic rank
+pub fn unrank_permutation(size: usize, rank: u128) -> Result<Vec<usize>, LehmerError> {
+    let mut rank = rank;
+
+    let mut code = vec![0; size];
+    for i in (0..size).rev() {
+        let radix = (size - i) as u128;
+        code[i] = (rank % radix) as usize;
+        rank /= radix;
+    }
+    if rank != 0 {
+        return Err(LehmerError::RankOutOfRange);
+    }
+    from_lehmer_code(&code)
+}
+
+/// Permutation of `0..size` with the given lexicographic rank, for ranks of any size
+pub fn unrank_permutation_big(size: usize, rank: &BigUint) -> Result<Vec<usize>, LehmerError> {
+    let mut rank = rank.clone();
+
+    let mut code = vec![0; size];
+    for i in (0..size).rev() {
+        let radix = BigUint::from(size - i);
+        let digit = &rank % &radix;
+        code[i] = digit.iter_u64_digits().next().unwrap_or(0) as usize;
+        rank /= radix;
+    }
+    if rank != BigUint::default() {
+        return Err(LehmerError::RankOutOfRange);
+    }
+    from_lehmer_code(&code)
+}
+
+/// `size!` as a `u128`, for sizes up to [`MAX_U128_SIZE`]
+pub fn factorial(size: usize) -> Result<u128, LehmerError> {
+    if size > MAX_U128_SIZE {
+        return Err(LehmerError::TooLarge(size));
+    }
+    Ok((1..=size as u128).product())
+}
+
+/// `size!` as a [`BigUint`]
+pub fn factorial_big(size: usize) -> BigUint {
+    (1..=size).fold(BigUint::from(1u8), |acc, i| acc * i)
+}
+
+/// Implementing a cryptographic randomization algorithm ['rand_chacha::ChaCha20Rng'],
+/// to generate the uniform rank of a shuffled vector of at most [`MAX_U128_SIZE`] items
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::lehmer::{gen_crypto_rank, unrank_permutation};
+///
+/// assert!(gen_crypto_rank(52).is_err());
+/// let rank = gen_crypto_rank(20).unwrap();
+/// let shuffled_vec = unrank_permutation(20, rank).unwrap();
+/// ```
+#[cfg(all(feature = "chacha", entropy))]
+pub fn gen_crypto_rank(size: usize) -> Result<u128, LehmerError> {
+    let mut rng = CryptoRng::from_entropy();
+    let factorial = factorial(size)?;
+    Ok(rng.gen_range(0..factorial))
+}
+
+/// Implementing a cryptographic randomization algorithm ['rand_chacha::ChaCha20Rng'],
+/// to generate the uniform rank of a shuffled vector of at most [`MAX_U128_SIZE`] items from a
+/// seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::lehmer::gen_crypto_rank_from_seed;
+///
+/// let seed = b"seed phrase";
+/// let rank = gen_crypto_rank_from_seed(20, seed).unwrap();
+/// assert_eq!(gen_crypto_rank_from_seed(20, seed), Ok(rank));
+/// ```
+#[cfg(feature = "chacha")]
+pub fn gen_crypto_rank_from_seed(size: usize, seed: impl AsRef<[u8]>) -> Result<u128, LehmerError> {
+    let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+    let factorial = factorial(size)?;
+    Ok(rng.gen_range(0..factorial))
+}
+
+/// Implementing a cryptographic randomization algorithm ['rand_chacha::ChaCha20Rng'],
+/// to generate the uniform rank of a shuffled vector of any size
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::lehmer::{gen_crypto_rank_big, unrank_permutation_big};
+///
+/// let rank = gen_crypto_rank_big(52);
+/// let deck = unrank_permutation_big(52, &rank).unwrap();
+/// ```
+#[cfg(all(feature = "chacha", entropy))]
+pub fn gen_crypto_rank_big(size: usize) -> BigUint {
+    let mut rng = CryptoRng::from_entropy();
+    rng.gen_biguint_below(&factorial_big(size))
+}
+
+/// Implementing a cryptographic randomization algorithm ['rand_chacha::ChaCha20Rng'],
+/// to generate the uniform rank of a shuffled vector of any size from a seed
+#[cfg(feature = "chacha")]
+pub fn gen_crypto_rank_big_from_seed(size: usize, seed: impl AsRef<[u8]>) -> BigUint {
+    let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+    rng.gen_biguint_below(&factorial_big(size))
+}
@@ -4,53 +4,312 @@
 //! We define *predictive* as the ability for users to define the traits of a vector, with some sub-set of indices, to return the shuffled positions of those indices. This is a one-time operation that only computes the final shuffled locations of the input indices.
 //!
 //! All algorithms can handle cryptographic, or non-cryptographic shuffling, with all shuffling implementations derived from an optimized version of Durstenfeld's modern implementation of the Fisher-Yates shuffling algo.
-
-use rand::{Rng, SeedableRng};
-use rand_chacha::ChaCha20Rng;
-use sha2::{Sha256, *};
+//!
+//! Without the default `std` feature the crate is `no_std` and only needs `alloc`. The seeded `gen_*` functions, the permutations of [`Shuffle`] and the predictions of [`ordered`], which fill a slice or return a `BTreeMap`, stay available, while everything returning a `HashMap` or seeding from entropy needs `std`.
+//!
+//! The random number generators and the seed hasher are picked with features, all but `blake3` on by default. At least one of `sha2` and `blake3` must be enabled. When both are, for example because two dependents of the crate ask for different hashers, SHA-256 takes precedence, so features stay additive and default builds never change their results:
+//!
+//! - `fastrand`: the `fastrand_*`, `modern_*`, `predictive_*` and `batch_predictive_*` functions, with [`fastrand::Rng`]
+//! - `chacha`: the `crypto_*` functions, [`constant_time`] and [`CryptoRng`], with [`rand_chacha::ChaCha20Rng`]
+//! - `sha2`: seeds are hashed with SHA-256, which [`keyed`], [`transcript`] and [`record`] also need
+//! - `blake3`: seeds are hashed with BLAKE3 when `sha2` is disabled, which changes every seeded result; build with `default-features = false` and make sure no other dependent enables `sha2`
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "sha2", feature = "blake3")))]
+compile_error!("predictive_shuffle needs a seed hasher, enable the `sha2` or `blake3` feature");
+
+use alloc::format;
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(all(feature = "std", any(feature = "chacha", feature = "fastrand")))]
+use core::borrow::Borrow;
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+use rng::Draw;
+#[cfg(all(feature = "std", any(feature = "chacha", feature = "fastrand")))]
 use std::collections::HashMap;
 
+#[cfg(all(feature = "ndarray", any(feature = "chacha", feature = "fastrand")))]
+pub mod axis;
+#[cfg(feature = "beacon")]
+pub mod beacon;
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub mod builder;
+#[cfg(all(feature = "std", any(feature = "chacha", feature = "fastrand")))]
+pub mod canonical;
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub mod co_shuffle;
+#[cfg(feature = "chacha")]
+pub mod constant_time;
+#[cfg(feature = "external")]
+pub mod external;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(
+    feature = "beacon",
+    all(feature = "std", feature = "sha2", feature = "chacha")
+))]
+mod hex;
+#[cfg(all(
+    feature = "std",
+    feature = "sha2",
+    feature = "chacha",
+    feature = "fastrand"
+))]
+pub mod keyed;
+#[cfg(feature = "lehmer")]
+pub mod lehmer;
+#[cfg(all(feature = "std", feature = "sha2", feature = "chacha"))]
+pub mod multiparty;
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub mod ordered;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(all(
+    feature = "std",
+    feature = "sha2",
+    feature = "chacha",
+    feature = "fastrand"
+))]
+pub mod record;
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub mod reservoir;
+mod rng;
+mod seed;
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub mod shuffler;
+#[cfg(all(
+    any(feature = "arrow", feature = "polars"),
+    any(feature = "chacha", feature = "fastrand")
+))]
+pub mod table;
+#[cfg(all(feature = "std", feature = "sha2", feature = "chacha"))]
+pub mod transcript;
+#[cfg(feature = "vrf")]
+pub mod vrf;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub use builder::ShuffleBuilder;
+#[cfg(feature = "chacha")]
+pub use rng::{CryptoRng, CryptoRngState};
+pub use seed::Seed;
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub use shuffler::Shuffler;
+
+/// Shuffles of a collection and predictions of where its items end up
+///
+/// The `*_ref` methods borrow their inputs and forward to the owned methods by default, so an
+/// implementation only has to provide the owned ones.
 pub trait Shuffle<T> {
+    #[cfg(all(feature = "fastrand", entropy))]
     fn fastrand_shuffle(&mut self);
+    #[cfg(feature = "fastrand")]
+    #[deprecated(note = "use `fastrand_shuffle_from_seed_ref`, which borrows the seed")]
     fn fastrand_shuffle_from_seed(&mut self, seed: Vec<u8>);
+    #[cfg(feature = "fastrand")]
+    #[allow(deprecated)]
+    fn fastrand_shuffle_from_seed_ref(&mut self, seed: impl AsRef<[u8]>)
+    where
+        Self: Sized,
+    {
+        self.fastrand_shuffle_from_seed(seed.as_ref().to_vec())
+    }
+    #[cfg(all(feature = "fastrand", entropy))]
     fn modern_shuffle(&mut self) -> Vec<T>;
+    #[cfg(feature = "fastrand")]
+    #[deprecated(note = "use `modern_shuffle_from_seed_ref`, which borrows the seed")]
     fn modern_shuffle_from_seed(&mut self, seed: Vec<u8>) -> Vec<T>;
+    #[cfg(feature = "fastrand")]
+    #[allow(deprecated)]
+    fn modern_shuffle_from_seed_ref(&mut self, seed: impl AsRef<[u8]>) -> Vec<T>
+    where
+        Self: Sized,
+    {
+        self.modern_shuffle_from_seed(seed.as_ref().to_vec())
+    }
+    #[cfg(all(feature = "chacha", entropy))]
     fn crypto_modern_shuffle(&mut self) -> Vec<T>;
+    #[cfg(feature = "chacha")]
+    #[deprecated(note = "use `crypto_modern_shuffle_from_seed_ref`, which borrows the seed")]
     fn crypto_modern_shuffle_from_seed(&mut self, seed: Vec<u8>) -> Vec<T>;
+    #[cfg(feature = "chacha")]
+    #[allow(deprecated)]
+    fn crypto_modern_shuffle_from_seed_ref(&mut self, seed: impl AsRef<[u8]>) -> Vec<T>
+    where
+        Self: Sized,
+    {
+        self.crypto_modern_shuffle_from_seed(seed.as_ref().to_vec())
+    }
+    #[cfg(all(feature = "fastrand", entropy))]
+    #[deprecated(note = "use `predictive_shuffle_ref`, which borrows the positions")]
     fn predictive_shuffle(&mut self, positions: Vec<usize>) -> HashMap<usize, usize>;
+    #[cfg(all(feature = "fastrand", entropy))]
+    #[allow(deprecated)]
+    fn predictive_shuffle_ref(
+        &mut self,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize>
+    where
+        Self: Sized,
+    {
+        self.predictive_shuffle(positions.into_iter().map(|i| *i.borrow()).collect())
+    }
+    #[cfg(all(feature = "fastrand", feature = "std"))]
+    #[deprecated(note = "use `predictive_shuffle_from_seed_ref`, which borrows its inputs")]
     fn predictive_shuffle_from_seed(
         &mut self,
         positions: Vec<usize>,
         seed: Vec<u8>,
     ) -> HashMap<usize, usize>;
+    #[cfg(all(feature = "fastrand", feature = "std"))]
+    #[allow(deprecated)]
+    fn predictive_shuffle_from_seed_ref(
+        &mut self,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize>
+    where
+        Self: Sized,
+    {
+        self.predictive_shuffle_from_seed(
+            positions.into_iter().map(|i| *i.borrow()).collect(),
+            seed.as_ref().to_vec(),
+        )
+    }
+    #[cfg(all(feature = "chacha", entropy))]
+    #[deprecated(note = "use `crypto_predictive_shuffle_ref`, which borrows the positions")]
     fn crypto_predictive_shuffle(&mut self, positions: Vec<usize>) -> HashMap<usize, usize>;
+    #[cfg(all(feature = "chacha", entropy))]
+    #[allow(deprecated)]
+    fn crypto_predictive_shuffle_ref(
+        &mut self,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize>
+    where
+        Self: Sized,
+    {
+        self.crypto_predictive_shuffle(positions.into_iter().map(|i| *i.borrow()).collect())
+    }
+    #[cfg(all(feature = "chacha", feature = "std"))]
+    #[deprecated(note = "use `crypto_predictive_shuffle_from_seed_ref`, which borrows its inputs")]
     fn crypto_predictive_shuffle_from_seed(
         &mut self,
         positions: Vec<usize>,
         seed: Vec<u8>,
     ) -> HashMap<usize, usize>;
+    #[cfg(all(feature = "chacha", feature = "std"))]
+    #[allow(deprecated)]
+    fn crypto_predictive_shuffle_from_seed_ref(
+        &mut self,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize>
+    where
+        Self: Sized,
+    {
+        self.crypto_predictive_shuffle_from_seed(
+            positions.into_iter().map(|i| *i.borrow()).collect(),
+            seed.as_ref().to_vec(),
+        )
+    }
+    #[cfg(all(feature = "fastrand", entropy))]
+    #[deprecated(note = "use `batch_predictive_shuffle_ref`, which borrows the positions")]
     fn batch_predictive_shuffle(
         &mut self,
         batch: usize,
         positions: Vec<usize>,
     ) -> HashMap<usize, usize>;
+    #[cfg(all(feature = "fastrand", entropy))]
+    #[allow(deprecated)]
+    fn batch_predictive_shuffle_ref(
+        &mut self,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize>
+    where
+        Self: Sized,
+    {
+        self.batch_predictive_shuffle(batch, positions.into_iter().map(|i| *i.borrow()).collect())
+    }
+    #[cfg(all(feature = "fastrand", feature = "std"))]
+    #[deprecated(note = "use `batch_predictive_shuffle_from_seed_ref`, which borrows its inputs")]
     fn batch_predictive_shuffle_from_seed(
         &mut self,
         batch: usize,
         positions: Vec<usize>,
         seed: Vec<u8>,
     ) -> HashMap<usize, usize>;
+    #[cfg(all(feature = "fastrand", feature = "std"))]
+    #[allow(deprecated)]
+    fn batch_predictive_shuffle_from_seed_ref(
+        &mut self,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize>
+    where
+        Self: Sized,
+    {
+        self.batch_predictive_shuffle_from_seed(
+            batch,
+            positions.into_iter().map(|i| *i.borrow()).collect(),
+            seed.as_ref().to_vec(),
+        )
+    }
+    #[cfg(all(feature = "chacha", entropy))]
+    #[deprecated(note = "use `crypto_batch_predictive_shuffle_ref`, which borrows the positions")]
     fn crypto_batch_predictive_shuffle(
         &mut self,
         batch: usize,
         positions: Vec<usize>,
     ) -> HashMap<usize, usize>;
+    #[cfg(all(feature = "chacha", entropy))]
+    #[allow(deprecated)]
+    fn crypto_batch_predictive_shuffle_ref(
+        &mut self,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize>
+    where
+        Self: Sized,
+    {
+        self.crypto_batch_predictive_shuffle(
+            batch,
+            positions.into_iter().map(|i| *i.borrow()).collect(),
+        )
+    }
+    #[cfg(all(feature = "chacha", feature = "std"))]
+    #[deprecated(
+        note = "use `crypto_batch_predictive_shuffle_from_seed_ref`, which borrows its inputs"
+    )]
     fn crypto_batch_predictive_shuffle_from_seed(
         &mut self,
         batch: usize,
         positions: Vec<usize>,
         seed: Vec<u8>,
     ) -> HashMap<usize, usize>;
+    #[cfg(all(feature = "chacha", feature = "std"))]
+    #[allow(deprecated)]
+    fn crypto_batch_predictive_shuffle_from_seed_ref(
+        &mut self,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize>
+    where
+        Self: Sized,
+    {
+        self.crypto_batch_predictive_shuffle_from_seed(
+            batch,
+            positions.into_iter().map(|i| *i.borrow()).collect(),
+            seed.as_ref().to_vec(),
+        )
+    }
 }
 
 impl<T> Shuffle<T> for Vec<T> {
@@ -66,9 +325,30 @@ impl<T> Shuffle<T> for Vec<T> {
     ///
     /// vec.fastrand_shuffle()
     /// ```   
+    #[cfg(all(feature = "fastrand", entropy))]
     fn fastrand_shuffle(&mut self) {
         let mut rng: fastrand::Rng = fastrand::Rng::new();
-        rng.shuffle(self.as_mut_slice());
+        rng.shuffle_slice(self.as_mut_slice());
+    }
+
+    /// Shuffle a given vector from Seed with ['fastrand::Rng']
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..10).collect();
+    /// let seed = b"seed phrase";
+    /// vec.fastrand_shuffle_from_seed_ref(seed)
+    /// ```   
+    #[cfg(feature = "fastrand")]
+    fn fastrand_shuffle_from_seed_ref(&mut self, seed: impl AsRef<[u8]>) {
+        let seed = byte_array(seed.as_ref());
+        let seed_int = u64::from_be_bytes(seed);
+        let mut rng: fastrand::Rng = fastrand::Rng::with_seed(seed_int);
+        rng.shuffle_slice(self.as_mut_slice());
     }
 
     /// Shuffle a given vector from Seed with ['fastrand::Rng']
@@ -83,12 +363,11 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let seed = b"seed phrase".to_vec();
     /// vec.fastrand_shuffle_from_seed(seed)
     /// ```   
+    #[cfg(feature = "fastrand")]
     fn fastrand_shuffle_from_seed(&mut self, seed: Vec<u8>) {
-        let mut rng: fastrand::Rng = fastrand::Rng::new();
-        let seed = byte_array(&seed);
-        let seed_int = u64::from_be_bytes(seed);
-        rng.seed(seed_int);
-        rng.shuffle(self.as_mut_slice());
+        let mut seed = seed;
+        self.fastrand_shuffle_from_seed_ref(&seed);
+        rng::wipe(&mut seed);
     }
 
     /// Shuffle a given vector with the modern Fisher-Yates Algorithm
@@ -102,17 +381,10 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let mut vec: Vec<usize> = (0..10).collect();
     /// let shuffled_vec = vec.modern_shuffle();
     /// ```   
+    #[cfg(all(feature = "fastrand", entropy))]
     fn modern_shuffle(&mut self) -> Vec<T> {
-        let size = self.len();
         let mut rng = fastrand::Rng::new();
-
-        let mut new_vec = vec![];
-        for i in (0..size).rev() {
-            let x: usize = rng.usize(0..=i);
-
-            new_vec.push(self.swap_remove(x));
-        }
-        new_vec
+        modern_with(self, &mut rng)
     }
 
     /// Implementing a crpytographic randomization algorithm ['rand_chacha::ChaCha20Rng'],
@@ -127,17 +399,30 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let mut vec: Vec<usize> = (0..10).collect();
     /// let shuffled_vec = vec.crypto_modern_shuffle();
     /// ```   
+    #[cfg(all(feature = "chacha", entropy))]
     fn crypto_modern_shuffle(&mut self) -> Vec<T> {
-        let size = self.len();
-        let mut rng = ChaCha20Rng::from_entropy();
-
-        let mut new_vec = vec![];
-        for i in (0..size).rev() {
-            let x: usize = rng.gen_range(0..=i);
+        let mut rng = CryptoRng::from_entropy();
+        modern_with(self, &mut rng)
+    }
 
-            new_vec.push(self.swap_remove(x));
-        }
-        new_vec
+    /// Shuffle Vector from Seed with the modern Fisher-Yates Algorithm
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..10).collect();
+    /// let seed = b"seed phrase";
+    /// let shuffled_vec = vec.modern_shuffle_from_seed_ref(seed);
+    /// ```   
+    #[cfg(feature = "fastrand")]
+    fn modern_shuffle_from_seed_ref(&mut self, seed: impl AsRef<[u8]>) -> Vec<T> {
+        let seed = byte_array(seed.as_ref());
+        let seed_int = u64::from_be_bytes(seed);
+        let mut rng = fastrand::Rng::with_seed(seed_int);
+        modern_with(self, &mut rng)
     }
 
     /// Shuffle Vector from Seed with the modern Fisher-Yates Algorithm
@@ -152,22 +437,31 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let seed = b"seed phrase".to_vec();
     /// let shuffled_vec = vec.modern_shuffle_from_seed(seed);
     /// ```   
+    #[cfg(feature = "fastrand")]
     fn modern_shuffle_from_seed(&mut self, seed: Vec<u8>) -> Vec<T> {
-        let size = self.len();
-        let seed = byte_array(&seed);
-
-        let seed_int = u64::from_be_bytes(seed);
-
-        let mut rng = fastrand::Rng::new();
-        rng.seed(seed_int);
-
-        let mut new_vec = vec![];
-        for i in (0..size).rev() {
-            let x: usize = rng.usize(0..=i);
+        let mut seed = seed;
+        let result = self.modern_shuffle_from_seed_ref(&seed);
+        rng::wipe(&mut seed);
+        result
+    }
 
-            new_vec.push(self.swap_remove(x));
-        }
-        new_vec
+    /// Implementing a crpytographic rnadomization algorithm ['rand_chacha::ChaCha20Rng'],
+    /// shuffle a given vector from with a with the modern Fisher-Yates Algorithm
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..10).collect();
+    /// let seed = b"seed phrase";
+    /// let shuffled_vec = vec.crypto_modern_shuffle_from_seed_ref(seed);
+    /// ```  
+    #[cfg(feature = "chacha")]
+    fn crypto_modern_shuffle_from_seed_ref(&mut self, seed: impl AsRef<[u8]>) -> Vec<T> {
+        let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+        modern_with(self, &mut rng)
     }
 
     /// Implementing a crpytographic rnadomization algorithm ['rand_chacha::ChaCha20Rng'],
@@ -183,21 +477,33 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let seed = b"seed phrase".to_vec();
     /// let shuffled_vec = vec.crypto_modern_shuffle_from_seed(seed);
     /// ```  
+    #[cfg(feature = "chacha")]
     fn crypto_modern_shuffle_from_seed(&mut self, seed: Vec<u8>) -> Vec<T> {
-        let size = self.len();
-        let seed = byte_array(&seed);
-
-        let seed_int = u64::from_be_bytes(seed);
-
-        let mut rng = ChaCha20Rng::seed_from_u64(seed_int);
-
-        let mut new_vec = vec![];
-        for i in (0..size).rev() {
-            let x: usize = rng.gen_range(0..=i);
+        let mut seed = seed;
+        let result = self.crypto_modern_shuffle_from_seed_ref(&seed);
+        rng::wipe(&mut seed);
+        result
+    }
 
-            new_vec.push(self.swap_remove(x));
-        }
-        new_vec
+    /// Predict Shuffled Position of Items
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..10).collect();
+    /// let seed = b"seed phrase";
+    /// let positions = vec![1,5];
+    /// let shuffled_vec = vec.predictive_shuffle_ref(&positions);
+    /// ```  
+    #[cfg(all(feature = "fastrand", entropy))]
+    fn predictive_shuffle_ref(
+        &mut self,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize> {
+        gen_predictive_shuffle_ref(self.len(), positions)
     }
 
     /// Predict Shuffled Position of Items
@@ -213,38 +519,33 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let positions = vec![1,5];
     /// let shuffled_vec = vec.predictive_shuffle(positions);
     /// ```  
+    #[cfg(all(feature = "fastrand", entropy))]
     fn predictive_shuffle(&mut self, positions: Vec<usize>) -> HashMap<usize, usize> {
-        let size = self.len();
-        // random function
-        let mut rand: fastrand::Rng = fastrand::Rng::new();
-
-        // mutable structures
-        let mut vec: Vec<Option<usize>> = vec![None; self.len()];
-        let mut peers = positions.len();
+        self.predictive_shuffle_ref(positions)
+    }
 
-        // fill vec
-        for i in positions {
-            vec[i] = Some(i)
-        }
-        let mut new_map = HashMap::new();
-        for i in (0..size).rev() {
-            let x: usize = rand.usize(0..=i);
-
-            if let Some(item) = vec[x] {
-                new_map.insert(item, i);
-                peers -= 1;
-                vec[x] = None;
-                if peers == 0 {
-                    break;
-                }
-            }
-
-            if let Some(item) = vec[i] {
-                vec[x] = Some(item);
-                vec[i] = None
-            }
-        }
-        new_map
+    /// Predict Shuffled Position of Items from Seed
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..100).collect();
+    /// let seed = b"seed phrase";
+    /// let positions = vec![1,5];
+    /// let new_positions = vec.predictive_shuffle_from_seed_ref(&positions, seed);
+    /// assert_eq!(new_positions.get(&1), Some(&79));
+    /// assert_eq!(new_positions.get(&5), Some(&86));
+    /// ```  
+    #[cfg(all(feature = "fastrand", feature = "std"))]
+    fn predictive_shuffle_from_seed_ref(
+        &mut self,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize> {
+        gen_predictive_shuffle_from_seed_ref(self.len(), positions, seed)
     }
 
     /// Predict Shuffled Position of Items from Seed
@@ -262,47 +563,37 @@ impl<T> Shuffle<T> for Vec<T> {
     /// assert_eq!(new_positions.get(&1), Some(&79));
     /// assert_eq!(new_positions.get(&5), Some(&86));
     /// ```  
+    #[cfg(all(feature = "fastrand", feature = "std"))]
     fn predictive_shuffle_from_seed(
         &mut self,
         positions: Vec<usize>,
         seed: Vec<u8>,
     ) -> HashMap<usize, usize> {
-        let size = self.len();
-        // seed
-        let seed = byte_array(&seed);
-        let seed_int = u64::from_be_bytes(seed);
-
-        // random function
-        let mut rand: fastrand::Rng = fastrand::Rng::new();
-        rand.seed(seed_int);
-
-        // mutable structures
-        let mut vec: Vec<Option<usize>> = vec![None; self.len()];
-        let mut peers = positions.len();
+        let mut seed = seed;
+        let result = self.predictive_shuffle_from_seed_ref(positions, &seed);
+        rng::wipe(&mut seed);
+        result
+    }
 
-        // fill vec
-        for i in positions {
-            vec[i] = Some(i)
-        }
-        let mut new_map = HashMap::new();
-        for i in (0..size).rev() {
-            let x: usize = rand.usize(0..=i);
-
-            if let Some(item) = vec[x] {
-                new_map.insert(item, i);
-                peers -= 1;
-                vec[x] = None;
-                if peers == 0 {
-                    break;
-                }
-            }
-
-            if let Some(item) = vec[i] {
-                vec[x] = Some(item);
-                vec[i] = None
-            }
-        }
-        new_map
+    /// Predict Shuffled Position of Items
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..10).collect();
+    /// let seed = b"seed phrase";
+    /// let positions = vec![1,5];
+    /// let shuffled_vec = vec.crypto_predictive_shuffle_ref(&positions);
+    /// ```  
+    #[cfg(all(feature = "chacha", entropy))]
+    fn crypto_predictive_shuffle_ref(
+        &mut self,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize> {
+        gen_crypto_predictive_shuffle_ref(self.len(), positions)
     }
 
     /// Predict Shuffled Position of Items
@@ -318,38 +609,33 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let positions = vec![1,5];
     /// let shuffled_vec = vec.crypto_predictive_shuffle(positions);
     /// ```  
+    #[cfg(all(feature = "chacha", entropy))]
     fn crypto_predictive_shuffle(&mut self, positions: Vec<usize>) -> HashMap<usize, usize> {
-        let size = self.len();
-        // random function
-        let mut rng = ChaCha20Rng::from_entropy();
-
-        // mutable structures
-        let mut vec: Vec<Option<usize>> = vec![None; self.len()];
-        let mut peers = positions.len();
+        self.crypto_predictive_shuffle_ref(positions)
+    }
 
-        // fill vec
-        for i in positions {
-            vec[i] = Some(i)
-        }
-        let mut new_map = HashMap::new();
-        for i in (0..size).rev() {
-            let x: usize = rng.gen_range(0..=i);
-
-            if let Some(item) = vec[x] {
-                new_map.insert(item, i);
-                peers -= 1;
-                vec[x] = None;
-                if peers == 0 {
-                    break;
-                }
-            }
-
-            if let Some(item) = vec[i] {
-                vec[x] = Some(item);
-                vec[i] = None
-            }
-        }
-        new_map
+    /// Predict Shuffled Position of Items from Seed
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..100).collect();
+    /// let seed = b"seed phrase";
+    /// let positions = vec![1,5];
+    /// let new_positions = vec.crypto_predictive_shuffle_from_seed_ref(&positions, seed);
+    /// assert_eq!(new_positions.get(&1), Some(&2));
+    /// assert_eq!(new_positions.get(&5), Some(&77));
+    /// ```  
+    #[cfg(all(feature = "chacha", feature = "std"))]
+    fn crypto_predictive_shuffle_from_seed_ref(
+        &mut self,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize> {
+        gen_crypto_predictive_shuffle_from_seed_ref(self.len(), positions, seed)
     }
 
     /// Predict Shuffled Position of Items from Seed
@@ -364,50 +650,41 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let seed = b"seed phrase".to_vec();
     /// let positions = vec![1,5];
     /// let new_positions = vec.crypto_predictive_shuffle_from_seed(positions, seed);
-    /// assert_eq!(new_positions.get(&1), Some(&8));
-    /// assert_eq!(new_positions.get(&5), Some(&91));
+    /// assert_eq!(new_positions.get(&1), Some(&2));
+    /// assert_eq!(new_positions.get(&5), Some(&77));
     /// ```  
+    #[cfg(all(feature = "chacha", feature = "std"))]
     fn crypto_predictive_shuffle_from_seed(
         &mut self,
         positions: Vec<usize>,
         seed: Vec<u8>,
     ) -> HashMap<usize, usize> {
-        let size = self.len();
-        // seed
-        let seed = byte_array(&seed);
-        let seed_int = u64::from_be_bytes(seed);
-
-        // random function
-        let mut rng = ChaCha20Rng::seed_from_u64(seed_int);
-
-        // mutable structures
-        let mut vec: Vec<Option<usize>> = vec![None; self.len()];
-        let mut peers = positions.len();
-
-        // fill vec
-        for i in positions {
-            vec[i] = Some(i)
-        }
+        let mut seed = seed;
+        let result = self.crypto_predictive_shuffle_from_seed_ref(positions, &seed);
+        rng::wipe(&mut seed);
+        result
+    }
 
-        let mut new_map = HashMap::new();
-        for i in (0..size).rev() {
-            let x: usize = rng.gen_range(0..i);
-
-            if let Some(item) = vec[x] {
-                new_map.insert(item, i);
-                peers -= 1;
-                vec[x] = None;
-                if peers == 0 {
-                    break;
-                }
-            }
-
-            if let Some(item) = vec[i] {
-                vec[x] = Some(item);
-                vec[i] = None
-            }
-        }
-        new_map
+    /// Predict Shuffled Position of Items from Seed
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..100).collect();
+    /// let batch = 4;
+    /// let positions = vec![1,5];
+    /// let new_positions = vec.batch_predictive_shuffle_ref(batch, &positions);
+    /// ```  
+    #[cfg(all(feature = "fastrand", entropy))]
+    fn batch_predictive_shuffle_ref(
+        &mut self,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize> {
+        gen_batch_predictive_shuffle_ref(self.len(), batch, positions)
     }
 
     /// Predict Shuffled Position of Items from Seed
@@ -423,52 +700,39 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let positions = vec![1,5];
     /// let new_positions = vec.batch_predictive_shuffle(batch, positions);
     /// ```  
+    #[cfg(all(feature = "fastrand", entropy))]
     fn batch_predictive_shuffle(
         &mut self,
         batch: usize,
         positions: Vec<usize>,
     ) -> HashMap<usize, usize> {
-        let size = self.len();
-
-        // random function
-        let mut rand: fastrand::Rng = fastrand::Rng::new();
-
-        // mutable structures
-        let mut vec: Vec<Option<usize>> = vec![None; self.len()];
-        let mut peers = positions.len();
-
-        // fill vec
-        for i in positions {
-            vec[i] = Some(i)
-        }
+        self.batch_predictive_shuffle_ref(batch, positions)
+    }
 
-        // iterate over all items
-        let mut new_map = HashMap::new();
-        let range = size / batch;
-        let mut randoms: Vec<usize> = (0..range).collect();
-        rand.shuffle(randoms.as_mut_slice());
-        for i in (0..size).rev() {
-            let x: usize = if i == 0 {
-                0
-            } else {
-                randoms[i % randoms.len()] % i
-            };
-
-            if let Some(item) = vec[x] {
-                new_map.insert(item, i);
-                peers -= 1;
-                vec[x] = None;
-                if peers == 0 {
-                    break;
-                }
-            }
-
-            if let Some(item) = vec[i] {
-                vec[x] = Some(item);
-                vec[i] = None
-            }
-        }
-        new_map
+    /// Predict Shuffled Position of Items from Seed
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..100).collect();
+    /// let batch = 4;
+    /// let seed = b"seed phrase";
+    /// let positions = vec![1,5];
+    /// let new_positions = vec.batch_predictive_shuffle_from_seed_ref(batch, &positions, seed);
+    /// assert_eq!(new_positions.get(&1), Some(&96));
+    /// assert_eq!(new_positions.get(&5), Some(&90));
+    /// ```  
+    #[cfg(all(feature = "fastrand", feature = "std"))]
+    fn batch_predictive_shuffle_from_seed_ref(
+        &mut self,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize> {
+        gen_batch_predictive_shuffle_from_seed_ref(self.len(), batch, positions, seed)
     }
 
     /// Predict Shuffled Position of Items from Seed
@@ -487,58 +751,39 @@ impl<T> Shuffle<T> for Vec<T> {
     /// assert_eq!(new_positions.get(&1), Some(&96));
     /// assert_eq!(new_positions.get(&5), Some(&90));
     /// ```  
+    #[cfg(all(feature = "fastrand", feature = "std"))]
     fn batch_predictive_shuffle_from_seed(
         &mut self,
         batch: usize,
         positions: Vec<usize>,
         seed: Vec<u8>,
     ) -> HashMap<usize, usize> {
-        let size = self.len();
-
-        // seed
-        let seed = byte_array(&seed);
-        let seed_int = u64::from_be_bytes(seed);
-
-        // random function
-        let mut rand: fastrand::Rng = fastrand::Rng::new();
-        rand.seed(seed_int);
-
-        // mutable structures
-        let mut vec: Vec<Option<usize>> = vec![None; self.len()];
-        let mut peers = positions.len();
-
-        // fill vec
-        for i in positions {
-            vec[i] = Some(i)
-        }
+        let mut seed = seed;
+        let result = self.batch_predictive_shuffle_from_seed_ref(batch, positions, &seed);
+        rng::wipe(&mut seed);
+        result
+    }
 
-        // iterate over all items
-        let mut new_map = HashMap::new();
-        let range = size / batch;
-        let mut randoms: Vec<usize> = (0..range).collect();
-        rand.shuffle(randoms.as_mut_slice());
-        for i in (0..size).rev() {
-            let x: usize = if i == 0 {
-                0
-            } else {
-                randoms[i % randoms.len()] % i
-            };
-
-            if let Some(item) = vec[x] {
-                new_map.insert(item, i);
-                peers -= 1;
-                vec[x] = None;
-                if peers == 0 {
-                    break;
-                }
-            }
-
-            if let Some(item) = vec[i] {
-                vec[x] = Some(item);
-                vec[i] = None
-            }
-        }
-        new_map
+    /// Predict Shuffled Position of Items from Seed
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..100).collect();
+    /// let batch = 4;
+    /// let positions = vec![1,5];
+    /// let new_positions = vec.crypto_batch_predictive_shuffle_ref(batch, &positions);
+    /// ```  
+    #[cfg(all(feature = "chacha", entropy))]
+    fn crypto_batch_predictive_shuffle_ref(
+        &mut self,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize> {
+        gen_crypto_batch_predictive_shuffle_ref(self.len(), batch, positions)
     }
 
     /// Predict Shuffled Position of Items from Seed
@@ -554,55 +799,39 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let positions = vec![1,5];
     /// let new_positions = vec.crypto_batch_predictive_shuffle(batch, positions);
     /// ```  
+    #[cfg(all(feature = "chacha", entropy))]
     fn crypto_batch_predictive_shuffle(
         &mut self,
         batch: usize,
         positions: Vec<usize>,
     ) -> HashMap<usize, usize> {
-        let size = self.len();
-
-        // random function
-        let mut rng = ChaCha20Rng::from_entropy();
-
-        // mutable structures
-        let mut vec: Vec<Option<usize>> = vec![None; self.len()];
-        let mut peers = positions.len();
-
-        // fill vec
-        for i in positions {
-            vec[i] = Some(i)
-        }
+        self.crypto_batch_predictive_shuffle_ref(batch, positions)
+    }
 
-        // iterate over all items
-        let mut new_map = HashMap::new();
-        let range = size / batch;
-        let mut randoms: Vec<usize> = vec![];
-        for i in (0..size).rev() {
-            let x: usize = if i > size - range {
-                let x = rng.gen_range(0..=i);
-                randoms.push(x);
-                x
-            } else if i == 0 {
-                0
-            } else {
-                randoms[i % randoms.len()] % i
-            };
-
-            if let Some(item) = vec[x] {
-                new_map.insert(item, i);
-                peers -= 1;
-                vec[x] = None;
-                if peers == 0 {
-                    break;
-                }
-            }
-
-            if let Some(item) = vec[i] {
-                vec[x] = Some(item);
-                vec[i] = None
-            }
-        }
-        new_map
+    /// Predict Shuffled Position of Items from Seed
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use predictive_shuffle::Shuffle;
+    ///
+    /// let mut vec: Vec<usize> = (0..100).collect();
+    /// let batch = 4;
+    /// let seed = b"seed phrase";
+    /// let positions = vec![1,5];
+    /// let new_positions = vec.crypto_batch_predictive_shuffle_from_seed_ref(batch, &positions, seed);
+    /// assert_eq!(new_positions.get(&1), Some(&20));
+    /// assert_eq!(new_positions.get(&5), Some(&81));
+    /// ```  
+    #[cfg(all(feature = "chacha", feature = "std"))]
+    fn crypto_batch_predictive_shuffle_from_seed_ref(
+        &mut self,
+        batch: usize,
+        positions: impl IntoIterator<Item = impl Borrow<usize>>,
+        seed: impl AsRef<[u8]>,
+    ) -> HashMap<usize, usize> {
+        gen_crypto_batch_predictive_shuffle_from_seed_ref(self.len(), batch, positions, seed)
     }
 
     /// Predict Shuffled Position of Items from Seed
@@ -618,63 +847,20 @@ impl<T> Shuffle<T> for Vec<T> {
     /// let seed = b"seed phrase".to_vec();
     /// let positions = vec![1,5];
     /// let new_positions = vec.crypto_batch_predictive_shuffle_from_seed(batch, positions, seed);
-    /// assert_eq!(new_positions.get(&1), Some(&7));
-    /// assert_eq!(new_positions.get(&5), Some(&93));
+    /// assert_eq!(new_positions.get(&1), Some(&20));
+    /// assert_eq!(new_positions.get(&5), Some(&81));
     /// ```  
+    #[cfg(all(feature = "chacha", feature = "std"))]
     fn crypto_batch_predictive_shuffle_from_seed(
         &mut self,
         batch: usize,
         positions: Vec<usize>,
         seed: Vec<u8>,
     ) -> HashMap<usize, usize> {
-        let size = self.len();
-
-        // seed
-        let seed = byte_array(&seed);
-        let seed_int = u64::from_be_bytes(seed);
-
-        // random function
-        let mut rng = ChaCha20Rng::seed_from_u64(seed_int);
-
-        // mutable structures
-        let mut vec: Vec<Option<usize>> = vec![None; self.len()];
-        let mut peers = positions.len();
-
-        // fill vec
-        for i in positions {
-            vec[i] = Some(i)
-        }
-
-        // iterate over all items
-        let mut new_map = HashMap::new();
-        let range = size / batch;
-        let mut randoms: Vec<usize> = vec![];
-        for i in (0..size).rev() {
-            let x: usize = if i > size - range {
-                let x = rng.gen_range(0..=i);
-                randoms.push(x);
-                x
-            } else if i == 0 {
-                0
-            } else {
-                randoms[i % randoms.len()] % i
-            };
-
-            if let Some(item) = vec[x] {
-                new_map.insert(item, i);
-                peers -= 1;
-                vec[x] = None;
-                if peers == 0 {
-                    break;
-                }
-            }
-
-            if let Some(item) = vec[i] {
-                vec[x] = Some(item);
-                vec[i] = None
-            }
-        }
-        new_map
+        let mut seed = seed;
+        let result = self.crypto_batch_predictive_shuffle_from_seed_ref(batch, positions, &seed);
+        rng::wipe(&mut seed);
+        result
     }
 }
 
@@ -690,10 +876,11 @@ impl<T> Shuffle<T> for Vec<T> {
 ///
 /// let shuffled_vec = gen_fastrand_shuffle(size);
 /// ```   
+#[cfg(all(feature = "fastrand", entropy))]
 pub fn gen_fastrand_shuffle(size: usize) -> Vec<usize> {
     let mut vec: Vec<usize> = (0..size).collect();
     let mut rng: fastrand::Rng = fastrand::Rng::new();
-    rng.shuffle(vec.as_mut_slice());
+    rng.shuffle_slice(vec.as_mut_slice());
     vec
 }
 
@@ -703,23 +890,44 @@ pub fn gen_fastrand_shuffle(size: usize) -> Vec<usize> {
 ///
 /// Basic usage:
 /// ```rust
-/// use predictive_shuffle::gen_fastrand_shuffle_from_seed;
+/// use predictive_shuffle::gen_fastrand_shuffle_from_seed_ref;
 ///
 /// let size = 10;
-/// let seed = b"seed phrase".to_vec();
-/// let shuffled_vec = gen_fastrand_shuffle_from_seed(size, seed);
+/// let seed = b"seed phrase";
+/// let shuffled_vec = gen_fastrand_shuffle_from_seed_ref(size, seed);
 /// ```   
-pub fn gen_fastrand_shuffle_from_seed(size: usize, seed: Vec<u8>) -> Vec<usize> {
+#[cfg(feature = "fastrand")]
+pub fn gen_fastrand_shuffle_from_seed_ref(size: usize, seed: impl AsRef<[u8]>) -> Vec<usize> {
     let mut vec: Vec<usize> = (0..size).collect();
-    let mut rng: fastrand::Rng = fastrand::Rng::new();
-    let seed = byte_array(&seed);
+    let seed = byte_array(seed.as_ref());
     let seed_int = u64::from_be_bytes(seed);
-    rng.seed(seed_int);
-    rng.shuffle(vec.as_mut_slice());
+    let mut rng: fastrand::Rng = fastrand::Rng::with_seed(seed_int);
+    rng.shuffle_slice(vec.as_mut_slice());
     vec
 }
 
-/// Generate a shuffled vector with the modern Fisher-Yates Algorithm
+/// Generate shuffled vector with from a seed with ['fastrand::Rng']
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_fastrand_shuffle_from_seed;
+///
+/// let size = 10;
+/// let seed = b"seed phrase".to_vec();
+/// let shuffled_vec = gen_fastrand_shuffle_from_seed(size, seed);
+/// ```   
+#[cfg(feature = "fastrand")]
+#[deprecated(note = "use `gen_fastrand_shuffle_from_seed_ref`, which borrows the seed")]
+pub fn gen_fastrand_shuffle_from_seed(size: usize, seed: Vec<u8>) -> Vec<usize> {
+    let mut seed = seed;
+    let result = gen_fastrand_shuffle_from_seed_ref(size, &seed);
+    rng::wipe(&mut seed);
+    result
+}
+
+/// Generate a shuffled vector with the modern Fisher-Yates Algorithm
 ///
 /// ### Example
 ///
@@ -730,16 +938,10 @@ pub fn gen_fastrand_shuffle_from_seed(size: usize, seed: Vec<u8>) -> Vec<usize>
 /// let size = 10;
 /// let shuffled_vec = gen_modern_shuffle(10);
 /// ```   
+#[cfg(all(feature = "fastrand", entropy))]
 pub fn gen_modern_shuffle(size: usize) -> Vec<usize> {
     let mut rng = fastrand::Rng::new();
-    let mut vec: Vec<usize> = (0..size).collect();
-    let mut new_vec = vec![];
-    for i in (0..size).rev() {
-        let x: usize = rng.usize(0..=i);
-
-        new_vec.push(vec.swap_remove(x));
-    }
-    new_vec
+    modern_with(&mut (0..size).collect(), &mut rng)
 }
 
 /// Implementing a crpytographic randomization algorithm ['rand_chacha::ChaCha20Rng'],
@@ -754,17 +956,30 @@ pub fn gen_modern_shuffle(size: usize) -> Vec<usize> {
 /// let size = 10;
 /// let shuffled_vec = gen_crypto_modern_shuffle(size);
 /// ```   
+#[cfg(all(feature = "chacha", entropy))]
 pub fn gen_crypto_modern_shuffle(size: usize) -> Vec<usize> {
-    let mut vec: Vec<usize> = (0..size).collect();
-    let mut rng = ChaCha20Rng::from_entropy();
-
-    let mut new_vec = vec![];
-    for i in (0..size).rev() {
-        let x: usize = rng.gen_range(0..=i);
+    let mut rng = CryptoRng::from_entropy();
+    modern_with(&mut (0..size).collect(), &mut rng)
+}
 
-        new_vec.push(vec.swap_remove(x));
-    }
-    new_vec
+/// Generate a shuffled vector from a seed with the modern Fisher-Yates Algorithm
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_modern_shuffle_from_seed_ref;
+///
+/// let size = 10;
+/// let seed = b"seed phrase";
+/// let shuffled_vec = gen_modern_shuffle_from_seed_ref(size, seed);
+/// ```   
+#[cfg(feature = "fastrand")]
+pub fn gen_modern_shuffle_from_seed_ref(size: usize, seed: impl AsRef<[u8]>) -> Vec<usize> {
+    let seed = byte_array(seed.as_ref());
+    let seed_int = u64::from_be_bytes(seed);
+    let mut rng = fastrand::Rng::with_seed(seed_int);
+    modern_with(&mut (0..size).collect(), &mut rng)
 }
 
 /// Generate a shuffled vector from a seed with the modern Fisher-Yates Algorithm
@@ -779,22 +994,32 @@ pub fn gen_crypto_modern_shuffle(size: usize) -> Vec<usize> {
 /// let seed = b"seed phrase".to_vec();
 /// let shuffled_vec = gen_modern_shuffle_from_seed(size, seed);
 /// ```   
+#[cfg(feature = "fastrand")]
+#[deprecated(note = "use `gen_modern_shuffle_from_seed_ref`, which borrows the seed")]
 pub fn gen_modern_shuffle_from_seed(size: usize, seed: Vec<u8>) -> Vec<usize> {
-    let mut vec: Vec<usize> = (0..size).collect();
-    let seed = byte_array(&seed);
-
-    let seed_int = u64::from_be_bytes(seed);
-
-    let mut rng = fastrand::Rng::new();
-    rng.seed(seed_int);
-
-    let mut new_vec = vec![];
-    for i in (0..size).rev() {
-        let x: usize = rng.usize(0..=i);
+    let mut seed = seed;
+    let result = gen_modern_shuffle_from_seed_ref(size, &seed);
+    rng::wipe(&mut seed);
+    result
+}
 
-        new_vec.push(vec.swap_remove(x));
-    }
-    new_vec
+/// Implementing a crpytographic rnadomization algorithm ['rand_chacha::ChaCha20Rng'],
+/// shuffle a given vector from with a with the modern Fisher-Yates Algorithm
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_crypto_modern_shuffle_from_seed_ref;
+///
+/// let size = 10;
+/// let seed = b"seed phrase";
+/// let shuffled_vec = gen_crypto_modern_shuffle_from_seed_ref(size, seed);
+/// ```  
+#[cfg(feature = "chacha")]
+pub fn gen_crypto_modern_shuffle_from_seed_ref(size: usize, seed: impl AsRef<[u8]>) -> Vec<usize> {
+    let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+    modern_with(&mut (0..size).collect(), &mut rng)
 }
 
 /// Implementing a crpytographic rnadomization algorithm ['rand_chacha::ChaCha20Rng'],
@@ -810,21 +1035,35 @@ pub fn gen_modern_shuffle_from_seed(size: usize, seed: Vec<u8>) -> Vec<usize> {
 /// let seed = b"seed phrase".to_vec();
 /// let shuffled_vec = gen_crypto_modern_shuffle_from_seed(size, seed);
 /// ```  
+#[cfg(feature = "chacha")]
+#[deprecated(note = "use `gen_crypto_modern_shuffle_from_seed_ref`, which borrows the seed")]
 pub fn gen_crypto_modern_shuffle_from_seed(size: usize, seed: Vec<u8>) -> Vec<usize> {
-    let mut vec: Vec<usize> = (0..10).collect();
-    let seed = byte_array(&seed);
-
-    let seed_int = u64::from_be_bytes(seed);
-
-    let mut rng = ChaCha20Rng::seed_from_u64(seed_int);
-
-    let mut new_vec = vec![];
-    for i in (0..size).rev() {
-        let x: usize = rng.gen_range(0..=i);
+    let mut seed = seed;
+    let result = gen_crypto_modern_shuffle_from_seed_ref(size, &seed);
+    rng::wipe(&mut seed);
+    result
+}
 
-        new_vec.push(vec.swap_remove(x));
-    }
-    new_vec
+/// Predict shuffled position from size and starting position(s)
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_predictive_shuffle_ref;
+///
+/// let size = 100;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let shuffled_vec = gen_predictive_shuffle_ref(size, &positions);
+/// ```  
+#[cfg(all(feature = "fastrand", entropy))]
+pub fn gen_predictive_shuffle_ref(
+    size: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+) -> HashMap<usize, usize> {
+    let mut rand: fastrand::Rng = fastrand::Rng::new();
+    predictive_with(size, positions, &mut rand)
 }
 
 /// Predict shuffled position from size and starting position(s)
@@ -840,37 +1079,37 @@ pub fn gen_crypto_modern_shuffle_from_seed(size: usize, seed: Vec<u8>) -> Vec<us
 /// let positions = vec![1,5];
 /// let shuffled_vec = gen_predictive_shuffle(size, positions);
 /// ```  
+#[cfg(all(feature = "fastrand", entropy))]
+#[deprecated(note = "use `gen_predictive_shuffle_ref`, which borrows the positions")]
 pub fn gen_predictive_shuffle(size: usize, positions: Vec<usize>) -> HashMap<usize, usize> {
-    // random function
-    let mut rand: fastrand::Rng = fastrand::Rng::new();
-
-    // mutable structures
-    let mut vec: Vec<Option<usize>> = vec![None; size];
-    let mut peers = positions.len();
-
-    // fill vec
-    for i in positions {
-        vec[i] = Some(i)
-    }
-    let mut new_map = HashMap::new();
-    for i in (0..size).rev() {
-        let x: usize = rand.usize(0..=i);
-
-        if let Some(item) = vec[x] {
-            new_map.insert(item, i);
-            peers -= 1;
-            vec[x] = None;
-            if peers == 0 {
-                break;
-            }
-        }
+    gen_predictive_shuffle_ref(size, positions)
+}
 
-        if let Some(item) = vec[i] {
-            vec[x] = Some(item);
-            vec[i] = None
-        }
-    }
-    new_map
+/// Predict shuffled position from size and starting position(s) with a seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_predictive_shuffle_from_seed_ref;
+///
+/// let size = 100;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let new_positions = gen_predictive_shuffle_from_seed_ref(size, &positions, seed);
+/// assert_eq!(new_positions.get(&1), Some(&79));
+/// assert_eq!(new_positions.get(&5), Some(&86));
+/// ```  
+#[cfg(all(feature = "fastrand", feature = "std"))]
+pub fn gen_predictive_shuffle_from_seed_ref(
+    size: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    seed: impl AsRef<[u8]>,
+) -> HashMap<usize, usize> {
+    let seed = byte_array(seed.as_ref());
+    let seed_int = u64::from_be_bytes(seed);
+    let mut rand: fastrand::Rng = fastrand::Rng::with_seed(seed_int);
+    predictive_with(size, positions, &mut rand)
 }
 
 /// Predict shuffled position from size and starting position(s) with a seed
@@ -888,46 +1127,40 @@ pub fn gen_predictive_shuffle(size: usize, positions: Vec<usize>) -> HashMap<usi
 /// assert_eq!(new_positions.get(&1), Some(&79));
 /// assert_eq!(new_positions.get(&5), Some(&86));
 /// ```  
+#[cfg(all(feature = "fastrand", feature = "std"))]
+#[deprecated(note = "use `gen_predictive_shuffle_from_seed_ref`, which borrows its inputs")]
 pub fn gen_predictive_shuffle_from_seed(
     size: usize,
     positions: Vec<usize>,
     seed: Vec<u8>,
 ) -> HashMap<usize, usize> {
-    // seed
-    let seed = byte_array(&seed);
-    let seed_int = u64::from_be_bytes(seed);
-
-    // random function
-    let mut rand: fastrand::Rng = fastrand::Rng::new();
-    rand.seed(seed_int);
-
-    // mutable structures
-    let mut vec: Vec<Option<usize>> = vec![None; size];
-    let mut peers = positions.len();
-
-    // fill vec
-    for i in positions {
-        vec[i] = Some(i)
-    }
-    let mut new_map = HashMap::new();
-    for i in (0..size).rev() {
-        let x: usize = rand.usize(0..=i);
-
-        if let Some(item) = vec[x] {
-            new_map.insert(item, i);
-            peers -= 1;
-            vec[x] = None;
-            if peers == 0 {
-                break;
-            }
-        }
+    let mut seed = seed;
+    let result = gen_predictive_shuffle_from_seed_ref(size, positions, &seed);
+    rng::wipe(&mut seed);
+    result
+}
 
-        if let Some(item) = vec[i] {
-            vec[x] = Some(item);
-            vec[i] = None
-        }
-    }
-    new_map
+/// Predict Shuffled Position of Items
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_crypto_predictive_shuffle_ref;
+///
+/// let size = 100;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let shuffled_vec = gen_crypto_predictive_shuffle_ref(size, &positions);
+/// ```  
+#[cfg(all(feature = "chacha", entropy))]
+pub fn gen_crypto_predictive_shuffle_ref(
+    size: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+) -> HashMap<usize, usize> {
+    // unlike the seeded crypto_draw, draws from entropy pick the swapped slot from 0..=i
+    let mut rng = CryptoRng::from_entropy();
+    predictive_with(size, positions, &mut rng)
 }
 
 /// Predict Shuffled Position of Items
@@ -943,37 +1176,35 @@ pub fn gen_predictive_shuffle_from_seed(
 /// let positions = vec![1,5];
 /// let shuffled_vec = gen_crypto_predictive_shuffle(size, positions);
 /// ```  
+#[cfg(all(feature = "chacha", entropy))]
+#[deprecated(note = "use `gen_crypto_predictive_shuffle_ref`, which borrows the positions")]
 pub fn gen_crypto_predictive_shuffle(size: usize, positions: Vec<usize>) -> HashMap<usize, usize> {
-    // random function
-    let mut rng = ChaCha20Rng::from_entropy();
-
-    // mutable structures
-    let mut vec: Vec<Option<usize>> = vec![None; size];
-    let mut peers = positions.len();
-
-    // fill vec
-    for i in positions {
-        vec[i] = Some(i)
-    }
-    let mut new_map = HashMap::new();
-    for i in (0..size).rev() {
-        let x: usize = rng.gen_range(0..=i);
-
-        if let Some(item) = vec[x] {
-            new_map.insert(item, i);
-            peers -= 1;
-            vec[x] = None;
-            if peers == 0 {
-                break;
-            }
-        }
+    gen_crypto_predictive_shuffle_ref(size, positions)
+}
 
-        if let Some(item) = vec[i] {
-            vec[x] = Some(item);
-            vec[i] = None
-        }
-    }
-    new_map
+/// Predict Shuffled Position of Items from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_crypto_predictive_shuffle_from_seed_ref;
+///
+/// let size = 100;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let new_positions = gen_crypto_predictive_shuffle_from_seed_ref(size, &positions, seed);
+/// assert_eq!(new_positions.get(&1), Some(&2));
+/// assert_eq!(new_positions.get(&5), Some(&77));
+/// ```  
+#[cfg(all(feature = "chacha", feature = "std"))]
+pub fn gen_crypto_predictive_shuffle_from_seed_ref(
+    size: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    seed: impl AsRef<[u8]>,
+) -> HashMap<usize, usize> {
+    let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+    crypto_predictive_with(size, positions, &mut rng)
 }
 
 /// Predict Shuffled Position of Items from Seed
@@ -988,49 +1219,43 @@ pub fn gen_crypto_predictive_shuffle(size: usize, positions: Vec<usize>) -> Hash
 /// let seed = b"seed phrase".to_vec();
 /// let positions = vec![1,5];
 /// let new_positions = gen_crypto_predictive_shuffle_from_seed(size, positions, seed);
-/// assert_eq!(new_positions.get(&1), Some(&8));
-/// assert_eq!(new_positions.get(&5), Some(&91));
+/// assert_eq!(new_positions.get(&1), Some(&2));
+/// assert_eq!(new_positions.get(&5), Some(&77));
 /// ```  
+#[cfg(all(feature = "chacha", feature = "std"))]
+#[deprecated(note = "use `gen_crypto_predictive_shuffle_from_seed_ref`, which borrows its inputs")]
 pub fn gen_crypto_predictive_shuffle_from_seed(
     size: usize,
     positions: Vec<usize>,
     seed: Vec<u8>,
 ) -> HashMap<usize, usize> {
-    // seed
-    let seed = byte_array(&seed);
-    let seed_int = u64::from_be_bytes(seed);
-
-    // random function
-    let mut rng = ChaCha20Rng::seed_from_u64(seed_int);
-
-    // mutable structures
-    let mut vec: Vec<Option<usize>> = vec![None; size];
-    let mut peers = positions.len();
-
-    // fill vec
-    for i in positions {
-        vec[i] = Some(i)
-    }
-
-    let mut new_map = HashMap::new();
-    for i in (0..size).rev() {
-        let x: usize = rng.gen_range(0..i);
-
-        if let Some(item) = vec[x] {
-            new_map.insert(item, i);
-            peers -= 1;
-            vec[x] = None;
-            if peers == 0 {
-                break;
-            }
-        }
+    let mut seed = seed;
+    let result = gen_crypto_predictive_shuffle_from_seed_ref(size, positions, &seed);
+    rng::wipe(&mut seed);
+    result
+}
 
-        if let Some(item) = vec[i] {
-            vec[x] = Some(item);
-            vec[i] = None
-        }
-    }
-    new_map
+/// Predict Shuffled Position of Items from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_batch_predictive_shuffle_ref;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let positions = vec![1,5];
+/// let new_positions = gen_batch_predictive_shuffle_ref(size, batch, &positions);
+/// ```  
+#[cfg(all(feature = "fastrand", entropy))]
+pub fn gen_batch_predictive_shuffle_ref(
+    size: usize,
+    batch: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+) -> HashMap<usize, usize> {
+    let mut rand: fastrand::Rng = fastrand::Rng::new();
+    batch_predictive_with(size, batch, positions, &mut rand)
 }
 
 /// Predict Shuffled Position of Items from Seed
@@ -1046,50 +1271,43 @@ pub fn gen_crypto_predictive_shuffle_from_seed(
 /// let positions = vec![1,5];
 /// let new_positions = gen_batch_predictive_shuffle(size, batch, positions);
 /// ```  
+#[cfg(all(feature = "fastrand", entropy))]
+#[deprecated(note = "use `gen_batch_predictive_shuffle_ref`, which borrows the positions")]
 pub fn gen_batch_predictive_shuffle(
     size: usize,
     batch: usize,
     positions: Vec<usize>,
 ) -> HashMap<usize, usize> {
-    // random function
-    let mut rand: fastrand::Rng = fastrand::Rng::new();
-
-    // mutable structures
-    let mut vec: Vec<Option<usize>> = vec![None; size];
-    let mut peers = positions.len();
-
-    // fill vec
-    for i in positions {
-        vec[i] = Some(i)
-    }
-
-    // iterate over all items
-    let mut new_map = HashMap::new();
-    let range = size / batch;
-    let mut randoms: Vec<usize> = (0..range).collect();
-    rand.shuffle(randoms.as_mut_slice());
-    for i in (0..size).rev() {
-        let x: usize = if i == 0 {
-            0
-        } else {
-            randoms[i % randoms.len()] % i
-        };
-
-        if let Some(item) = vec[x] {
-            new_map.insert(item, i);
-            peers -= 1;
-            vec[x] = None;
-            if peers == 0 {
-                break;
-            }
-        }
+    gen_batch_predictive_shuffle_ref(size, batch, positions)
+}
 
-        if let Some(item) = vec[i] {
-            vec[x] = Some(item);
-            vec[i] = None
-        }
-    }
-    new_map
+/// Predict Shuffled Position of Items from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_batch_predictive_shuffle_from_seed_ref;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let new_positions = gen_batch_predictive_shuffle_from_seed_ref(size, batch, &positions, seed);
+/// assert_eq!(new_positions.get(&1), Some(&96));
+/// assert_eq!(new_positions.get(&5), Some(&90));
+/// ```  
+#[cfg(all(feature = "fastrand", feature = "std"))]
+pub fn gen_batch_predictive_shuffle_from_seed_ref(
+    size: usize,
+    batch: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    seed: impl AsRef<[u8]>,
+) -> HashMap<usize, usize> {
+    let seed = byte_array(seed.as_ref());
+    let seed_int = u64::from_be_bytes(seed);
+    let mut rand: fastrand::Rng = fastrand::Rng::with_seed(seed_int);
+    batch_predictive_with(size, batch, positions, &mut rand)
 }
 
 /// Predict Shuffled Position of Items from Seed
@@ -1108,56 +1326,41 @@ pub fn gen_batch_predictive_shuffle(
 /// assert_eq!(new_positions.get(&1), Some(&96));
 /// assert_eq!(new_positions.get(&5), Some(&90));
 /// ```  
+#[cfg(all(feature = "fastrand", feature = "std"))]
+#[deprecated(note = "use `gen_batch_predictive_shuffle_from_seed_ref`, which borrows its inputs")]
 pub fn gen_batch_predictive_shuffle_from_seed(
     size: usize,
     batch: usize,
     positions: Vec<usize>,
     seed: Vec<u8>,
 ) -> HashMap<usize, usize> {
-    // seed
-    let seed = byte_array(&seed);
-    let seed_int = u64::from_be_bytes(seed);
-
-    // random function
-    let mut rand: fastrand::Rng = fastrand::Rng::new();
-    rand.seed(seed_int);
-
-    // mutable structures
-    let mut vec: Vec<Option<usize>> = vec![None; size];
-    let mut peers = positions.len();
-
-    // fill vec
-    for i in positions {
-        vec[i] = Some(i)
-    }
-
-    // iterate over all items
-    let mut new_map = HashMap::new();
-    let range = size / batch;
-    let mut randoms: Vec<usize> = (0..range).collect();
-    rand.shuffle(randoms.as_mut_slice());
-    for i in (0..size).rev() {
-        let x: usize = if i == 0 {
-            0
-        } else {
-            randoms[i % randoms.len()] % i
-        };
-
-        if let Some(item) = vec[x] {
-            new_map.insert(item, i);
-            peers -= 1;
-            vec[x] = None;
-            if peers == 0 {
-                break;
-            }
-        }
+    let mut seed = seed;
+    let result = gen_batch_predictive_shuffle_from_seed_ref(size, batch, positions, &seed);
+    rng::wipe(&mut seed);
+    result
+}
 
-        if let Some(item) = vec[i] {
-            vec[x] = Some(item);
-            vec[i] = None
-        }
-    }
-    new_map
+/// Predict Shuffled Position of Items from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_crypto_batch_predictive_shuffle_ref;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let positions = vec![1,5];
+/// let new_positions = gen_crypto_batch_predictive_shuffle_ref(size, batch, &positions);
+/// ```  
+#[cfg(all(feature = "chacha", entropy))]
+pub fn gen_crypto_batch_predictive_shuffle_ref(
+    size: usize,
+    batch: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+) -> HashMap<usize, usize> {
+    let mut rng = CryptoRng::from_entropy();
+    crypto_batch_predictive_with(size, batch, positions, &mut rng)
 }
 
 /// Predict Shuffled Position of Items from Seed
@@ -1173,53 +1376,41 @@ pub fn gen_batch_predictive_shuffle_from_seed(
 /// let positions = vec![1,5];
 /// let new_positions = gen_crypto_batch_predictive_shuffle(size, batch, positions);
 /// ```  
+#[cfg(all(feature = "chacha", entropy))]
+#[deprecated(note = "use `gen_crypto_batch_predictive_shuffle_ref`, which borrows the positions")]
 pub fn gen_crypto_batch_predictive_shuffle(
     size: usize,
     batch: usize,
     positions: Vec<usize>,
 ) -> HashMap<usize, usize> {
-    // random function
-    let mut rng = ChaCha20Rng::from_entropy();
-
-    // mutable structures
-    let mut vec: Vec<Option<usize>> = vec![None; size];
-    let mut peers = positions.len();
-
-    // fill vec
-    for i in positions {
-        vec[i] = Some(i)
-    }
-
-    // iterate over all items
-    let mut new_map = HashMap::new();
-    let range = size / batch;
-    let mut randoms: Vec<usize> = vec![];
-    for i in (0..size).rev() {
-        let x: usize = if i > size - range {
-            let x = rng.gen_range(0..=i);
-            randoms.push(x);
-            x
-        } else if i == 0 {
-            0
-        } else {
-            randoms[i % randoms.len()] % i
-        };
-
-        if let Some(item) = vec[x] {
-            new_map.insert(item, i);
-            peers -= 1;
-            vec[x] = None;
-            if peers == 0 {
-                break;
-            }
-        }
+    gen_crypto_batch_predictive_shuffle_ref(size, batch, positions)
+}
 
-        if let Some(item) = vec[i] {
-            vec[x] = Some(item);
-            vec[i] = None
-        }
-    }
-    new_map
+/// Predict Shuffled Position of Items from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::gen_crypto_batch_predictive_shuffle_from_seed_ref;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let seed = b"seed phrase";
+/// let positions = vec![1,5];
+/// let new_positions = gen_crypto_batch_predictive_shuffle_from_seed_ref(size, batch, &positions, seed);
+/// assert_eq!(new_positions.get(&1), Some(&20));
+/// assert_eq!(new_positions.get(&5), Some(&81));
+/// ```  
+#[cfg(all(feature = "chacha", feature = "std"))]
+pub fn gen_crypto_batch_predictive_shuffle_from_seed_ref(
+    size: usize,
+    batch: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    seed: impl AsRef<[u8]>,
+) -> HashMap<usize, usize> {
+    let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+    crypto_batch_predictive_with(size, batch, positions, &mut rng)
 }
 
 /// Predict Shuffled Position of Items from Seed
@@ -1235,74 +1426,227 @@ pub fn gen_crypto_batch_predictive_shuffle(
 /// let seed = b"seed phrase".to_vec();
 /// let positions = vec![1,5];
 /// let new_positions = gen_crypto_batch_predictive_shuffle_from_seed(size, batch, positions, seed);
-/// assert_eq!(new_positions.get(&1), Some(&7));
-/// assert_eq!(new_positions.get(&5), Some(&93));
+/// assert_eq!(new_positions.get(&1), Some(&20));
+/// assert_eq!(new_positions.get(&5), Some(&81));
 /// ```  
+#[cfg(all(feature = "chacha", feature = "std"))]
+#[deprecated(
+    note = "use `gen_crypto_batch_predictive_shuffle_from_seed_ref`, which borrows its inputs"
+)]
 pub fn gen_crypto_batch_predictive_shuffle_from_seed(
     size: usize,
     batch: usize,
     positions: Vec<usize>,
     seed: Vec<u8>,
 ) -> HashMap<usize, usize> {
-    // seed
-    let seed = byte_array(&seed);
-    let seed_int = u64::from_be_bytes(seed);
+    let mut seed = seed;
+    let result = gen_crypto_batch_predictive_shuffle_from_seed_ref(size, batch, positions, &seed);
+    rng::wipe(&mut seed);
+    result
+}
+
+/// Modern Fisher-Yates shuffle of `items`, drawing from `rng`
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub(crate) fn modern_with<T>(items: &mut Vec<T>, rng: &mut impl Draw) -> Vec<T> {
+    let size = items.len();
+    let mut new_vec = vec![];
+    for i in (0..size).rev() {
+        let x: usize = rng.index_to(i);
 
-    // random function
-    let mut rng = ChaCha20Rng::seed_from_u64(seed_int);
+        new_vec.push(items.swap_remove(x));
+    }
+    new_vec
+}
 
-    // mutable structures
-    let mut vec: Vec<Option<usize>> = vec![None; size];
-    let mut peers = positions.len();
+/// [`gen_predictive_shuffle_from_seed_ref`], drawing from `rng`
+#[cfg(all(
+    feature = "std",
+    any(feature = "fastrand", all(feature = "chacha", entropy))
+))]
+pub(crate) fn predictive_with(
+    size: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    rng: &mut impl Draw,
+) -> HashMap<usize, usize> {
+    predict_map(size, positions, modern_draw(rng))
+}
 
-    // fill vec
-    for i in positions {
-        vec[i] = Some(i)
+/// [`gen_crypto_predictive_shuffle_from_seed_ref`], drawing from `rng`
+#[cfg(all(feature = "chacha", feature = "std"))]
+pub(crate) fn crypto_predictive_with(
+    size: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    rng: &mut CryptoRng,
+) -> HashMap<usize, usize> {
+    predict_map(size, positions, crypto_draw(rng))
+}
+
+/// [`gen_batch_predictive_shuffle_from_seed_ref`], drawing from `rand`
+#[cfg(all(feature = "fastrand", feature = "std"))]
+pub(crate) fn batch_predictive_with(
+    size: usize,
+    batch: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    rand: &mut fastrand::Rng,
+) -> HashMap<usize, usize> {
+    predict_map(size, positions, batch_draw(size, batch, rand))
+}
+
+/// [`gen_crypto_batch_predictive_shuffle_from_seed_ref`], drawing from `rng`
+#[cfg(all(feature = "chacha", feature = "std"))]
+pub(crate) fn crypto_batch_predictive_with(
+    size: usize,
+    batch: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    rng: &mut CryptoRng,
+) -> HashMap<usize, usize> {
+    predict_map(size, positions, crypto_batch_draw(size, batch, rng))
+}
+
+/// [`ordered::predict_into`], collected into a `HashMap`
+///
+/// A repeated position keeps the shuffled position of its last occurrence, which is the one
+/// `predict_into` tracks.
+#[cfg(all(any(feature = "chacha", feature = "fastrand"), feature = "std"))]
+fn predict_map(
+    size: usize,
+    positions: impl IntoIterator<Item = impl Borrow<usize>>,
+    draw: impl FnMut(usize) -> usize,
+) -> HashMap<usize, usize> {
+    let positions: Vec<usize> = positions.into_iter().map(|i| *i.borrow()).collect();
+    let mut out = vec![size; positions.len()];
+    ordered::predict_into(size, &positions, &mut out, draw);
+    positions.into_iter().zip(out).collect()
+}
+
+/// Check that every position is below `size` and none is repeated, returning the first one that
+/// is not
+///
+/// Every entry point that takes positions from a caller maps the error into its own error type.
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub(crate) fn check_positions(size: usize, positions: &[usize]) -> Result<(), usize> {
+    let mut seen = vec![false; size];
+    for &position in positions {
+        if position >= size || seen[position] {
+            return Err(position);
+        }
+        seen[position] = true;
     }
+    Ok(())
+}
 
-    // iterate over all items
-    let mut new_map = HashMap::new();
+/// Check that a batch size is between 1 and `size`, the sizes every `*batch_predictive_*`
+/// function accepts, returning it if it is not
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub(crate) fn check_batch(size: usize, batch: usize) -> Result<(), usize> {
+    match batch > 0 && batch <= size {
+        true => Ok(()),
+        false => Err(batch),
+    }
+}
+
+/// Draws of the modern Fisher-Yates shuffle, picking the slot swapped into slot `i` from `0..=i`
+#[cfg(any(feature = "fastrand", all(feature = "chacha", entropy)))]
+pub(crate) fn modern_draw(rng: &mut impl Draw) -> impl FnMut(usize) -> usize + '_ {
+    move |i| rng.index_to(i)
+}
+
+/// Draws of the seeded `crypto_predictive_*` functions, picking the slot swapped into slot `i`
+/// from `0..i`
+#[cfg(feature = "chacha")]
+pub(crate) fn crypto_draw(rng: &mut CryptoRng) -> impl FnMut(usize) -> usize + '_ {
+    move |i| if i == 0 { 0 } else { rng.index_below(i) }
+}
+
+/// Draws of the `batch_predictive_*` functions, reusing `size / batch` shuffled values
+#[cfg(feature = "fastrand")]
+pub(crate) fn batch_draw(
+    size: usize,
+    batch: usize,
+    rand: &mut fastrand::Rng,
+) -> impl FnMut(usize) -> usize {
     let range = size / batch;
-    let mut randoms: Vec<usize> = vec![];
-    for i in (0..size).rev() {
-        let x: usize = if i > size - range {
-            let x = rng.gen_range(0..=i);
-            randoms.push(x);
-            x
-        } else if i == 0 {
+    let mut randoms: Vec<usize> = (0..range).collect();
+    rand.shuffle_slice(randoms.as_mut_slice());
+    move |i| {
+        if i == 0 {
             0
         } else {
             randoms[i % randoms.len()] % i
-        };
-
-        if let Some(item) = vec[x] {
-            new_map.insert(item, i);
-            peers -= 1;
-            vec[x] = None;
-            if peers == 0 {
-                break;
-            }
         }
+    }
+}
 
-        if let Some(item) = vec[i] {
-            vec[x] = Some(item);
-            vec[i] = None
+/// Draws of the `crypto_batch_predictive_*` functions, drawing the last `size / batch` slots and
+/// reusing those draws for the rest
+///
+/// When `size / batch` is 1 or less, there is no draw to reuse yet, so slots are drawn until the
+/// first one is recorded.
+#[cfg(feature = "chacha")]
+pub(crate) fn crypto_batch_draw(
+    size: usize,
+    batch: usize,
+    rng: &mut CryptoRng,
+) -> impl FnMut(usize) -> usize + '_ {
+    let range = size / batch;
+    let mut randoms: Vec<usize> = vec![];
+    move |i| {
+        if i == 0 {
+            0
+        } else if i > size - range || randoms.is_empty() {
+            let x = rng.index_to(i);
+            randoms.push(x);
+            x
+        } else {
+            randoms[i % randoms.len()] % i
         }
     }
-    new_map
 }
 
-pub fn hash(seed: &Vec<u8>) -> Vec<u8> {
+/// Hex digest of `seed`, with SHA-256 if the `sha2` feature is enabled and BLAKE3 otherwise
+#[cfg(feature = "sha2")]
+pub fn hash(seed: &[u8]) -> Vec<u8> {
+    use sha2::{Digest, Sha256};
+
     let mut hasher = Sha256::new();
     hasher.update(seed);
-    let result = hasher.finalize();
-    format!("{:x}", result).as_bytes().to_vec()
+    let mut result = hasher.finalize();
+    let mut hex = format!("{:x}", result);
+    let hash = hex.as_bytes().to_vec();
+    rng::wipe(result.as_mut_slice());
+    rng::wipe(&mut hex);
+    hash
+}
+
+/// Hex digest of `seed`, with SHA-256 if the `sha2` feature is enabled and BLAKE3 otherwise
+#[cfg(not(feature = "sha2"))]
+pub fn hash(seed: &[u8]) -> Vec<u8> {
+    let mut result = blake3::hash(seed);
+    let mut hex = format!("{}", result.to_hex());
+    let hash = hex.as_bytes().to_vec();
+    rng::wipe(&mut result);
+    rng::wipe(&mut hex);
+    hash
+}
+
+/// Raw 256-bit digest of `seed`, with the same hasher as [`hash`]
+#[cfg(all(feature = "chacha", feature = "sha2"))]
+pub(crate) fn digest(seed: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(seed).into()
+}
+
+/// Raw 256-bit digest of `seed`, with the same hasher as [`hash`]
+#[cfg(all(feature = "chacha", not(feature = "sha2")))]
+pub(crate) fn digest(seed: &[u8]) -> [u8; 32] {
+    blake3::hash(seed).into()
 }
 
-pub fn byte_array(v: &Vec<u8>) -> [u8; 8] {
+pub fn byte_array(v: &[u8]) -> [u8; 8] {
     let mut hash = hash(v);
-    hash.truncate(8);
-    hash.try_into().unwrap_or_else(|v: Vec<u8>| {
-        panic!("Expected a Vec of length {} but it was {}", 8, v.len())
-    })
+    let mut array = [0; 8];
+    array.copy_from_slice(&hash[..8]);
+    rng::wipe(&mut hash);
+    array
 }
@@ -0,0 +1,181 @@
+//! # multiparty
+//! Multi-party seed combination for draws between parties that do not trust each other.
+//!
+//! Every party first publishes a [`Commitment`] to its own secret [`Reveal`]. Once all parties
+//! have committed, each party reveals, and the final [`Seed`] is the SHA-256 [`crate::hash`] of
+//! every reveal in canonical order (sorted by party identifier). No party can bias the seed
+//! without breaking its own commitment, as long as one party keeps its seed secret until all
+//! commitments are in.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! use predictive_shuffle::gen_crypto_predictive_shuffle_from_seed_ref;
+//! use predictive_shuffle::multiparty::SeedCeremony;
+//! use predictive_shuffle::transcript::Reveal;
+//!
+//! let alice = Reveal::new(b"alice secret".to_vec());
+//! let bob = Reveal::new(b"bob secret".to_vec());
+//!
+//! let mut ceremony = SeedCeremony::new();
+//! ceremony.commit("alice", alice.commitment()).unwrap();
+//! ceremony.commit("bob", bob.commitment()).unwrap();
+//!
+//! ceremony.reveal("bob", bob).unwrap();
+//! assert_eq!(ceremony.missing(), vec!["alice"]);
+//! ceremony.reveal("alice", alice).unwrap();
+//!
+//! let seed = ceremony.seed().unwrap();
+//! let new_positions = gen_crypto_predictive_shuffle_from_seed_ref(100, [1, 5], &seed);
+//! ```
+
+use crate::transcript::{Commitment, Reveal};
+use crate::{hash, rng, Seed};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Errors detected while collecting commitments and reveals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultiPartyError {
+    /// The party has already committed
+    DuplicateCommitment(String),
+    /// A commitment arrived after the first reveal
+    CommitmentsClosed(String),
+    /// A reveal arrived from a party that never committed
+    UnknownParty(String),
+    /// The party has already revealed
+    DuplicateReveal(String),
+    /// The reveal does not open the party's commitment
+    RevealMismatch(String),
+    /// No party has committed
+    NoParties,
+    /// These parties committed but have not revealed
+    MissingReveals(Vec<String>),
+}
+
+impl fmt::Display for MultiPartyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultiPartyError::DuplicateCommitment(party) => {
+                write!(f, "party {} has already committed", party)
+            }
+            MultiPartyError::CommitmentsClosed(party) => {
+                write!(f, "party {} committed after reveals started", party)
+            }
+            MultiPartyError::UnknownParty(party) => {
+                write!(f, "party {} revealed without committing", party)
+            }
+            MultiPartyError::DuplicateReveal(party) => {
+                write!(f, "party {} has already revealed", party)
+            }
+            MultiPartyError::RevealMismatch(party) => {
+                write!(
+                    f,
+                    "reveal from party {} does not match its commitment",
+                    party
+                )
+            }
+            MultiPartyError::NoParties => write!(f, "no party has committed"),
+            MultiPartyError::MissingReveals(parties) => {
+                write!(f, "missing reveals from {}", parties.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for MultiPartyError {}
+
+/// Commitments and reveals collected from every party to a draw
+#[derive(Debug, Clone, Default)]
+pub struct SeedCeremony {
+    commitments: BTreeMap<String, Commitment>,
+    reveals: BTreeMap<String, Reveal>,
+}
+
+impl SeedCeremony {
+    /// Start a ceremony with no parties
+    pub fn new() -> Self {
+        SeedCeremony::default()
+    }
+
+    /// Record a party's commitment, which must happen before any party reveals
+    pub fn commit(
+        &mut self,
+        party: impl Into<String>,
+        commitment: Commitment,
+    ) -> Result<(), MultiPartyError> {
+        let party = party.into();
+        if !self.reveals.is_empty() {
+            return Err(MultiPartyError::CommitmentsClosed(party));
+        }
+        if self.commitments.contains_key(&party) {
+            return Err(MultiPartyError::DuplicateCommitment(party));
+        }
+        self.commitments.insert(party, commitment);
+        Ok(())
+    }
+
+    /// Record a party's reveal after checking it against the party's commitment
+    pub fn reveal(
+        &mut self,
+        party: impl Into<String>,
+        reveal: Reveal,
+    ) -> Result<(), MultiPartyError> {
+        let party = party.into();
+        let commitment = match self.commitments.get(&party) {
+            Some(commitment) => commitment,
+            None => return Err(MultiPartyError::UnknownParty(party)),
+        };
+        if self.reveals.contains_key(&party) {
+            return Err(MultiPartyError::DuplicateReveal(party));
+        }
+        if !commitment.verify(&reveal) {
+            return Err(MultiPartyError::RevealMismatch(party));
+        }
+        self.reveals.insert(party, reveal);
+        Ok(())
+    }
+
+    /// Parties, in canonical order
+    pub fn parties(&self) -> Vec<&str> {
+        self.commitments
+            .keys()
+            .map(|party| party.as_str())
+            .collect()
+    }
+
+    /// Parties that committed but have not revealed, in canonical order
+    pub fn missing(&self) -> Vec<&str> {
+        self.commitments
+            .keys()
+            .filter(|party| !self.reveals.contains_key(*party))
+            .map(|party| party.as_str())
+            .collect()
+    }
+
+    /// Combine every reveal into the final seed, once all parties have revealed
+    pub fn seed(&self) -> Result<Seed, MultiPartyError> {
+        if self.commitments.is_empty() {
+            return Err(MultiPartyError::NoParties);
+        }
+        let missing = self.missing();
+        if !missing.is_empty() {
+            return Err(MultiPartyError::MissingReveals(
+                missing.into_iter().map(String::from).collect(),
+            ));
+        }
+
+        // length-prefix every field so no two sets of reveals share a preimage
+        let mut preimage = (self.reveals.len() as u64).to_be_bytes().to_vec();
+        for (party, reveal) in &self.reveals {
+            for field in [party.as_bytes(), &reveal.seed, &reveal.nonce] {
+                preimage.extend_from_slice(&(field.len() as u64).to_be_bytes());
+                preimage.extend_from_slice(field);
+            }
+        }
+        let seed = Seed::new(hash(&preimage));
+        rng::wipe(&mut preimage);
+        Ok(seed)
+    }
+}
@@ -0,0 +1,508 @@
+//! # ordered
+//! Predictive shuffles without a `HashMap` result.
+//!
+//! Every predictive function in the crate root returns a fresh `HashMap<usize, usize>`, which
+//! costs hashing on every insert and iterates in random order. The `*_into` functions in this
+//! module instead write the shuffled position of `positions[k]` into `out[k]`, so the result is
+//! aligned with the input and the only allocation is the scratch space of the shuffle itself. The
+//! `*_ordered` functions return a `BTreeMap`, which iterates in position order.
+//!
+//! Both give the same shuffled positions as the function of the same name in the crate root.
+//! Positions must be below `size` and distinct; a repeated position maps to `size` in all but its
+//! last occurrence.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! # #[cfg(feature = "fastrand")]
+//! # {
+//! use predictive_shuffle::ordered::{
+//!     gen_predictive_shuffle_from_seed_into, gen_predictive_shuffle_from_seed_ordered,
+//! };
+//!
+//! let size = 100;
+//! let seed = b"seed phrase".to_vec();
+//! let positions = vec![1,5];
+//! let mut new_positions = vec![0; positions.len()];
+//! gen_predictive_shuffle_from_seed_into(size, &positions, &seed, &mut new_positions);
+//! assert_eq!(new_positions, vec![79, 86]);
+//!
+//! let map = gen_predictive_shuffle_from_seed_ordered(size, &positions, &seed);
+//! assert_eq!(map.get(&1), Some(&79));
+//! # }
+//! ```
+
+#[cfg(any(feature = "fastrand", all(feature = "chacha", entropy)))]
+use crate::modern_draw;
+#[cfg(feature = "fastrand")]
+use crate::{batch_draw, byte_array};
+#[cfg(feature = "chacha")]
+use crate::{crypto_batch_draw, crypto_draw, CryptoRng};
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// [`crate::gen_predictive_shuffle`], aligned with `positions`
+///
+/// Writes the shuffled position of `positions[k]` into `out[k]`. Panics if `out` and `positions`
+/// differ in length.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_predictive_shuffle_into;
+///
+/// let size = 100;
+/// let positions = vec![1,5];
+/// let mut new_positions = vec![0; positions.len()];
+/// gen_predictive_shuffle_into(size, &positions, &mut new_positions);
+/// ```
+#[cfg(all(feature = "fastrand", entropy))]
+pub fn gen_predictive_shuffle_into(size: usize, positions: &[usize], out: &mut [usize]) {
+    let mut rand: fastrand::Rng = fastrand::Rng::new();
+    predict_into(size, positions, out, modern_draw(&mut rand));
+}
+
+/// [`crate::gen_predictive_shuffle`], ordered by position
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_predictive_shuffle_ordered;
+///
+/// let size = 100;
+/// let positions = vec![1,5];
+/// let new_positions = gen_predictive_shuffle_ordered(size, &positions);
+/// ```
+#[cfg(all(feature = "fastrand", entropy))]
+pub fn gen_predictive_shuffle_ordered(size: usize, positions: &[usize]) -> BTreeMap<usize, usize> {
+    let mut out = vec![0; positions.len()];
+    gen_predictive_shuffle_into(size, positions, &mut out);
+    positions.iter().copied().zip(out).collect()
+}
+
+/// [`crate::gen_predictive_shuffle_from_seed_ref`], aligned with `positions`
+///
+/// Writes the shuffled position of `positions[k]` into `out[k]`. Panics if `out` and `positions`
+/// differ in length.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_predictive_shuffle_from_seed_into;
+///
+/// let size = 100;
+/// let seed = b"seed phrase".to_vec();
+/// let positions = vec![1,5];
+/// let mut new_positions = vec![0; positions.len()];
+/// gen_predictive_shuffle_from_seed_into(size, &positions, seed, &mut new_positions);
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn gen_predictive_shuffle_from_seed_into(
+    size: usize,
+    positions: &[usize],
+    seed: impl AsRef<[u8]>,
+    out: &mut [usize],
+) {
+    let seed = byte_array(seed.as_ref());
+    let seed_int = u64::from_be_bytes(seed);
+    let mut rand: fastrand::Rng = fastrand::Rng::with_seed(seed_int);
+    predict_into(size, positions, out, modern_draw(&mut rand));
+}
+
+/// [`crate::gen_predictive_shuffle_from_seed_ref`], ordered by position
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_predictive_shuffle_from_seed_ordered;
+///
+/// let size = 100;
+/// let seed = b"seed phrase".to_vec();
+/// let positions = vec![1,5];
+/// let new_positions = gen_predictive_shuffle_from_seed_ordered(size, &positions, seed);
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn gen_predictive_shuffle_from_seed_ordered(
+    size: usize,
+    positions: &[usize],
+    seed: impl AsRef<[u8]>,
+) -> BTreeMap<usize, usize> {
+    let mut out = vec![0; positions.len()];
+    gen_predictive_shuffle_from_seed_into(size, positions, seed, &mut out);
+    positions.iter().copied().zip(out).collect()
+}
+
+/// [`crate::gen_crypto_predictive_shuffle`], aligned with `positions`
+///
+/// Writes the shuffled position of `positions[k]` into `out[k]`. Panics if `out` and `positions`
+/// differ in length.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_crypto_predictive_shuffle_into;
+///
+/// let size = 100;
+/// let positions = vec![1,5];
+/// let mut new_positions = vec![0; positions.len()];
+/// gen_crypto_predictive_shuffle_into(size, &positions, &mut new_positions);
+/// ```
+#[cfg(all(feature = "chacha", entropy))]
+pub fn gen_crypto_predictive_shuffle_into(size: usize, positions: &[usize], out: &mut [usize]) {
+    let mut rng = CryptoRng::from_entropy();
+    predict_into(size, positions, out, modern_draw(&mut rng));
+}
+
+/// [`crate::gen_crypto_predictive_shuffle`], ordered by position
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_crypto_predictive_shuffle_ordered;
+///
+/// let size = 100;
+/// let positions = vec![1,5];
+/// let new_positions = gen_crypto_predictive_shuffle_ordered(size, &positions);
+/// ```
+#[cfg(all(feature = "chacha", entropy))]
+pub fn gen_crypto_predictive_shuffle_ordered(
+    size: usize,
+    positions: &[usize],
+) -> BTreeMap<usize, usize> {
+    let mut out = vec![0; positions.len()];
+    gen_crypto_predictive_shuffle_into(size, positions, &mut out);
+    positions.iter().copied().zip(out).collect()
+}
+
+/// [`crate::gen_crypto_predictive_shuffle_from_seed_ref`], aligned with `positions`
+///
+/// Writes the shuffled position of `positions[k]` into `out[k]`. Panics if `out` and `positions`
+/// differ in length.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_crypto_predictive_shuffle_from_seed_into;
+///
+/// let size = 100;
+/// let seed = b"seed phrase".to_vec();
+/// let positions = vec![1,5];
+/// let mut new_positions = vec![0; positions.len()];
+/// gen_crypto_predictive_shuffle_from_seed_into(size, &positions, seed, &mut new_positions);
+/// ```
+#[cfg(feature = "chacha")]
+pub fn gen_crypto_predictive_shuffle_from_seed_into(
+    size: usize,
+    positions: &[usize],
+    seed: impl AsRef<[u8]>,
+    out: &mut [usize],
+) {
+    let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+    predict_into(size, positions, out, crypto_draw(&mut rng));
+}
+
+/// [`crate::gen_crypto_predictive_shuffle_from_seed_ref`], ordered by position
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_crypto_predictive_shuffle_from_seed_ordered;
+///
+/// let size = 100;
+/// let seed = b"seed phrase".to_vec();
+/// let positions = vec![1,5];
+/// let new_positions = gen_crypto_predictive_shuffle_from_seed_ordered(size, &positions, seed);
+/// ```
+#[cfg(feature = "chacha")]
+pub fn gen_crypto_predictive_shuffle_from_seed_ordered(
+    size: usize,
+    positions: &[usize],
+    seed: impl AsRef<[u8]>,
+) -> BTreeMap<usize, usize> {
+    let mut out = vec![0; positions.len()];
+    gen_crypto_predictive_shuffle_from_seed_into(size, positions, seed, &mut out);
+    positions.iter().copied().zip(out).collect()
+}
+
+/// [`crate::gen_batch_predictive_shuffle`], aligned with `positions`
+///
+/// Writes the shuffled position of `positions[k]` into `out[k]`. Panics if `out` and `positions`
+/// differ in length.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_batch_predictive_shuffle_into;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let positions = vec![1,5];
+/// let mut new_positions = vec![0; positions.len()];
+/// gen_batch_predictive_shuffle_into(size, batch, &positions, &mut new_positions);
+/// ```
+#[cfg(all(feature = "fastrand", entropy))]
+pub fn gen_batch_predictive_shuffle_into(
+    size: usize,
+    batch: usize,
+    positions: &[usize],
+    out: &mut [usize],
+) {
+    let mut rand: fastrand::Rng = fastrand::Rng::new();
+    predict_into(size, positions, out, batch_draw(size, batch, &mut rand));
+}
+
+/// [`crate::gen_batch_predictive_shuffle`], ordered by position
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_batch_predictive_shuffle_ordered;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let positions = vec![1,5];
+/// let new_positions = gen_batch_predictive_shuffle_ordered(size, batch, &positions);
+/// ```
+#[cfg(all(feature = "fastrand", entropy))]
+pub fn gen_batch_predictive_shuffle_ordered(
+    size: usize,
+    batch: usize,
+    positions: &[usize],
+) -> BTreeMap<usize, usize> {
+    let mut out = vec![0; positions.len()];
+    gen_batch_predictive_shuffle_into(size, batch, positions, &mut out);
+    positions.iter().copied().zip(out).collect()
+}
+
+/// [`crate::gen_batch_predictive_shuffle_from_seed_ref`], aligned with `positions`
+///
+/// Writes the shuffled position of `positions[k]` into `out[k]`. Panics if `out` and `positions`
+/// differ in length.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_batch_predictive_shuffle_from_seed_into;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let seed = b"seed phrase".to_vec();
+/// let positions = vec![1,5];
+/// let mut new_positions = vec![0; positions.len()];
+/// gen_batch_predictive_shuffle_from_seed_into(size, batch, &positions, seed, &mut new_positions);
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn gen_batch_predictive_shuffle_from_seed_into(
+    size: usize,
+    batch: usize,
+    positions: &[usize],
+    seed: impl AsRef<[u8]>,
+    out: &mut [usize],
+) {
+    let seed = byte_array(seed.as_ref());
+    let seed_int = u64::from_be_bytes(seed);
+    let mut rand: fastrand::Rng = fastrand::Rng::with_seed(seed_int);
+    predict_into(size, positions, out, batch_draw(size, batch, &mut rand));
+}
+
+/// [`crate::gen_batch_predictive_shuffle_from_seed_ref`], ordered by position
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_batch_predictive_shuffle_from_seed_ordered;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let seed = b"seed phrase".to_vec();
+/// let positions = vec![1,5];
+/// let new_positions = gen_batch_predictive_shuffle_from_seed_ordered(size, batch, &positions, seed);
+/// ```
+#[cfg(feature = "fastrand")]
+pub fn gen_batch_predictive_shuffle_from_seed_ordered(
+    size: usize,
+    batch: usize,
+    positions: &[usize],
+    seed: impl AsRef<[u8]>,
+) -> BTreeMap<usize, usize> {
+    let mut out = vec![0; positions.len()];
+    gen_batch_predictive_shuffle_from_seed_into(size, batch, positions, seed, &mut out);
+    positions.iter().copied().zip(out).collect()
+}
+
+/// [`crate::gen_crypto_batch_predictive_shuffle`], aligned with `positions`
+///
+/// Writes the shuffled position of `positions[k]` into `out[k]`. Panics if `out` and `positions`
+/// differ in length.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_crypto_batch_predictive_shuffle_into;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let positions = vec![1,5];
+/// let mut new_positions = vec![0; positions.len()];
+/// gen_crypto_batch_predictive_shuffle_into(size, batch, &positions, &mut new_positions);
+/// ```
+#[cfg(all(feature = "chacha", entropy))]
+pub fn gen_crypto_batch_predictive_shuffle_into(
+    size: usize,
+    batch: usize,
+    positions: &[usize],
+    out: &mut [usize],
+) {
+    let mut rng = CryptoRng::from_entropy();
+    predict_into(
+        size,
+        positions,
+        out,
+        crypto_batch_draw(size, batch, &mut rng),
+    );
+}
+
+/// [`crate::gen_crypto_batch_predictive_shuffle`], ordered by position
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_crypto_batch_predictive_shuffle_ordered;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let positions = vec![1,5];
+/// let new_positions = gen_crypto_batch_predictive_shuffle_ordered(size, batch, &positions);
+/// ```
+#[cfg(all(feature = "chacha", entropy))]
+pub fn gen_crypto_batch_predictive_shuffle_ordered(
+    size: usize,
+    batch: usize,
+    positions: &[usize],
+) -> BTreeMap<usize, usize> {
+    let mut out = vec![0; positions.len()];
+    gen_crypto_batch_predictive_shuffle_into(size, batch, positions, &mut out);
+    positions.iter().copied().zip(out).collect()
+}
+
+/// [`crate::gen_crypto_batch_predictive_shuffle_from_seed_ref`], aligned with `positions`
+///
+/// Writes the shuffled position of `positions[k]` into `out[k]`. Panics if `out` and `positions`
+/// differ in length.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_crypto_batch_predictive_shuffle_from_seed_into;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let seed = b"seed phrase".to_vec();
+/// let positions = vec![1,5];
+/// let mut new_positions = vec![0; positions.len()];
+/// gen_crypto_batch_predictive_shuffle_from_seed_into(size, batch, &positions, seed, &mut new_positions);
+/// ```
+#[cfg(feature = "chacha")]
+pub fn gen_crypto_batch_predictive_shuffle_from_seed_into(
+    size: usize,
+    batch: usize,
+    positions: &[usize],
+    seed: impl AsRef<[u8]>,
+    out: &mut [usize],
+) {
+    let mut rng = CryptoRng::from_seed_ref(seed.as_ref());
+    predict_into(
+        size,
+        positions,
+        out,
+        crypto_batch_draw(size, batch, &mut rng),
+    );
+}
+
+/// [`crate::gen_crypto_batch_predictive_shuffle_from_seed_ref`], ordered by position
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::ordered::gen_crypto_batch_predictive_shuffle_from_seed_ordered;
+///
+/// let size = 100;
+/// let batch = 4;
+/// let seed = b"seed phrase".to_vec();
+/// let positions = vec![1,5];
+/// let new_positions = gen_crypto_batch_predictive_shuffle_from_seed_ordered(size, batch, &positions, seed);
+/// ```
+#[cfg(feature = "chacha")]
+pub fn gen_crypto_batch_predictive_shuffle_from_seed_ordered(
+    size: usize,
+    batch: usize,
+    positions: &[usize],
+    seed: impl AsRef<[u8]>,
+) -> BTreeMap<usize, usize> {
+    let mut out = vec![0; positions.len()];
+    gen_crypto_batch_predictive_shuffle_from_seed_into(size, batch, positions, seed, &mut out);
+    positions.iter().copied().zip(out).collect()
+}
+
+/// Track the positions through a modern Fisher-Yates shuffle, where `draw(i)` picks the slot
+/// swapped into slot `i`
+///
+/// Every predictive function of the crate runs this loop, whether it returns an aligned slice, a
+/// `BTreeMap` or a `HashMap`.
+pub(crate) fn predict_into<F: FnMut(usize) -> usize>(
+    size: usize,
+    positions: &[usize],
+    out: &mut [usize],
+    mut draw: F,
+) {
+    assert_eq!(
+        positions.len(),
+        out.len(),
+        "out must be aligned with positions"
+    );
+    out.fill(size);
+
+    // mutable structures, holding the index into positions of every tracked item
+    let mut vec: Vec<Option<usize>> = vec![None; size];
+    let mut peers = positions.len();
+
+    // fill vec
+    for (index, &position) in positions.iter().enumerate() {
+        vec[position] = Some(index)
+    }
+    for i in (0..size).rev() {
+        let x: usize = draw(i);
+
+        if let Some(index) = vec[x] {
+            out[index] = i;
+            peers -= 1;
+            vec[x] = None;
+            if peers == 0 {
+                break;
+            }
+        }
+
+        if let Some(index) = vec[i] {
+            vec[x] = Some(index);
+            vec[i] = None
+        }
+    }
+}
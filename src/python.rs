@@ -0,0 +1,323 @@
+//! # python
+//! Python bindings, built into a `predictive_shuffle` extension module with maturin:
+//!
+//! ```text
+//! maturin develop --release
+//! ```
+//!
+//! The module exposes the `gen_*` functions under their Rust names. Seeds are `bytes`, and
+//! positions can be any sequence of ints, including a NumPy array. Permutations are returned as
+//! a [`PyPermutation`], which converts to a NumPy array with `numpy.asarray` or `to_numpy()`, and
+//! predictions as a `dict` from position to shuffled position, as the Rust functions return a
+//! `HashMap`. Results are identical to the Rust functions for the same seed.
+//!
+//! ```python
+//! import numpy as np
+//! import predictive_shuffle as ps
+//!
+//! permutation = ps.gen_crypto_modern_shuffle_from_seed(100, b"seed phrase")
+//! shuffled = np.asarray(permutation)
+//! new_positions = ps.gen_crypto_predictive_shuffle_from_seed(100, np.array([1, 5]), b"seed phrase")
+//! assert shuffled[new_positions[1]] == 1
+//! ```
+
+use crate::record::Permutation;
+use crate::{
+    gen_batch_predictive_shuffle_from_seed_ref, gen_batch_predictive_shuffle_ref,
+    gen_crypto_batch_predictive_shuffle_from_seed_ref, gen_crypto_batch_predictive_shuffle_ref,
+    gen_crypto_modern_shuffle, gen_crypto_modern_shuffle_from_seed_ref,
+    gen_crypto_predictive_shuffle_from_seed_ref, gen_crypto_predictive_shuffle_ref,
+    gen_fastrand_shuffle, gen_fastrand_shuffle_from_seed_ref, gen_modern_shuffle,
+    gen_modern_shuffle_from_seed_ref, gen_predictive_shuffle_from_seed_ref,
+    gen_predictive_shuffle_ref,
+};
+use numpy::PyArray1;
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+use std::collections::HashMap;
+
+/// A permutation of `0..len`, as returned by the `gen_*` shuffles
+#[pyclass(name = "Permutation", module = "predictive_shuffle", frozen, eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PyPermutation(pub Permutation);
+
+#[pymethods]
+impl PyPermutation {
+    #[new]
+    fn new(items: Vec<usize>) -> PyResult<Self> {
+        let mut seen = vec![false; items.len()];
+        for &item in &items {
+            if item >= items.len() || seen[item] {
+                return Err(PyValueError::new_err(format!(
+                    "{} is out of range or repeated",
+                    item
+                )));
+            }
+            seen[item] = true;
+        }
+        Ok(PyPermutation(items.into()))
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<usize> {
+        let slice = self.0.as_slice();
+        let index = if index < 0 {
+            index + slice.len() as isize
+        } else {
+            index
+        };
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| slice.get(index).copied())
+            .ok_or_else(|| PyIndexError::new_err("permutation index out of range"))
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(PyList::new(py, self.0.as_slice())?.try_iter()?.into_any())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Permutation({:?})", self.0.as_slice())
+    }
+
+    /// NumPy array protocol, so `numpy.asarray` accepts a permutation
+    #[pyo3(signature = (dtype=None, copy=None))]
+    fn __array__<'py>(
+        &self,
+        py: Python<'py>,
+        dtype: Option<Bound<'py, PyAny>>,
+        copy: Option<bool>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        if copy == Some(false) {
+            return Err(PyValueError::new_err(
+                "a permutation is always copied into a new array",
+            ));
+        }
+        let array = self.to_numpy(py).into_any();
+        match dtype {
+            Some(dtype) => array.call_method1("astype", (dtype,)),
+            None => Ok(array),
+        }
+    }
+
+    /// Copy into a new NumPy array
+    fn to_numpy<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<usize>> {
+        PyArray1::from_slice(py, self.0.as_slice())
+    }
+
+    /// Copy into a new list
+    fn to_list(&self) -> Vec<usize> {
+        self.0.as_slice().to_vec()
+    }
+
+    /// Reorder `items` by the permutation, as the matching `Shuffle` method would shuffle them
+    fn apply<'py>(&self, items: Vec<Bound<'py, PyAny>>) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        if items.len() != self.0.len() {
+            return Err(PyValueError::new_err(format!(
+                "expected {} items, got {}",
+                self.0.len(),
+                items.len()
+            )));
+        }
+        Ok(self
+            .0
+            .as_slice()
+            .iter()
+            .map(|&i| items[i].clone())
+            .collect())
+    }
+}
+
+impl From<Vec<usize>> for PyPermutation {
+    fn from(vec: Vec<usize>) -> Self {
+        PyPermutation(vec.into())
+    }
+}
+
+fn check_positions(size: usize, positions: &[usize]) -> PyResult<()> {
+    crate::check_positions(size, positions).map_err(|position| {
+        PyValueError::new_err(format!(
+            "position {} is out of range or repeated for the size {}",
+            position, size
+        ))
+    })
+}
+
+fn check_batch(size: usize, batch: usize) -> PyResult<()> {
+    crate::check_batch(size, batch).map_err(|batch| {
+        PyValueError::new_err(format!(
+            "batch {} must be between 1 and the size {}",
+            batch, size
+        ))
+    })
+}
+
+/// [`crate::gen_fastrand_shuffle`]
+#[pyfunction(name = "gen_fastrand_shuffle")]
+fn py_gen_fastrand_shuffle(size: usize) -> PyPermutation {
+    gen_fastrand_shuffle(size).into()
+}
+
+/// [`crate::gen_fastrand_shuffle_from_seed_ref`]
+#[pyfunction(name = "gen_fastrand_shuffle_from_seed")]
+fn py_gen_fastrand_shuffle_from_seed(size: usize, seed: &[u8]) -> PyPermutation {
+    gen_fastrand_shuffle_from_seed_ref(size, seed).into()
+}
+
+/// [`crate::gen_modern_shuffle`]
+#[pyfunction(name = "gen_modern_shuffle")]
+fn py_gen_modern_shuffle(size: usize) -> PyPermutation {
+    gen_modern_shuffle(size).into()
+}
+
+/// [`crate::gen_modern_shuffle_from_seed_ref`]
+#[pyfunction(name = "gen_modern_shuffle_from_seed")]
+fn py_gen_modern_shuffle_from_seed(size: usize, seed: &[u8]) -> PyPermutation {
+    gen_modern_shuffle_from_seed_ref(size, seed).into()
+}
+
+/// [`crate::gen_crypto_modern_shuffle`]
+#[pyfunction(name = "gen_crypto_modern_shuffle")]
+fn py_gen_crypto_modern_shuffle(size: usize) -> PyPermutation {
+    gen_crypto_modern_shuffle(size).into()
+}
+
+/// [`crate::gen_crypto_modern_shuffle_from_seed_ref`]
+#[pyfunction(name = "gen_crypto_modern_shuffle_from_seed")]
+fn py_gen_crypto_modern_shuffle_from_seed(size: usize, seed: &[u8]) -> PyPermutation {
+    gen_crypto_modern_shuffle_from_seed_ref(size, seed).into()
+}
+
+/// [`crate::gen_predictive_shuffle_ref`]
+#[pyfunction(name = "gen_predictive_shuffle")]
+fn py_gen_predictive_shuffle(
+    size: usize,
+    positions: Vec<usize>,
+) -> PyResult<HashMap<usize, usize>> {
+    check_positions(size, &positions)?;
+    Ok(gen_predictive_shuffle_ref(size, positions))
+}
+
+/// [`crate::gen_predictive_shuffle_from_seed_ref`]
+#[pyfunction(name = "gen_predictive_shuffle_from_seed")]
+fn py_gen_predictive_shuffle_from_seed(
+    size: usize,
+    positions: Vec<usize>,
+    seed: &[u8],
+) -> PyResult<HashMap<usize, usize>> {
+    check_positions(size, &positions)?;
+    Ok(gen_predictive_shuffle_from_seed_ref(size, positions, seed))
+}
+
+/// [`crate::gen_crypto_predictive_shuffle_ref`]
+#[pyfunction(name = "gen_crypto_predictive_shuffle")]
+fn py_gen_crypto_predictive_shuffle(
+    size: usize,
+    positions: Vec<usize>,
+) -> PyResult<HashMap<usize, usize>> {
+    check_positions(size, &positions)?;
+    Ok(gen_crypto_predictive_shuffle_ref(size, positions))
+}
+
+/// [`crate::gen_crypto_predictive_shuffle_from_seed_ref`]
+#[pyfunction(name = "gen_crypto_predictive_shuffle_from_seed")]
+fn py_gen_crypto_predictive_shuffle_from_seed(
+    size: usize,
+    positions: Vec<usize>,
+    seed: &[u8],
+) -> PyResult<HashMap<usize, usize>> {
+    check_positions(size, &positions)?;
+    Ok(gen_crypto_predictive_shuffle_from_seed_ref(
+        size, positions, seed,
+    ))
+}
+
+/// [`crate::gen_batch_predictive_shuffle_ref`]
+#[pyfunction(name = "gen_batch_predictive_shuffle")]
+fn py_gen_batch_predictive_shuffle(
+    size: usize,
+    batch: usize,
+    positions: Vec<usize>,
+) -> PyResult<HashMap<usize, usize>> {
+    check_batch(size, batch)?;
+    check_positions(size, &positions)?;
+    Ok(gen_batch_predictive_shuffle_ref(size, batch, positions))
+}
+
+/// [`crate::gen_batch_predictive_shuffle_from_seed_ref`]
+#[pyfunction(name = "gen_batch_predictive_shuffle_from_seed")]
+fn py_gen_batch_predictive_shuffle_from_seed(
+    size: usize,
+    batch: usize,
+    positions: Vec<usize>,
+    seed: &[u8],
+) -> PyResult<HashMap<usize, usize>> {
+    check_batch(size, batch)?;
+    check_positions(size, &positions)?;
+    Ok(gen_batch_predictive_shuffle_from_seed_ref(
+        size, batch, positions, seed,
+    ))
+}
+
+/// [`crate::gen_crypto_batch_predictive_shuffle_ref`]
+#[pyfunction(name = "gen_crypto_batch_predictive_shuffle")]
+fn py_gen_crypto_batch_predictive_shuffle(
+    size: usize,
+    batch: usize,
+    positions: Vec<usize>,
+) -> PyResult<HashMap<usize, usize>> {
+    check_batch(size, batch)?;
+    check_positions(size, &positions)?;
+    Ok(gen_crypto_batch_predictive_shuffle_ref(
+        size, batch, positions,
+    ))
+}
+
+/// [`crate::gen_crypto_batch_predictive_shuffle_from_seed_ref`]
+#[pyfunction(name = "gen_crypto_batch_predictive_shuffle_from_seed")]
+fn py_gen_crypto_batch_predictive_shuffle_from_seed(
+    size: usize,
+    batch: usize,
+    positions: Vec<usize>,
+    seed: &[u8],
+) -> PyResult<HashMap<usize, usize>> {
+    check_batch(size, batch)?;
+    check_positions(size, &positions)?;
+    Ok(gen_crypto_batch_predictive_shuffle_from_seed_ref(
+        size, batch, positions, seed,
+    ))
+}
+
+/// The `predictive_shuffle` Python module
+#[pymodule(name = "predictive_shuffle")]
+pub fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyPermutation>()?;
+    m.add_function(wrap_pyfunction!(py_gen_fastrand_shuffle, m)?)?;
+    m.add_function(wrap_pyfunction!(py_gen_fastrand_shuffle_from_seed, m)?)?;
+    m.add_function(wrap_pyfunction!(py_gen_modern_shuffle, m)?)?;
+    m.add_function(wrap_pyfunction!(py_gen_modern_shuffle_from_seed, m)?)?;
+    m.add_function(wrap_pyfunction!(py_gen_crypto_modern_shuffle, m)?)?;
+    m.add_function(wrap_pyfunction!(py_gen_crypto_modern_shuffle_from_seed, m)?)?;
+    m.add_function(wrap_pyfunction!(py_gen_predictive_shuffle, m)?)?;
+    m.add_function(wrap_pyfunction!(py_gen_predictive_shuffle_from_seed, m)?)?;
+    m.add_function(wrap_pyfunction!(py_gen_crypto_predictive_shuffle, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        py_gen_crypto_predictive_shuffle_from_seed,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(py_gen_batch_predictive_shuffle, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        py_gen_batch_predictive_shuffle_from_seed,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(py_gen_crypto_batch_predictive_shuffle, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        py_gen_crypto_batch_predictive_shuffle_from_seed,
+        m
+    )?)?;
+    Ok(())
+}
@@ -0,0 +1,657 @@
+//! # record
+//! Shuffle requests and results that can be stored and replayed.
+//!
+//! A [`ShuffleRequest`] names the [`Algorithm`], the size, the positions and a
+//! [`SeedDescriptor`] for the seed. Running it gives a [`ShuffleResult`], either a
+//! [`Permutation`] or the [`PredictedPositions`] sorted by position, so the result does not
+//! depend on `HashMap` iteration order.
+//!
+//! With the `serde` feature enabled, every type in this module implements `Serialize` and
+//! `Deserialize`. Seed bytes are encoded as lowercase hex, and the encoding of a
+//! [`ShuffleRecord`] is stable byte-for-byte across save and reload.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! use predictive_shuffle::record::{Algorithm, SeedDescriptor, ShuffleRecord, ShuffleRequest};
+//!
+//! let request = ShuffleRequest {
+//!     algorithm: Algorithm::CryptoPredictiveShuffle,
+//!     size: 100,
+//!     batch: None,
+//!     positions: vec![5, 1],
+//!     seed: SeedDescriptor::Seed(b"seed phrase".to_vec()),
+//! };
+//! let record = ShuffleRecord::new(request).unwrap();
+//! assert!(record.verify().is_ok());
+//!
+//! let positions = record.result.positions().unwrap();
+//! assert_eq!(positions.iter().map(|(from, _)| from).collect::<Vec<_>>(), vec![1, 5]);
+//! ```
+
+#[cfg(entropy)]
+use crate::constant_time::{gen_constant_time_predictive_shuffle, gen_constant_time_shuffle};
+use crate::constant_time::{
+    gen_constant_time_predictive_shuffle_from_seed, gen_constant_time_shuffle_from_seed,
+};
+use crate::keyed::{
+    gen_keyed_batch_predictive_shuffle_from_seed,
+    gen_keyed_constant_time_predictive_shuffle_from_seed,
+    gen_keyed_constant_time_shuffle_from_seed, gen_keyed_crypto_batch_predictive_shuffle_from_seed,
+    gen_keyed_crypto_modern_shuffle_from_seed, gen_keyed_crypto_predictive_shuffle_from_seed,
+    gen_keyed_fastrand_shuffle_from_seed, gen_keyed_modern_shuffle_from_seed,
+    gen_keyed_predictive_shuffle_from_seed, KeyedError, Keyring, ServerKey,
+};
+use crate::{
+    check_batch, check_positions, gen_batch_predictive_shuffle_from_seed_ref,
+    gen_crypto_batch_predictive_shuffle_from_seed_ref, gen_crypto_modern_shuffle_from_seed_ref,
+    gen_crypto_predictive_shuffle_from_seed_ref, gen_fastrand_shuffle_from_seed_ref,
+    gen_modern_shuffle_from_seed_ref, gen_predictive_shuffle_from_seed_ref,
+};
+#[cfg(entropy)]
+use crate::{
+    gen_batch_predictive_shuffle_ref, gen_crypto_batch_predictive_shuffle_ref,
+    gen_crypto_modern_shuffle, gen_crypto_predictive_shuffle_ref, gen_fastrand_shuffle,
+    gen_modern_shuffle, gen_predictive_shuffle_ref,
+};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Largest size a request may have, so that an untrusted request cannot make it allocate without
+/// bound
+pub const MAX_SIZE: usize = 1 << 24;
+
+/// Errors returned when running or replaying a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordError {
+    /// The algorithm identifier is not known to this version
+    UnknownAlgorithm(String),
+    /// A batch algorithm was requested without a non-zero batch size
+    MissingBatch,
+    /// The batch size is larger than the size
+    InvalidBatch(usize),
+    /// The size is larger than [`MAX_SIZE`]
+    InvalidSize(usize),
+    /// A position is not below the size, or is repeated
+    InvalidPosition(usize),
+    /// The seed is keyed, so the request must be run with a [`Keyring`]
+    KeyRequired,
+    /// The key of a keyed seed could not be used
+    Keyed(KeyedError),
+    /// The request was seeded from entropy, so its result cannot be recomputed
+    NotReproducible,
+    /// The request is seeded from entropy, which this build has no source of
+    EntropyUnavailable,
+    /// Recomputing the request gives a different result
+    ResultMismatch,
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::UnknownAlgorithm(id) => write!(f, "unknown algorithm {}", id),
+            RecordError::MissingBatch => write!(f, "batch algorithms need a non-zero batch size"),
+            RecordError::InvalidBatch(batch) => {
+                write!(f, "batch size {} is larger than the size", batch)
+            }
+            RecordError::InvalidSize(size) => {
+                write!(f, "size {} is larger than the maximum {}", size, MAX_SIZE)
+            }
+            RecordError::InvalidPosition(position) => {
+                write!(f, "position {} is out of range or repeated", position)
+            }
+            RecordError::KeyRequired => write!(f, "keyed seeds need a keyring"),
+            RecordError::Keyed(e) => write!(f, "{}", e),
+            RecordError::NotReproducible => {
+                write!(f, "entropy seeded results cannot be recomputed")
+            }
+            RecordError::EntropyUnavailable => {
+                write!(
+                    f,
+                    "entropy seeded requests need the `js` feature on this target"
+                )
+            }
+            RecordError::ResultMismatch => {
+                write!(f, "recomputing the request gives a different result")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+impl From<KeyedError> for RecordError {
+    fn from(e: KeyedError) -> Self {
+        RecordError::Keyed(e)
+    }
+}
+
+/// Identifier of a shuffle algorithm, named after its `gen_*` function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Algorithm {
+    /// [`crate::gen_fastrand_shuffle`]
+    FastrandShuffle,
+    /// [`crate::gen_modern_shuffle`]
+    ModernShuffle,
+    /// [`crate::gen_crypto_modern_shuffle`]
+    CryptoModernShuffle,
+    /// [`crate::gen_predictive_shuffle_ref`]
+    PredictiveShuffle,
+    /// [`crate::gen_crypto_predictive_shuffle_ref`]
+    CryptoPredictiveShuffle,
+    /// [`crate::gen_batch_predictive_shuffle_ref`]
+    BatchPredictiveShuffle,
+    /// [`crate::gen_crypto_batch_predictive_shuffle_ref`]
+    CryptoBatchPredictiveShuffle,
+    /// [`crate::constant_time::gen_constant_time_shuffle`]
+    ConstantTimeShuffle,
+    /// [`crate::constant_time::gen_constant_time_predictive_shuffle`]
+    ConstantTimePredictiveShuffle,
+}
+
+impl Algorithm {
+    /// Every algorithm, in declaration order
+    pub const ALL: [Algorithm; 9] = [
+        Algorithm::FastrandShuffle,
+        Algorithm::ModernShuffle,
+        Algorithm::CryptoModernShuffle,
+        Algorithm::PredictiveShuffle,
+        Algorithm::CryptoPredictiveShuffle,
+        Algorithm::BatchPredictiveShuffle,
+        Algorithm::CryptoBatchPredictiveShuffle,
+        Algorithm::ConstantTimeShuffle,
+        Algorithm::ConstantTimePredictiveShuffle,
+    ];
+
+    /// Stable identifier, as used in serialised requests
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Algorithm::FastrandShuffle => "fastrand_shuffle",
+            Algorithm::ModernShuffle => "modern_shuffle",
+            Algorithm::CryptoModernShuffle => "crypto_modern_shuffle",
+            Algorithm::PredictiveShuffle => "predictive_shuffle",
+            Algorithm::CryptoPredictiveShuffle => "crypto_predictive_shuffle",
+            Algorithm::BatchPredictiveShuffle => "batch_predictive_shuffle",
+            Algorithm::CryptoBatchPredictiveShuffle => "crypto_batch_predictive_shuffle",
+            Algorithm::ConstantTimeShuffle => "constant_time_shuffle",
+            Algorithm::ConstantTimePredictiveShuffle => "constant_time_predictive_shuffle",
+        }
+    }
+
+    /// Whether the algorithm predicts positions rather than producing a permutation
+    pub fn is_predictive(&self) -> bool {
+        matches!(
+            self,
+            Algorithm::PredictiveShuffle
+                | Algorithm::CryptoPredictiveShuffle
+                | Algorithm::BatchPredictiveShuffle
+                | Algorithm::CryptoBatchPredictiveShuffle
+                | Algorithm::ConstantTimePredictiveShuffle
+        )
+    }
+
+    /// Whether the algorithm takes a batch size
+    pub fn is_batch(&self) -> bool {
+        matches!(
+            self,
+            Algorithm::BatchPredictiveShuffle | Algorithm::CryptoBatchPredictiveShuffle
+        )
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = RecordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Algorithm::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.as_str() == s)
+            .ok_or_else(|| RecordError::UnknownAlgorithm(s.to_string()))
+    }
+}
+
+/// Where the seed of a request comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum SeedDescriptor {
+    /// Operating system entropy, so the result cannot be recomputed
+    Entropy,
+    /// Seed bytes passed to the `*_from_seed` function
+    Seed(#[cfg_attr(feature = "serde", serde(with = "hex_bytes"))] Vec<u8>),
+    /// Seed bytes and context passed to the [`crate::keyed`] function, with the identifier of
+    /// the server key
+    Keyed {
+        /// Identifier of the [`crate::keyed::ServerKey`]
+        key_id: String,
+        /// Public seed bytes
+        #[cfg_attr(feature = "serde", serde(with = "hex_bytes"))]
+        seed: Vec<u8>,
+        /// Context bytes mixed into the keyed seed
+        #[cfg_attr(feature = "serde", serde(with = "hex_bytes"))]
+        context: Vec<u8>,
+    },
+}
+
+/// A shuffled vector of `0..size`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Permutation(Vec<usize>);
+
+impl Permutation {
+    /// Wrap a shuffled vector
+    pub fn new(vec: Vec<usize>) -> Self {
+        Permutation(vec)
+    }
+
+    /// Shuffled items
+    pub fn as_slice(&self) -> &[usize] {
+        &self.0
+    }
+
+    /// Shuffled items, in the form returned by the `gen_*` functions
+    pub fn into_vec(self) -> Vec<usize> {
+        self.0
+    }
+
+    /// Number of items
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no items
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<usize>> for Permutation {
+    fn from(vec: Vec<usize>) -> Self {
+        Permutation(vec)
+    }
+}
+
+impl From<Permutation> for Vec<usize> {
+    fn from(permutation: Permutation) -> Self {
+        permutation.0
+    }
+}
+
+/// Shuffled position of every requested position, sorted by position
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct PredictedPositions(Vec<(usize, usize)>);
+
+impl PredictedPositions {
+    /// Shuffled position of `position`, if it was requested
+    pub fn get(&self, position: usize) -> Option<usize> {
+        self.0
+            .binary_search_by_key(&position, |&(from, _)| from)
+            .ok()
+            .map(|i| self.0[i].1)
+    }
+
+    /// `(position, shuffled position)` pairs, sorted by position
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// `(position, shuffled position)` pairs, sorted by position
+    pub fn as_slice(&self) -> &[(usize, usize)] {
+        &self.0
+    }
+
+    /// Number of positions
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no positions
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<HashMap<usize, usize>> for PredictedPositions {
+    fn from(map: HashMap<usize, usize>) -> Self {
+        let mut pairs: Vec<(usize, usize)> = map.into_iter().collect();
+        pairs.sort_unstable();
+        PredictedPositions(pairs)
+    }
+}
+
+impl From<PredictedPositions> for HashMap<usize, usize> {
+    fn from(positions: PredictedPositions) -> Self {
+        positions.0.into_iter().collect()
+    }
+}
+
+/// Output of a [`ShuffleRequest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ShuffleResult {
+    /// Result of a shuffle algorithm
+    Permutation(Permutation),
+    /// Result of a predictive algorithm
+    Positions(PredictedPositions),
+}
+
+impl ShuffleResult {
+    /// The permutation, for shuffle algorithms
+    pub fn permutation(&self) -> Option<&Permutation> {
+        match self {
+            ShuffleResult::Permutation(permutation) => Some(permutation),
+            ShuffleResult::Positions(_) => None,
+        }
+    }
+
+    /// The predicted positions, for predictive algorithms
+    pub fn positions(&self) -> Option<&PredictedPositions> {
+        match self {
+            ShuffleResult::Permutation(_) => None,
+            ShuffleResult::Positions(positions) => Some(positions),
+        }
+    }
+}
+
+/// Everything needed to run, and for seeded requests rerun, a shuffle
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShuffleRequest {
+    /// Algorithm to run
+    pub algorithm: Algorithm,
+    /// Number of items
+    pub size: usize,
+    /// Batch size, for batch algorithms
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub batch: Option<usize>,
+    /// Positions to predict, for predictive algorithms
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub positions: Vec<usize>,
+    /// Seed of the shuffle
+    pub seed: SeedDescriptor,
+}
+
+impl ShuffleRequest {
+    /// Run a request seeded from entropy or from a plain seed
+    pub fn run(&self) -> Result<ShuffleResult, RecordError> {
+        let seed = match &self.seed {
+            #[cfg(entropy)]
+            SeedDescriptor::Entropy => None,
+            #[cfg(not(entropy))]
+            SeedDescriptor::Entropy => return Err(RecordError::EntropyUnavailable),
+            SeedDescriptor::Seed(seed) => Some(seed.clone()),
+            SeedDescriptor::Keyed { .. } => return Err(RecordError::KeyRequired),
+        };
+        self.run_with(seed)
+    }
+
+    /// Run a request, looking up the server key of a keyed seed in `keyring`
+    pub fn run_keyed(&self, keyring: &Keyring) -> Result<ShuffleResult, RecordError> {
+        match &self.seed {
+            SeedDescriptor::Keyed {
+                key_id,
+                seed,
+                context,
+            } => self.run_with_key(keyring.get(key_id)?, seed.clone(), context),
+            _ => self.run(),
+        }
+    }
+
+    fn run_with_key(
+        &self,
+        key: &ServerKey,
+        seed: Vec<u8>,
+        context: &[u8],
+    ) -> Result<ShuffleResult, RecordError> {
+        let size = self.size;
+        let batch = self.check()?;
+        let positions = self.positions.clone();
+
+        let permutation = match self.algorithm {
+            Algorithm::FastrandShuffle => {
+                gen_keyed_fastrand_shuffle_from_seed(key, size, seed, context)
+            }
+            Algorithm::ModernShuffle => {
+                gen_keyed_modern_shuffle_from_seed(key, size, seed, context)
+            }
+            Algorithm::CryptoModernShuffle => {
+                gen_keyed_crypto_modern_shuffle_from_seed(key, size, seed, context)
+            }
+            Algorithm::ConstantTimeShuffle => {
+                gen_keyed_constant_time_shuffle_from_seed(key, size, seed, context)
+            }
+            Algorithm::ConstantTimePredictiveShuffle => {
+                let new_positions = gen_keyed_constant_time_predictive_shuffle_from_seed(
+                    key, size, &positions, seed, context,
+                );
+                let map: HashMap<usize, usize> =
+                    positions.into_iter().zip(new_positions.value).collect();
+                return Ok(ShuffleResult::Positions(map.into()));
+            }
+            algorithm => {
+                let map = match algorithm {
+                    Algorithm::PredictiveShuffle => {
+                        gen_keyed_predictive_shuffle_from_seed(key, size, positions, seed, context)
+                    }
+                    Algorithm::CryptoPredictiveShuffle => {
+                        gen_keyed_crypto_predictive_shuffle_from_seed(
+                            key, size, positions, seed, context,
+                        )
+                    }
+                    Algorithm::BatchPredictiveShuffle => {
+                        gen_keyed_batch_predictive_shuffle_from_seed(
+                            key, size, batch, positions, seed, context,
+                        )
+                    }
+                    Algorithm::CryptoBatchPredictiveShuffle => {
+                        gen_keyed_crypto_batch_predictive_shuffle_from_seed(
+                            key, size, batch, positions, seed, context,
+                        )
+                    }
+                    _ => unreachable!("shuffle algorithms are matched above"),
+                };
+                return Ok(ShuffleResult::Positions(map.value.into()));
+            }
+        };
+        Ok(ShuffleResult::Permutation(permutation.value.into()))
+    }
+
+    /// Check the size, batch size and positions, returning the batch size to run with
+    fn check(&self) -> Result<usize, RecordError> {
+        let size = self.size;
+        if size > MAX_SIZE {
+            return Err(RecordError::InvalidSize(size));
+        }
+        let batch = match self.batch {
+            Some(batch) if batch > 0 && self.algorithm.is_batch() => {
+                check_batch(size, batch).map_err(RecordError::InvalidBatch)?;
+                batch
+            }
+            Some(batch) if batch > 0 => batch,
+            _ if self.algorithm.is_batch() => return Err(RecordError::MissingBatch),
+            _ => 0,
+        };
+        if self.algorithm.is_predictive() {
+            check_positions(size, &self.positions).map_err(RecordError::InvalidPosition)?;
+        }
+        Ok(batch)
+    }
+
+    fn run_with(&self, seed: Option<Vec<u8>>) -> Result<ShuffleResult, RecordError> {
+        let size = self.size;
+        let positions = self.positions.as_slice();
+        let batch = self.check()?;
+
+        let permutation = match (self.algorithm, seed) {
+            #[cfg(entropy)]
+            (Algorithm::FastrandShuffle, None) => gen_fastrand_shuffle(size),
+            (Algorithm::FastrandShuffle, Some(seed)) => {
+                gen_fastrand_shuffle_from_seed_ref(size, seed)
+            }
+            #[cfg(entropy)]
+            (Algorithm::ModernShuffle, None) => gen_modern_shuffle(size),
+            (Algorithm::ModernShuffle, Some(seed)) => gen_modern_shuffle_from_seed_ref(size, seed),
+            #[cfg(entropy)]
+            (Algorithm::CryptoModernShuffle, None) => gen_crypto_modern_shuffle(size),
+            (Algorithm::CryptoModernShuffle, Some(seed)) => {
+                gen_crypto_modern_shuffle_from_seed_ref(size, seed)
+            }
+            #[cfg(entropy)]
+            (Algorithm::ConstantTimeShuffle, None) => gen_constant_time_shuffle(size),
+            (Algorithm::ConstantTimeShuffle, Some(seed)) => {
+                gen_constant_time_shuffle_from_seed(size, seed)
+            }
+            (Algorithm::ConstantTimePredictiveShuffle, seed) => {
+                let new_positions = match seed {
+                    #[cfg(entropy)]
+                    None => gen_constant_time_predictive_shuffle(size, positions),
+                    #[cfg(not(entropy))]
+                    None => unreachable!("entropy seeded requests are rejected by `run`"),
+                    Some(seed) => {
+                        gen_constant_time_predictive_shuffle_from_seed(size, positions, seed)
+                    }
+                };
+                let map: HashMap<usize, usize> =
+                    positions.iter().copied().zip(new_positions).collect();
+                return Ok(ShuffleResult::Positions(map.into()));
+            }
+            (algorithm, seed) => {
+                let map = match (algorithm, seed) {
+                    #[cfg(entropy)]
+                    (Algorithm::PredictiveShuffle, None) => {
+                        gen_predictive_shuffle_ref(size, positions)
+                    }
+                    (Algorithm::PredictiveShuffle, Some(seed)) => {
+                        gen_predictive_shuffle_from_seed_ref(size, positions, seed)
+                    }
+                    #[cfg(entropy)]
+                    (Algorithm::CryptoPredictiveShuffle, None) => {
+                        gen_crypto_predictive_shuffle_ref(size, positions)
+                    }
+                    (Algorithm::CryptoPredictiveShuffle, Some(seed)) => {
+                        gen_crypto_predictive_shuffle_from_seed_ref(size, positions, seed)
+                    }
+                    #[cfg(entropy)]
+                    (Algorithm::BatchPredictiveShuffle, None) => {
+                        gen_batch_predictive_shuffle_ref(size, batch, positions)
+                    }
+                    (Algorithm::BatchPredictiveShuffle, Some(seed)) => {
+                        gen_batch_predictive_shuffle_from_seed_ref(size, batch, positions, seed)
+                    }
+                    #[cfg(entropy)]
+                    (Algorithm::CryptoBatchPredictiveShuffle, None) => {
+                        gen_crypto_batch_predictive_shuffle_ref(size, batch, positions)
+                    }
+                    (Algorithm::CryptoBatchPredictiveShuffle, Some(seed)) => {
+                        gen_crypto_batch_predictive_shuffle_from_seed_ref(
+                            size, batch, positions, seed,
+                        )
+                    }
+                    _ => unreachable!("shuffle algorithms are matched above"),
+                };
+                return Ok(ShuffleResult::Positions(map.into()));
+            }
+        };
+        Ok(ShuffleResult::Permutation(permutation.into()))
+    }
+}
+
+/// A request together with its result
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShuffleRecord {
+    /// The request that was run
+    pub request: ShuffleRequest,
+    /// Its result
+    pub result: ShuffleResult,
+}
+
+impl ShuffleRecord {
+    /// Run a request seeded from entropy or from a plain seed, and record its result
+    pub fn new(request: ShuffleRequest) -> Result<Self, RecordError> {
+        let result = request.run()?;
+        Ok(ShuffleRecord { request, result })
+    }
+
+    /// Run a request with a keyed seed, and record its result
+    pub fn new_keyed(request: ShuffleRequest, keyring: &Keyring) -> Result<Self, RecordError> {
+        let result = request.run_keyed(keyring)?;
+        Ok(ShuffleRecord { request, result })
+    }
+
+    /// Recompute a record with a plain seed and check its result
+    pub fn verify(&self) -> Result<(), RecordError> {
+        self.check_seed()?;
+        self.check(self.request.run()?)
+    }
+
+    /// Recompute a record with a keyed seed and check its result
+    pub fn verify_keyed(&self, keyring: &Keyring) -> Result<(), RecordError> {
+        self.check_seed()?;
+        self.check(self.request.run_keyed(keyring)?)
+    }
+
+    fn check_seed(&self) -> Result<(), RecordError> {
+        if self.request.seed == SeedDescriptor::Entropy {
+            return Err(RecordError::NotReproducible);
+        }
+        Ok(())
+    }
+
+    fn check(&self, result: ShuffleResult) -> Result<(), RecordError> {
+        if result != self.result {
+            return Err(RecordError::ResultMismatch);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+mod hex_bytes {
+    use crate::hex;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        hex::decode(&text).ok_or_else(|| de::Error::custom(format!("invalid hex {:?}", text)))
+    }
+}
@@ -0,0 +1,339 @@
+//! # reservoir
+//! Streaming reservoir sampling of `k` items from iterators of unknown length.
+//!
+//! Uniform samples use Li's Algorithm L, and weighted samples use Efraimidis and Spirakis'
+//! A-ExpJ, both of which skip over the stream instead of drawing a random number per item. Seeds
+//! are handled exactly like [`crate::gen_modern_shuffle_from_seed_ref`], and a sample can optionally
+//! be returned in shuffled order with [`Reservoir::into_shuffled`].
+
+#[cfg(feature = "fastrand")]
+use crate::byte_array;
+use crate::rng::Draw;
+#[cfg(feature = "chacha")]
+use crate::CryptoRng;
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{Ordering, Reverse};
+// `ln` and `exp` are inherent methods of `f64` only when `std` is linked, which dependencies may do
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float;
+#[cfg(feature = "chacha")]
+use rand::Rng;
+
+/// Random source used by the reservoir samplers
+pub trait SampleRng {
+    /// Uniform float in `(0, 1]`
+    fn unit(&mut self) -> f64;
+    /// Uniform integer in `0..=upper`
+    fn index(&mut self, upper: usize) -> usize;
+}
+
+#[cfg(feature = "fastrand")]
+impl SampleRng for fastrand::Rng {
+    fn unit(&mut self) -> f64 {
+        1.0 - self.f64()
+    }
+
+    fn index(&mut self, upper: usize) -> usize {
+        self.index_to(upper)
+    }
+}
+
+#[cfg(feature = "chacha")]
+impl SampleRng for CryptoRng {
+    fn unit(&mut self) -> f64 {
+        1.0 - self.gen::<f64>()
+    }
+
+    fn index(&mut self, upper: usize) -> usize {
+        self.index_to(upper)
+    }
+}
+
+/// Items selected from a stream, along with the random source that selected them
+#[derive(Debug, Clone)]
+pub struct Reservoir<T, R> {
+    items: Vec<T>,
+    rng: R,
+}
+
+impl<T, R: SampleRng> Reservoir<T, R> {
+    /// Number of sampled items, which is less than `k` only if the stream was shorter than `k`
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether no items were sampled
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Sampled items in reservoir order
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Sampled items in reservoir order, which is not uniformly random for short streams
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+
+    /// Sampled items shuffled with the modern Fisher-Yates Algorithm, continuing the random
+    /// stream used for sampling
+    pub fn into_shuffled(self) -> Vec<T> {
+        let Reservoir { mut items, mut rng } = self;
+
+        let mut new_vec = vec![];
+        for i in (0..items.len()).rev() {
+            let x: usize = rng.index(i);
+
+            new_vec.push(items.swap_remove(x));
+        }
+        new_vec
+    }
+}
+
+/// Reservoir sampling adapters for any [`Iterator`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// # #[cfg(feature = "fastrand")]
+/// # {
+/// use predictive_shuffle::reservoir::ReservoirSampling;
+///
+/// let seed = b"seed phrase";
+/// let sample = (0..1_000).reservoir_sample_from_seed(5, seed).into_shuffled();
+/// assert_eq!(sample.len(), 5);
+/// # }
+/// ```
+pub trait ReservoirSampling: Iterator + Sized {
+    /// Uniformly sample `k` items with ['fastrand::Rng']
+    #[cfg(all(feature = "fastrand", entropy))]
+    fn reservoir_sample(self, k: usize) -> Reservoir<Self::Item, fastrand::Rng> {
+        algorithm_l(self, k, fastrand::Rng::new())
+    }
+
+    /// Uniformly sample `k` items from a seed with ['fastrand::Rng']
+    #[cfg(feature = "fastrand")]
+    fn reservoir_sample_from_seed(
+        self,
+        k: usize,
+        seed: impl AsRef<[u8]>,
+    ) -> Reservoir<Self::Item, fastrand::Rng> {
+        let seed = byte_array(seed.as_ref());
+        let seed_int = u64::from_be_bytes(seed);
+
+        algorithm_l(self, k, fastrand::Rng::with_seed(seed_int))
+    }
+
+    /// Uniformly sample `k` items with ['rand_chacha::ChaCha20Rng']
+    #[cfg(all(feature = "chacha", entropy))]
+    fn crypto_reservoir_sample(self, k: usize) -> Reservoir<Self::Item, CryptoRng> {
+        algorithm_l(self, k, CryptoRng::from_entropy())
+    }
+
+    /// Uniformly sample `k` items from a seed with ['rand_chacha::ChaCha20Rng']
+    #[cfg(feature = "chacha")]
+    fn crypto_reservoir_sample_from_seed(
+        self,
+        k: usize,
+        seed: impl AsRef<[u8]>,
+    ) -> Reservoir<Self::Item, CryptoRng> {
+        algorithm_l(self, k, CryptoRng::from_seed_ref(seed.as_ref()))
+    }
+
+    /// Sample `k` items with probability proportional to `weight` with ['fastrand::Rng']
+    #[cfg(all(feature = "fastrand", entropy))]
+    fn weighted_reservoir_sample<F>(
+        self,
+        k: usize,
+        weight: F,
+    ) -> Reservoir<Self::Item, fastrand::Rng>
+    where
+        F: FnMut(&Self::Item) -> f64,
+    {
+        a_exp_j(self, k, weight, fastrand::Rng::new())
+    }
+
+    /// Sample `k` items with probability proportional to `weight` from a seed with
+    /// ['fastrand::Rng']
+    #[cfg(feature = "fastrand")]
+    fn weighted_reservoir_sample_from_seed<F>(
+        self,
+        k: usize,
+        seed: impl AsRef<[u8]>,
+        weight: F,
+    ) -> Reservoir<Self::Item, fastrand::Rng>
+    where
+        F: FnMut(&Self::Item) -> f64,
+    {
+        let seed = byte_array(seed.as_ref());
+        let seed_int = u64::from_be_bytes(seed);
+
+        a_exp_j(self, k, weight, fastrand::Rng::with_seed(seed_int))
+    }
+
+    /// Sample `k` items with probability proportional to `weight` with
+    /// ['rand_chacha::ChaCha20Rng']
+    #[cfg(all(feature = "chacha", entropy))]
+    fn crypto_weighted_reservoir_sample<F>(
+        self,
+        k: usize,
+        weight: F,
+    ) -> Reservoir<Self::Item, CryptoRng>
+    where
+        F: FnMut(&Self::Item) -> f64,
+    {
+        a_exp_j(self, k, weight, CryptoRng::from_entropy())
+    }
+
+    /// Sample `k` items with probability proportional to `weight` from a seed with
+    /// ['rand_chacha::ChaCha20Rng']
+    #[cfg(feature = "chacha")]
+    fn crypto_weighted_reservoir_sample_from_seed<F>(
+        self,
+        k: usize,
+        seed: impl AsRef<[u8]>,
+        weight: F,
+    ) -> Reservoir<Self::Item, CryptoRng>
+    where
+        F: FnMut(&Self::Item) -> f64,
+    {
+        a_exp_j(self, k, weight, CryptoRng::from_seed_ref(seed.as_ref()))
+    }
+}
+
+impl<I: Iterator> ReservoirSampling for I {}
+
+/// Uniformly sample `k` items from `iter` with Algorithm L
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// # #[cfg(feature = "fastrand")]
+/// # {
+/// use predictive_shuffle::reservoir::algorithm_l;
+///
+/// let rng = fastrand::Rng::with_seed(7);
+/// let sample = algorithm_l(0..100, 10, rng);
+/// assert_eq!(sample.len(), 10);
+/// # }
+/// ```
+pub fn algorithm_l<I, R>(iter: I, k: usize, mut rng: R) -> Reservoir<I::Item, R>
+where
+    I: IntoIterator,
+    R: SampleRng,
+{
+    let mut iter = iter.into_iter();
+    let mut items: Vec<I::Item> = iter.by_ref().take(k).collect();
+    if k == 0 || items.len() < k {
+        return Reservoir { items, rng };
+    }
+
+    let mut w = (rng.unit().ln() / k as f64).exp();
+    loop {
+        // number of items to skip before the next replacement, saturating on overflow
+        let skip = (rng.unit().ln() / (1.0 - w).ln()).floor() as usize;
+        match iter.nth(skip) {
+            Some(item) => {
+                let x = rng.index(k - 1);
+                items[x] = item;
+                w *= (rng.unit().ln() / k as f64).exp();
+            }
+            None => break,
+        }
+    }
+    Reservoir { items, rng }
+}
+
+/// Sample `k` items from `iter` with probability proportional to `weight` with A-ExpJ
+///
+/// Items with a weight that is not strictly positive are never selected.
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// # #[cfg(feature = "fastrand")]
+/// # {
+/// use predictive_shuffle::reservoir::a_exp_j;
+///
+/// let rng = fastrand::Rng::with_seed(7);
+/// let sample = a_exp_j(0..100, 10, |item| *item as f64, rng);
+/// assert!(!sample.as_slice().contains(&0));
+/// # }
+/// ```
+pub fn a_exp_j<I, F, R>(iter: I, k: usize, mut weight: F, mut rng: R) -> Reservoir<I::Item, R>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item) -> f64,
+    R: SampleRng,
+{
+    let mut items: Vec<I::Item> = Vec::with_capacity(k);
+    if k == 0 {
+        return Reservoir { items, rng };
+    }
+
+    // min-heap of log keys, ln(u) / w, pointing at reservoir slots
+    let mut keys: BinaryHeap<Reverse<Key>> = BinaryHeap::with_capacity(k);
+    let mut threshold = 0.0;
+    let mut jump = 0.0;
+    for item in iter {
+        let w = weight(&item);
+        if !(w > 0.0 && w.is_finite()) {
+            continue;
+        }
+
+        if items.len() < k {
+            keys.push(Reverse(Key(rng.unit().ln() / w, items.len())));
+            items.push(item);
+            if items.len() == k {
+                threshold = keys.peek().map_or(0.0, |key| key.0 .0);
+                jump = rng.unit().ln() / threshold;
+            }
+            continue;
+        }
+
+        jump -= w;
+        if jump <= 0.0 {
+            // draw the new key from (t^w, 1), where t is the smallest key in the reservoir
+            let t = (threshold * w).exp();
+            let r = t + (1.0 - t) * rng.unit();
+            let slot = keys.pop().map_or(0, |key| key.0 .1);
+            keys.push(Reverse(Key(r.ln() / w, slot)));
+            items[slot] = item;
+
+            threshold = keys.peek().map_or(0.0, |key| key.0 .0);
+            jump = rng.unit().ln() / threshold;
+        }
+    }
+    Reservoir { items, rng }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Key(f64, usize);
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
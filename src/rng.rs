@@ -0,0 +1,226 @@
+//! # rng
+//! The ['rand_chacha::ChaCha20Rng'] behind every `crypto_*` function.
+//!
+//! Seed bytes are hashed with the crate's seed hasher, and the whole 256-bit digest keys
+//! ChaCha20, so the generator of a `crypto_*_from_seed` function cannot be recovered from part of
+//! its output by searching a smaller space of states than the key itself.
+//!
+//! With the `zeroize` feature enabled, seed vectors handed over by value, the derived digest and
+//! the ChaCha20 state itself are wiped once they are no longer needed. Borrowed seeds stay with
+//! the caller, who is responsible for wiping them.
+
+#[cfg(feature = "chacha")]
+use crate::digest;
+#[cfg(feature = "chacha")]
+use alloc::vec::Vec;
+#[cfg(feature = "chacha")]
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "chacha")]
+use rand::{Rng, RngCore, SeedableRng};
+#[cfg(feature = "chacha")]
+use rand_chacha::ChaCha20Rng;
+
+/// ['rand_chacha::ChaCha20Rng'] that wipes its state when dropped if the `zeroize` feature is
+/// enabled
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::CryptoRng;
+/// use rand::Rng;
+///
+/// let seed = b"seed phrase".to_vec();
+/// let mut rng = CryptoRng::from_seed(seed);
+/// let x: usize = rng.gen_range(0..10);
+/// ```
+#[cfg(feature = "chacha")]
+#[derive(Debug, Clone)]
+pub struct CryptoRng(ChaCha20Rng);
+
+#[cfg(feature = "chacha")]
+impl CryptoRng {
+    /// Seed from operating system entropy
+    #[cfg(entropy)]
+    pub fn from_entropy() -> Self {
+        CryptoRng(ChaCha20Rng::from_entropy())
+    }
+
+    /// Seed the same way as every `crypto_*_from_seed` function, consuming (and with the
+    /// `zeroize` feature, wiping) the seed bytes
+    pub fn from_seed(seed: Vec<u8>) -> Self {
+        let mut seed = seed;
+        let rng = CryptoRng::from_seed_ref(&seed);
+        wipe(&mut seed);
+        rng
+    }
+
+    /// Seed the same way as every `crypto_*_from_seed_ref` function, leaving the borrowed seed
+    /// bytes to the caller
+    ///
+    /// The whole 256-bit digest of the seed bytes is the ChaCha20 key.
+    pub fn from_seed_ref(seed: &[u8]) -> Self {
+        let mut key = digest(seed);
+        let rng = CryptoRng::from_key(key);
+        wipe(&mut key);
+        rng
+    }
+
+    /// Seed with a full 256-bit ChaCha20 key, for seed material that is already uniformly random
+    pub fn from_key(key: [u8; 32]) -> Self {
+        let mut key = key;
+        let rng = ChaCha20Rng::from_seed(key);
+        wipe(&mut key);
+        CryptoRng(rng)
+    }
+
+    /// Position in the ChaCha20 stream, to resume from with [`CryptoRng::from_state`]
+    pub fn state(&self) -> CryptoRngState {
+        CryptoRngState {
+            seed: self.0.get_seed(),
+            stream: self.0.get_stream(),
+            word_pos: self.0.get_word_pos(),
+        }
+    }
+
+    /// Resume drawing where [`CryptoRng::state`] was taken
+    pub fn from_state(state: &CryptoRngState) -> Self {
+        let mut rng = ChaCha20Rng::from_seed(state.seed);
+        rng.set_stream(state.stream);
+        rng.set_word_pos(state.word_pos);
+        CryptoRng(rng)
+    }
+}
+
+/// Checkpoint of a [`CryptoRng`], which wipes its seed when dropped if the `zeroize` feature is
+/// enabled
+#[cfg(feature = "chacha")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CryptoRngState {
+    /// ChaCha20 key
+    pub seed: [u8; 32],
+    /// ChaCha20 stream number
+    pub stream: u64,
+    /// Number of 32-bit words drawn from the stream
+    pub word_pos: u128,
+}
+
+#[cfg(all(feature = "chacha", feature = "zeroize"))]
+impl zeroize::Zeroize for CryptoRngState {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.stream.zeroize();
+        self.word_pos.zeroize();
+    }
+}
+
+#[cfg(all(feature = "chacha", feature = "zeroize"))]
+impl Drop for CryptoRngState {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(all(feature = "chacha", feature = "zeroize"))]
+impl zeroize::ZeroizeOnDrop for CryptoRngState {}
+
+#[cfg(feature = "chacha")]
+impl Deref for CryptoRng {
+    type Target = ChaCha20Rng;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "chacha")]
+impl DerefMut for CryptoRng {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "chacha")]
+impl RngCore for CryptoRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(feature = "chacha")]
+impl rand::CryptoRng for CryptoRng {}
+
+#[cfg(all(feature = "chacha", feature = "zeroize"))]
+impl Drop for CryptoRng {
+    fn drop(&mut self) {
+        // ChaCha20Rng has no zeroize support, so overwrite it with the all-zero key state
+        unsafe { core::ptr::write_volatile(&mut self.0, ChaCha20Rng::from_seed([0; 32])) };
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// Index draws made through `u64`, so that a seeded generator is consumed the same way on 32 and
+/// 64-bit targets and `wasm32` builds reproduce native results
+#[cfg(any(feature = "chacha", feature = "fastrand"))]
+pub(crate) trait Draw {
+    /// Uniform index in `0..=upper`
+    fn index_to(&mut self, upper: usize) -> usize;
+
+    /// Uniform index in `0..len`
+    #[cfg(feature = "chacha")]
+    fn index_below(&mut self, len: usize) -> usize;
+
+    /// Fisher-Yates shuffle drawing in the same order as `fastrand::Rng::shuffle`
+    #[cfg(feature = "fastrand")]
+    fn shuffle_slice<T>(&mut self, slice: &mut [T]) {
+        for i in 1..slice.len() {
+            slice.swap(i, self.index_to(i));
+        }
+    }
+}
+
+#[cfg(feature = "fastrand")]
+impl Draw for fastrand::Rng {
+    fn index_to(&mut self, upper: usize) -> usize {
+        self.u64(0..=upper as u64) as usize
+    }
+
+    #[cfg(feature = "chacha")]
+    fn index_below(&mut self, len: usize) -> usize {
+        self.u64(0..len as u64) as usize
+    }
+}
+
+#[cfg(feature = "chacha")]
+impl Draw for CryptoRng {
+    fn index_to(&mut self, upper: usize) -> usize {
+        self.gen_range(0..=upper as u64) as usize
+    }
+
+    fn index_below(&mut self, len: usize) -> usize {
+        self.gen_range(0..len as u64) as usize
+    }
+}
+
+/// Wipe secret material if the `zeroize` feature is enabled
+#[cfg(feature = "zeroize")]
+pub(crate) fn wipe<Z: zeroize::Zeroize + ?Sized>(secret: &mut Z) {
+    secret.zeroize();
+}
+
+/// Wipe secret material if the `zeroize` feature is enabled
+#[cfg(not(feature = "zeroize"))]
+pub(crate) fn wipe<Z: ?Sized>(_secret: &mut Z) {}
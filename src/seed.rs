@@ -0,0 +1,83 @@
+//! # seed
+//! Seed material accepted by every `*_from_seed` function.
+//!
+//! With the `zeroize` feature enabled, a [`Seed`] wipes its bytes when dropped.
+
+use alloc::vec::Vec;
+
+/// Seed bytes for the `*_from_seed` functions, produced by protocols such as
+/// [`crate::multiparty`]
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// # #[cfg(feature = "chacha")]
+/// # {
+/// use predictive_shuffle::{Seed, Shuffle};
+///
+/// let mut vec: Vec<usize> = (0..10).collect();
+/// let seed = Seed::new(b"seed phrase".to_vec());
+/// let shuffled_vec = vec.crypto_modern_shuffle_from_seed_ref(&seed);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Seed(Vec<u8>);
+
+impl Seed {
+    /// Wrap raw seed bytes
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Seed(bytes)
+    }
+
+    /// Seed bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Seed bytes, in the form taken by the `*_from_seed` functions
+    pub fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.0)
+    }
+}
+
+impl From<Vec<u8>> for Seed {
+    fn from(bytes: Vec<u8>) -> Self {
+        Seed(bytes)
+    }
+}
+
+impl From<&[u8]> for Seed {
+    fn from(bytes: &[u8]) -> Self {
+        Seed(bytes.to_vec())
+    }
+}
+
+impl From<Seed> for Vec<u8> {
+    fn from(seed: Seed) -> Self {
+        seed.into_vec()
+    }
+}
+
+impl AsRef<[u8]> for Seed {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Seed {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Seed {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Seed {}
@@ -0,0 +1,212 @@
+//! # shuffler
+//! A [`Shuffler`] hashes its seed once and keeps its random number generator between calls, so
+//! related shuffles, predictions and samples draw from one continuing stream instead of each
+//! reseeding.
+//!
+//! Seeds are hashed as in the `*_from_seed_ref` functions, and every method draws like
+//! [`crate::ordered::gen_predictive_shuffle_from_seed_into`], so the first prediction of a freshly
+//! seeded `Shuffler<fastrand::Rng>` matches it. A `Shuffler<CryptoRng>` draws the same way from
+//! ChaCha20, so its results differ from the `crypto_*` functions for the same seed, which predict
+//! with a Sattolo step and permute with `swap_remove`. From the same state,
+//! [`Shuffler::predict`] gives the positions that [`Shuffler::shuffle`] and [`Shuffler::gen`] move
+//! items to.
+//! [`Shuffler::state`] checkpoints the stream, and [`Shuffler::from_state`] resumes it.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! # #[cfg(feature = "chacha")]
+//! # {
+//! use predictive_shuffle::{CryptoRng, Shuffler};
+//!
+//! let mut shuffler = Shuffler::<CryptoRng>::from_seed(b"seed phrase");
+//! let first = shuffler.gen(10);
+//!
+//! let checkpoint = shuffler.state();
+//! let second = shuffler.gen(10);
+//!
+//! let mut resumed = Shuffler::<CryptoRng>::from_state(checkpoint);
+//! assert_eq!(resumed.gen(10), second);
+//! # }
+//! ```
+
+#[cfg(feature = "fastrand")]
+use crate::byte_array;
+use crate::ordered::predict_into;
+use crate::reservoir::SampleRng;
+#[cfg(feature = "chacha")]
+use crate::{CryptoRng, CryptoRngState};
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Random number generator behind a [`Shuffler`], which can be seeded and checkpointed
+pub trait ShuffleRng: SampleRng + Sized {
+    /// Checkpoint of the generator, from which it resumes drawing
+    type State;
+
+    /// Seed from operating system entropy
+    #[cfg(entropy)]
+    fn from_entropy() -> Self;
+
+    /// Seed the same way as the `*_from_seed_ref` functions using this generator
+    fn from_seed_ref(seed: &[u8]) -> Self;
+
+    /// Checkpoint the generator
+    fn state(&self) -> Self::State;
+
+    /// Resume drawing from a checkpoint
+    fn from_state(state: Self::State) -> Self;
+}
+
+#[cfg(feature = "fastrand")]
+impl ShuffleRng for fastrand::Rng {
+    type State = u64;
+
+    #[cfg(entropy)]
+    fn from_entropy() -> Self {
+        fastrand::Rng::new()
+    }
+
+    fn from_seed_ref(seed: &[u8]) -> Self {
+        let seed = byte_array(seed);
+        let seed_int = u64::from_be_bytes(seed);
+        fastrand::Rng::with_seed(seed_int)
+    }
+
+    fn state(&self) -> u64 {
+        self.get_seed()
+    }
+
+    fn from_state(state: u64) -> Self {
+        fastrand::Rng::with_seed(state)
+    }
+}
+
+#[cfg(feature = "chacha")]
+impl ShuffleRng for CryptoRng {
+    type State = CryptoRngState;
+
+    #[cfg(entropy)]
+    fn from_entropy() -> Self {
+        CryptoRng::from_entropy()
+    }
+
+    fn from_seed_ref(seed: &[u8]) -> Self {
+        CryptoRng::from_seed_ref(seed)
+    }
+
+    fn state(&self) -> CryptoRngState {
+        CryptoRng::state(self)
+    }
+
+    fn from_state(state: CryptoRngState) -> Self {
+        CryptoRng::from_state(&state)
+    }
+}
+
+/// Shuffles, predictions and samples drawn from one continuing random stream
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// # #[cfg(feature = "fastrand")]
+/// # {
+/// use predictive_shuffle::Shuffler;
+///
+/// let mut shuffler = Shuffler::<fastrand::Rng>::from_seed(b"seed phrase");
+/// let state = shuffler.state();
+/// let new_positions = shuffler.predict(100, &[1, 5]);
+///
+/// let mut shuffler = Shuffler::<fastrand::Rng>::from_state(state);
+/// let shuffled = shuffler.gen(100);
+/// assert_eq!(shuffled[new_positions[0]], 1);
+/// assert_eq!(shuffled[new_positions[1]], 5);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Shuffler<R> {
+    rng: R,
+}
+
+impl<R: ShuffleRng> Shuffler<R> {
+    /// Draw from `rng`
+    pub fn new(rng: R) -> Self {
+        Shuffler { rng }
+    }
+
+    /// Seed from operating system entropy
+    #[cfg(entropy)]
+    pub fn from_entropy() -> Self {
+        Shuffler::new(R::from_entropy())
+    }
+
+    /// Seed the same way as the `*_from_seed_ref` functions using `R`
+    pub fn from_seed(seed: impl AsRef<[u8]>) -> Self {
+        Shuffler::new(R::from_seed_ref(seed.as_ref()))
+    }
+
+    /// Resume the stream where [`Shuffler::state`] was taken
+    pub fn from_state(state: R::State) -> Self {
+        Shuffler::new(R::from_state(state))
+    }
+
+    /// Checkpoint the stream
+    pub fn state(&self) -> R::State {
+        self.rng.state()
+    }
+
+    /// Random number generator, as left by the draws so far
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+
+    /// Shuffle `items` in place
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (0..items.len()).rev() {
+            let x = self.rng.index(i);
+            items.swap(x, i);
+        }
+    }
+
+    /// Generate a shuffled vector of `0..size`
+    pub fn gen(&mut self, size: usize) -> Vec<usize> {
+        let mut vec: Vec<usize> = (0..size).collect();
+        self.shuffle(&mut vec);
+        vec
+    }
+
+    /// Predict the shuffled positions of `positions` in a vector of `size` items, aligned with
+    /// `positions`
+    ///
+    /// Drawing stops once every position is placed, so the stream continues from an earlier
+    /// point than after a full [`Shuffler::shuffle`]. Positions must be below `size` and
+    /// distinct, as in [`crate::ordered`].
+    pub fn predict(&mut self, size: usize, positions: &[usize]) -> Vec<usize> {
+        let mut out = vec![0; positions.len()];
+        predict_into(size, positions, &mut out, |i| self.rng.index(i));
+        out
+    }
+
+    /// Sample `k` distinct items of `0..size`, drawing only `k` times
+    ///
+    /// The sample is the last `k` items of [`Shuffler::gen`] from the same state, last first.
+    /// Panics if `k` is larger than `size`.
+    pub fn sample(&mut self, size: usize, k: usize) -> Vec<usize> {
+        assert!(k <= size, "cannot sample {} items out of {}", k, size);
+
+        // items moved away from their own slot by earlier draws
+        let mut moved: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut sample = Vec::with_capacity(k);
+        for i in (size - k..size).rev() {
+            let x = self.rng.index(i);
+            let item = moved.get(&x).copied().unwrap_or(x);
+            let last = moved.remove(&i).unwrap_or(i);
+            moved.insert(x, last);
+            sample.push(item);
+        }
+        sample
+    }
+}
@@ -0,0 +1,255 @@
+//! # table
+//! Row shuffles of Arrow [`RecordBatch`]es and Polars [`DataFrame`]s, behind the `arrow` and
+//! `polars` features.
+//!
+//! A [`TakeIndex`] holds the permutation of [`crate::gen_modern_shuffle_from_seed_ref`] or
+//! [`crate::gen_crypto_modern_shuffle_from_seed_ref`] as a take-index: row `k` of the shuffled
+//! table is row `indices[k]` of the original. It is applied to every column at once by the
+//! `take` kernels of Arrow and Polars, and [`TakeIndex::predict`] tells where given rows land.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! # #[cfg(feature = "arrow")]
+//! # {
+//! use arrow_array::{ArrayRef, Int64Array, RecordBatch};
+//! use predictive_shuffle::table::TakeIndex;
+//! use std::sync::Arc;
+//!
+//! let ids: ArrayRef = Arc::new(Int64Array::from((0..100).collect::<Vec<i64>>()));
+//! let batch = RecordBatch::try_from_iter([("id", ids)]).unwrap();
+//!
+//! let index = TakeIndex::modern_from_seed_ref(batch.num_rows(), b"seed phrase");
+//! let shuffled = index.take_record_batch(&batch).unwrap();
+//! let new_positions = index.predict([1, 5]);
+//!
+//! let ids = shuffled.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+//! assert_eq!(ids.value(new_positions[&1]), 1);
+//! assert_eq!(ids.value(new_positions[&5]), 5);
+//! # }
+//! ```
+
+#[cfg(all(feature = "chacha", entropy))]
+use crate::gen_crypto_modern_shuffle;
+#[cfg(feature = "chacha")]
+use crate::gen_crypto_modern_shuffle_from_seed_ref;
+#[cfg(all(feature = "fastrand", entropy))]
+use crate::gen_modern_shuffle;
+#[cfg(feature = "fastrand")]
+use crate::gen_modern_shuffle_from_seed_ref;
+#[cfg(feature = "arrow")]
+use arrow_array::{RecordBatch, UInt64Array};
+#[cfg(feature = "arrow")]
+use arrow_schema::ArrowError;
+#[cfg(feature = "arrow")]
+use arrow_select::take::take_record_batch;
+#[cfg(feature = "polars")]
+use polars::prelude::{DataFrame, IdxCa, IdxSize, PolarsError, PolarsResult};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+/// Row order of a shuffled table, as the permutation of a `gen_*` shuffle
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use predictive_shuffle::table::TakeIndex;
+/// use predictive_shuffle::gen_crypto_modern_shuffle_from_seed_ref;
+///
+/// let index = TakeIndex::crypto_modern_from_seed_ref(100, b"seed phrase");
+/// assert_eq!(index.as_slice(), gen_crypto_modern_shuffle_from_seed_ref(100, b"seed phrase"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TakeIndex {
+    indices: Vec<usize>,
+}
+
+impl TakeIndex {
+    /// Shuffle `num_rows` rows with [`crate::gen_modern_shuffle`]
+    #[cfg(all(feature = "fastrand", entropy))]
+    pub fn modern(num_rows: usize) -> Self {
+        TakeIndex::from(gen_modern_shuffle(num_rows))
+    }
+
+    /// Shuffle `num_rows` rows with [`crate::gen_modern_shuffle_from_seed_ref`]
+    #[cfg(feature = "fastrand")]
+    pub fn modern_from_seed_ref(num_rows: usize, seed: impl AsRef<[u8]>) -> Self {
+        TakeIndex::from(gen_modern_shuffle_from_seed_ref(num_rows, seed))
+    }
+
+    /// Shuffle `num_rows` rows with [`crate::gen_crypto_modern_shuffle`]
+    #[cfg(all(feature = "chacha", entropy))]
+    pub fn crypto_modern(num_rows: usize) -> Self {
+        TakeIndex::from(gen_crypto_modern_shuffle(num_rows))
+    }
+
+    /// Shuffle `num_rows` rows with [`crate::gen_crypto_modern_shuffle_from_seed_ref`]
+    #[cfg(feature = "chacha")]
+    pub fn crypto_modern_from_seed_ref(num_rows: usize, seed: impl AsRef<[u8]>) -> Self {
+        TakeIndex::from(gen_crypto_modern_shuffle_from_seed_ref(num_rows, seed))
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Whether there are no rows
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Original row of every shuffled row
+    pub fn as_slice(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Predict the shuffled positions of `rows`
+    ///
+    /// Unlike the `gen_*_predictive_shuffle` functions, which run their own algorithm, these are
+    /// the positions the take-index moves rows to. Panics if a row is out of range.
+    pub fn predict(
+        &self,
+        rows: impl IntoIterator<Item = impl Borrow<usize>>,
+    ) -> HashMap<usize, usize> {
+        let mut new_positions = vec![0; self.len()];
+        for (new_position, &row) in self.indices.iter().enumerate() {
+            new_positions[row] = new_position;
+        }
+        rows.into_iter()
+            .map(|row| (*row.borrow(), new_positions[*row.borrow()]))
+            .collect()
+    }
+
+    /// Shuffle the rows of `batch`, which must have [`TakeIndex::len`] rows
+    #[cfg(feature = "arrow")]
+    pub fn take_record_batch(&self, batch: &RecordBatch) -> Result<RecordBatch, ArrowError> {
+        if batch.num_rows() != self.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "take-index of {} rows applied to a record batch of {} rows",
+                self.len(),
+                batch.num_rows()
+            )));
+        }
+        let indices: UInt64Array = self.indices.iter().map(|&i| i as u64).collect();
+        take_record_batch(batch, &indices)
+    }
+
+    /// Shuffle the rows of `df`, which must have [`TakeIndex::len`] rows
+    #[cfg(feature = "polars")]
+    pub fn take_data_frame(&self, df: &DataFrame) -> PolarsResult<DataFrame> {
+        if df.height() != self.len() {
+            return Err(PolarsError::ShapeMismatch(
+                format!(
+                    "take-index of {} rows applied to a data frame of {} rows",
+                    self.len(),
+                    df.height()
+                )
+                .into(),
+            ));
+        }
+        let indices = IdxCa::from_vec(
+            "index".into(),
+            self.indices.iter().map(|&i| i as IdxSize).collect(),
+        );
+        df.take(&indices)
+    }
+}
+
+impl From<Vec<usize>> for TakeIndex {
+    fn from(indices: Vec<usize>) -> Self {
+        TakeIndex { indices }
+    }
+}
+
+impl From<TakeIndex> for Vec<usize> {
+    fn from(index: TakeIndex) -> Self {
+        index.indices
+    }
+}
+
+/// Shuffle the Rows of a Record Batch from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use arrow_array::{ArrayRef, Int64Array, RecordBatch};
+/// use predictive_shuffle::table::shuffle_record_batch_from_seed_ref;
+/// use std::sync::Arc;
+///
+/// let ids: ArrayRef = Arc::new(Int64Array::from(vec![0, 1, 2, 3]));
+/// let batch = RecordBatch::try_from_iter([("id", ids)]).unwrap();
+/// let shuffled_batch = shuffle_record_batch_from_seed_ref(&batch, b"seed phrase").unwrap();
+/// ```
+#[cfg(all(feature = "arrow", feature = "fastrand"))]
+pub fn shuffle_record_batch_from_seed_ref(
+    batch: &RecordBatch,
+    seed: impl AsRef<[u8]>,
+) -> Result<RecordBatch, ArrowError> {
+    TakeIndex::modern_from_seed_ref(batch.num_rows(), seed).take_record_batch(batch)
+}
+
+/// Cryptographically Shuffle the Rows of a Record Batch from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use arrow_array::{ArrayRef, Int64Array, RecordBatch};
+/// use predictive_shuffle::table::crypto_shuffle_record_batch_from_seed_ref;
+/// use std::sync::Arc;
+///
+/// let ids: ArrayRef = Arc::new(Int64Array::from(vec![0, 1, 2, 3]));
+/// let batch = RecordBatch::try_from_iter([("id", ids)]).unwrap();
+/// let shuffled_batch = crypto_shuffle_record_batch_from_seed_ref(&batch, b"seed phrase").unwrap();
+/// ```
+#[cfg(all(feature = "arrow", feature = "chacha"))]
+pub fn crypto_shuffle_record_batch_from_seed_ref(
+    batch: &RecordBatch,
+    seed: impl AsRef<[u8]>,
+) -> Result<RecordBatch, ArrowError> {
+    TakeIndex::crypto_modern_from_seed_ref(batch.num_rows(), seed).take_record_batch(batch)
+}
+
+/// Shuffle the Rows of a Data Frame from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use polars::prelude::*;
+/// use predictive_shuffle::table::shuffle_data_frame_from_seed_ref;
+///
+/// let df = DataFrame::new(vec![Column::new("id".into(), [0i64, 1, 2, 3])]).unwrap();
+/// let shuffled_df = shuffle_data_frame_from_seed_ref(&df, b"seed phrase").unwrap();
+/// ```
+#[cfg(all(feature = "polars", feature = "fastrand"))]
+pub fn shuffle_data_frame_from_seed_ref(
+    df: &DataFrame,
+    seed: impl AsRef<[u8]>,
+) -> PolarsResult<DataFrame> {
+    TakeIndex::modern_from_seed_ref(df.height(), seed).take_data_frame(df)
+}
+
+/// Cryptographically Shuffle the Rows of a Data Frame from Seed
+///
+/// ### Example
+///
+/// Basic usage:
+/// ```rust
+/// use polars::prelude::*;
+/// use predictive_shuffle::table::crypto_shuffle_data_frame_from_seed_ref;
+///
+/// let df = DataFrame::new(vec![Column::new("id".into(), [0i64, 1, 2, 3])]).unwrap();
+/// let shuffled_df = crypto_shuffle_data_frame_from_seed_ref(&df, b"seed phrase").unwrap();
+/// ```
+#[cfg(all(feature = "polars", feature = "chacha"))]
+pub fn crypto_shuffle_data_frame_from_seed_ref(
+    df: &DataFrame,
+    seed: impl AsRef<[u8]>,
+) -> PolarsResult<DataFrame> {
+    TakeIndex::crypto_modern_from_seed_ref(df.height(), seed).take_data_frame(df)
+}
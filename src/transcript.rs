@@ -0,0 +1,433 @@
+//! # transcript
+//! Verifiable commit-reveal transcripts for public draws.
+//!
+//! Before entries close, the operator publishes a [`Commitment`] to a secret seed. Once the
+//! entrant list is final, the operator reveals the seed in a [`Transcript`], which anyone can
+//! check with [`verify_transcript`] by recomputing the draw over the published entrant list.
+//!
+//! The draw is [`Shuffler::predict`] over a [`Shuffler<CryptoRng>`] seeded with
+//! [`Shuffler::from_seed`] from the revealed seed. It is a predictive Durstenfeld shuffle drawing
+//! every swap from `0..=i`, so an entrant may keep their own slot and all `n!` orders are
+//! reachable. Verifiers who do not want to trust [`verify_transcript`] can recompute the result
+//! with those public functions:
+//!
+//! ```rust
+//! use predictive_shuffle::transcript::{Reveal, Transcript};
+//! use predictive_shuffle::{CryptoRng, Shuffler};
+//!
+//! let reveal = Reveal::new(b"secret seed".to_vec());
+//! let entrants = vec!["alice", "bob", "carol", "dave"];
+//! let transcript = Transcript::new(&reveal, &entrants, vec![0, 1]).unwrap();
+//!
+//! let mut shuffler = Shuffler::<CryptoRng>::from_seed(&transcript.reveal.seed);
+//! let drawn = shuffler.predict(transcript.size, &transcript.positions);
+//! let result: Vec<(usize, usize)> = transcript.positions.iter().copied().zip(drawn).collect();
+//! assert_eq!(result, transcript.result);
+//! ```
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! use predictive_shuffle::transcript::{verify_transcript, Reveal, Transcript};
+//!
+//! // before entries close
+//! let reveal = Reveal::new(b"secret seed".to_vec());
+//! let commitment = reveal.commitment();
+//! println!("commitment: {}", commitment);
+//!
+//! // after entries close
+//! let entrants = vec!["alice", "bob", "carol", "dave"];
+//! let transcript = Transcript::new(&reveal, &entrants, vec![0, 1]).unwrap();
+//! let published = transcript.to_string();
+//!
+//! // anyone can verify
+//! let transcript: Transcript = published.parse().unwrap();
+//! assert_eq!(transcript.commitment, commitment);
+//! assert!(verify_transcript(&transcript, &entrants).is_ok());
+//! ```
+
+#[cfg(feature = "beacon")]
+use crate::beacon::BeaconRound;
+use crate::shuffler::Shuffler;
+use crate::{check_positions, hash, hex, rng, CryptoRng};
+#[cfg(entropy)]
+use rand::RngCore;
+use std::fmt;
+use std::str::FromStr;
+
+/// Identifier of the shuffle algorithm transcripts are produced with
+///
+/// `v1` drew swaps from `0..i` as [`crate::gen_crypto_predictive_shuffle_from_seed_ref`] does,
+/// which never leaves an entrant in place, and `v2` keyed ChaCha20 with only 32 bits of the seed
+/// digest. Neither is accepted any more.
+pub const ALGORITHM_VERSION: &str = "crypto_predictive_draw_from_seed/v3";
+
+/// Length of the random nonce mixed into a commitment
+pub const NONCE_LEN: usize = 32;
+
+const HEADER: &str = "predictive_shuffle transcript";
+
+/// Errors returned when building, parsing or verifying a transcript
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptError {
+    /// The transcript was produced by an algorithm this version cannot recompute
+    UnsupportedVersion(String),
+    /// The revealed seed does not match the published commitment
+    CommitmentMismatch,
+    /// The entrant list does not match the hash in the transcript
+    EntrantsMismatch,
+    /// A position is not below the number of entrants, or is repeated
+    InvalidPosition(usize),
+    /// Recomputing the shuffle gives a different result
+    ResultMismatch,
+    /// The beacon round is invalid or does not match the revealed seed
+    BeaconMismatch,
+    /// The transcript text is malformed
+    Parse(String),
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranscriptError::UnsupportedVersion(version) => {
+                write!(f, "unsupported algorithm version {}", version)
+            }
+            TranscriptError::CommitmentMismatch => {
+                write!(f, "revealed seed does not match the commitment")
+            }
+            TranscriptError::EntrantsMismatch => {
+                write!(f, "entrant list does not match the transcript")
+            }
+            TranscriptError::InvalidPosition(position) => {
+                write!(f, "position {} is out of range or repeated", position)
+            }
+            TranscriptError::ResultMismatch => {
+                write!(f, "recomputed shuffle does not match the result")
+            }
+            TranscriptError::BeaconMismatch => {
+                write!(f, "beacon round does not match the revealed seed")
+            }
+            TranscriptError::Parse(reason) => write!(f, "malformed transcript: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for TranscriptError {}
+
+/// Published SHA-256 commitment to a seed and nonce
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Commitment {
+    digest: Vec<u8>,
+}
+
+impl Commitment {
+    /// Hex encoded SHA-256 digest, as returned by [`crate::hash`]
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    /// Check that a reveal opens this commitment
+    pub fn verify(&self, reveal: &Reveal) -> bool {
+        reveal.commitment() == *self
+    }
+}
+
+impl fmt::Display for Commitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.digest))
+    }
+}
+
+impl FromStr for Commitment {
+    type Err = TranscriptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digest = parse_digest(s)?;
+        Ok(Commitment { digest })
+    }
+}
+
+/// Secret seed and nonce, kept private until the draw is made
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reveal {
+    /// Seed passed to the shuffle
+    pub seed: Vec<u8>,
+    /// Random nonce that keeps low-entropy seeds from being guessed from the commitment
+    pub nonce: Vec<u8>,
+}
+
+impl Reveal {
+    /// Create a reveal for `seed` with a fresh random nonce from ['rand_chacha::ChaCha20Rng']
+    #[cfg(entropy)]
+    pub fn new(seed: Vec<u8>) -> Self {
+        let mut rng = CryptoRng::from_entropy();
+        let mut nonce = vec![0; NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+        Reveal { seed, nonce }
+    }
+
+    /// Create a reveal from a seed and a known nonce
+    pub fn with_nonce(seed: Vec<u8>, nonce: Vec<u8>) -> Self {
+        Reveal { seed, nonce }
+    }
+
+    /// Commitment to publish before the draw: SHA-256 of the nonce length, nonce and seed
+    pub fn commitment(&self) -> Commitment {
+        let mut preimage = (self.nonce.len() as u64).to_be_bytes().to_vec();
+        preimage.extend_from_slice(&self.nonce);
+        preimage.extend_from_slice(&self.seed);
+        let digest = hash(&preimage);
+        rng::wipe(&mut preimage);
+        Commitment { digest }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Reveal {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.nonce.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Reveal {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Reveal {}
+
+/// Hash of an entrant list, with every entrant prefixed by its length so entries cannot be
+/// merged or split without changing the hash
+pub fn hash_entrants<E: AsRef<[u8]>>(entrants: &[E]) -> Vec<u8> {
+    let mut preimage = (entrants.len() as u64).to_be_bytes().to_vec();
+    for entrant in entrants {
+        let entrant = entrant.as_ref();
+        preimage.extend_from_slice(&(entrant.len() as u64).to_be_bytes());
+        preimage.extend_from_slice(entrant);
+    }
+    hash(&preimage)
+}
+
+/// Complete public record of a draw
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    /// Algorithm the result was computed with, see [`ALGORITHM_VERSION`]
+    pub version: String,
+    /// Commitment published before entries closed
+    pub commitment: Commitment,
+    /// Hash of the entrant list, see [`hash_entrants`]
+    pub entrants_hash: Vec<u8>,
+    /// Number of entrants
+    pub size: usize,
+    /// Entrant positions whose shuffled positions were drawn
+    pub positions: Vec<usize>,
+    /// Revealed seed and nonce
+    pub reveal: Reveal,
+    /// Shuffled position of every drawn entrant, ordered by entrant position
+    pub result: Vec<(usize, usize)>,
+    /// Beacon round the seed was derived from, for beacon-seeded draws
+    #[cfg(feature = "beacon")]
+    pub beacon: Option<BeaconRound>,
+}
+
+impl Transcript {
+    /// Run the draw over `entrants` and record it
+    pub fn new<E: AsRef<[u8]>>(
+        reveal: &Reveal,
+        entrants: &[E],
+        positions: Vec<usize>,
+    ) -> Result<Self, TranscriptError> {
+        let size = entrants.len();
+        check_positions(size, &positions).map_err(TranscriptError::InvalidPosition)?;
+
+        Ok(Transcript {
+            version: ALGORITHM_VERSION.to_string(),
+            commitment: reveal.commitment(),
+            entrants_hash: hash_entrants(entrants),
+            size,
+            result: draw(size, &positions, &reveal.seed),
+            positions,
+            reveal: reveal.clone(),
+            #[cfg(feature = "beacon")]
+            beacon: None,
+        })
+    }
+
+    /// Run the draw over `entrants` with the seed of a beacon round, see
+    /// [`BeaconRound::seed`]
+    ///
+    /// The reveal carries the derived seed with an empty nonce, since the beacon round itself is
+    /// the public commitment. [`verify_transcript`] only checks the round's randomness, see
+    /// [`crate::beacon`], so verifiers must authenticate the round from its published record,
+    /// with [`BeaconRound::from_nist_xml_verified`] for NIST or drand's client for drand.
+    #[cfg(feature = "beacon")]
+    pub fn from_beacon<E: AsRef<[u8]>>(
+        beacon: &BeaconRound,
+        entrants: &[E],
+        positions: Vec<usize>,
+    ) -> Result<Self, TranscriptError> {
+        let reveal = Reveal::with_nonce(beacon.seed().into_vec(), vec![]);
+        let mut transcript = Transcript::new(&reveal, entrants, positions)?;
+        transcript.beacon = Some(beacon.clone());
+        Ok(transcript)
+    }
+}
+
+/// Verify a transcript against the published entrant list
+pub fn verify_transcript<E: AsRef<[u8]>>(
+    transcript: &Transcript,
+    entrants: &[E],
+) -> Result<(), TranscriptError> {
+    if transcript.version != ALGORITHM_VERSION {
+        return Err(TranscriptError::UnsupportedVersion(
+            transcript.version.clone(),
+        ));
+    }
+    if !transcript.commitment.verify(&transcript.reveal) {
+        return Err(TranscriptError::CommitmentMismatch);
+    }
+    #[cfg(feature = "beacon")]
+    if let Some(beacon) = &transcript.beacon {
+        if beacon.check_randomness().is_err() || beacon.seed().as_bytes() != transcript.reveal.seed
+        {
+            return Err(TranscriptError::BeaconMismatch);
+        }
+    }
+    if entrants.len() != transcript.size || hash_entrants(entrants) != transcript.entrants_hash {
+        return Err(TranscriptError::EntrantsMismatch);
+    }
+    check_positions(transcript.size, &transcript.positions)
+        .map_err(TranscriptError::InvalidPosition)?;
+
+    let result = draw(
+        transcript.size,
+        &transcript.positions,
+        &transcript.reveal.seed,
+    );
+    if result != transcript.result {
+        return Err(TranscriptError::ResultMismatch);
+    }
+    Ok(())
+}
+
+/// Shuffled position of every entrant in `positions`, ordered by entrant position
+fn draw(size: usize, positions: &[usize], seed: &[u8]) -> Vec<(usize, usize)> {
+    let drawn = Shuffler::<CryptoRng>::from_seed(seed).predict(size, positions);
+    let mut result: Vec<(usize, usize)> = positions.iter().copied().zip(drawn).collect();
+    result.sort_unstable();
+    result
+}
+
+fn parse_digest(s: &str) -> Result<Vec<u8>, TranscriptError> {
+    let valid = s.len() == 64 && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+    if !valid {
+        return Err(TranscriptError::Parse(format!("invalid digest {:?}", s)));
+    }
+    Ok(s.as_bytes().to_vec())
+}
+
+impl fmt::Display for Transcript {
+    /// Line based `key: value` encoding, stable byte-for-byte for a given transcript
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let positions: Vec<String> = self.positions.iter().map(|p| p.to_string()).collect();
+        let result: Vec<String> = self
+            .result
+            .iter()
+            .map(|(from, to)| format!("{}:{}", from, to))
+            .collect();
+
+        writeln!(f, "{}", HEADER)?;
+        writeln!(f, "version: {}", self.version)?;
+        writeln!(f, "commitment: {}", self.commitment)?;
+        writeln!(
+            f,
+            "entrants: {}",
+            String::from_utf8_lossy(&self.entrants_hash)
+        )?;
+        writeln!(f, "size: {}", self.size)?;
+        writeln!(f, "positions: {}", positions.join(","))?;
+        writeln!(f, "seed: {}", hex::encode(&self.reveal.seed))?;
+        writeln!(f, "nonce: {}", hex::encode(&self.reveal.nonce))?;
+        writeln!(f, "result: {}", result.join(","))?;
+        #[cfg(feature = "beacon")]
+        if let Some(beacon) = &self.beacon {
+            writeln!(f, "beacon: {}", beacon)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Transcript {
+    type Err = TranscriptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(TranscriptError::Parse("missing header".to_string()));
+        }
+
+        let mut field = |key: &str| -> Result<&str, TranscriptError> {
+            lines
+                .next()
+                .and_then(|line| line.strip_prefix(key))
+                .and_then(|line| line.strip_prefix(':'))
+                .map(|value| value.trim())
+                .ok_or_else(|| TranscriptError::Parse(format!("missing {}", key)))
+        };
+        let invalid = |key: &str| TranscriptError::Parse(format!("invalid {}", key));
+
+        let version = field("version")?.to_string();
+        let commitment = field("commitment")?.parse()?;
+        let entrants_hash = parse_digest(field("entrants")?)?;
+        let size = field("size")?.parse().map_err(|_| invalid("size"))?;
+        let positions = split_list(field("positions")?)
+            .map(|p| p.parse().map_err(|_| invalid("positions")))
+            .collect::<Result<_, _>>()?;
+        let seed = hex::decode(field("seed")?).ok_or_else(|| invalid("seed"))?;
+        let nonce = hex::decode(field("nonce")?).ok_or_else(|| invalid("nonce"))?;
+        let result = split_list(field("result")?)
+            .map(|pair| {
+                let (from, to) = pair.split_once(':').ok_or_else(|| invalid("result"))?;
+                let from = from.parse().map_err(|_| invalid("result"))?;
+                let to = to.parse().map_err(|_| invalid("result"))?;
+                Ok((from, to))
+            })
+            .collect::<Result<_, TranscriptError>>()?;
+
+        #[cfg(feature = "beacon")]
+        let beacon = match lines.next() {
+            Some(line) => {
+                let beacon = line
+                    .strip_prefix("beacon:")
+                    .ok_or_else(|| TranscriptError::Parse(format!("unexpected {:?}", line)))?;
+                Some(beacon.parse().map_err(|_| invalid("beacon"))?)
+            }
+            None => None,
+        };
+        #[cfg(not(feature = "beacon"))]
+        if let Some(line) = lines.next() {
+            return Err(TranscriptError::Parse(format!("unexpected {:?}", line)));
+        }
+
+        Ok(Transcript {
+            version,
+            commitment,
+            entrants_hash,
+            size,
+            positions,
+            reveal: Reveal { seed, nonce },
+            result,
+            #[cfg(feature = "beacon")]
+            beacon,
+        })
+    }
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').filter(|item| !item.is_empty())
+}
@@ -0,0 +1,124 @@
+//! # vrf
+//! Verifiable shuffle seeds from a verifiable random function (sr25519 VRF, via `schnorrkel`).
+//!
+//! The operator evaluates the VRF on a public input with their secret key, producing a [`Seed`]
+//! and a [`VrfProof`]. Anyone holding the operator's public key can check the proof with
+//! [`vrf_verify`], which recovers the same seed, so the seed could not have been picked after the
+//! fact. Enabled with the `vrf` feature.
+//!
+//! The seed is fed into the `crypto_*_from_seed` functions, such as
+//! [`crate::gen_crypto_predictive_shuffle_from_seed_ref`], which key ChaCha20 with its whole
+//! digest, so verifiers recompute the draw with the same public functions.
+//!
+//! ### Example
+//!
+//! Basic usage:
+//! ```rust
+//! use predictive_shuffle::gen_crypto_predictive_shuffle_from_seed_ref;
+//! use predictive_shuffle::vrf::{vrf_prove, vrf_verify, Keypair};
+//!
+//! let keypair = Keypair::generate();
+//! let input = b"giveaway 2026-10-18";
+//! let (seed, proof) = vrf_prove(&keypair, input);
+//! let new_positions = gen_crypto_predictive_shuffle_from_seed_ref(100, [1, 5], &seed);
+//!
+//! let verified = vrf_verify(&keypair.public, input, &proof).unwrap();
+//! assert_eq!(
+//!     gen_crypto_predictive_shuffle_from_seed_ref(100, [1, 5], &verified),
+//!     new_positions
+//! );
+//! ```
+
+use crate::Seed;
+use schnorrkel::vrf::{VRFPreOut, VRFProof};
+use schnorrkel::{signing_context, SignatureError};
+use std::fmt;
+
+pub use schnorrkel::{Keypair, MiniSecretKey, PublicKey};
+
+/// Signing context binding VRF evaluations to this crate
+const CONTEXT: &[u8] = b"predictive_shuffle vrf";
+
+/// Label the shuffle seed is extracted from the VRF output with
+const SEED_LABEL: &[u8] = b"predictive_shuffle seed";
+
+/// Length of a serialised [`VrfProof`]
+pub const VRF_PROOF_LEN: usize = 96;
+
+/// Errors returned when checking a VRF proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VrfError {
+    /// The proof bytes could not be decoded
+    Malformed,
+    /// The proof does not match the public key and input
+    InvalidProof,
+}
+
+impl fmt::Display for VrfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VrfError::Malformed => write!(f, "malformed VRF proof"),
+            VrfError::InvalidProof => {
+                write!(f, "VRF proof does not match the public key and input")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VrfError {}
+
+impl From<SignatureError> for VrfError {
+    fn from(_: SignatureError) -> Self {
+        VrfError::InvalidProof
+    }
+}
+
+/// VRF output and proof, published alongside the draw
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VrfProof {
+    output: VRFPreOut,
+    proof: VRFProof,
+}
+
+impl VrfProof {
+    /// VRF output followed by the proof
+    pub fn to_bytes(&self) -> [u8; VRF_PROOF_LEN] {
+        let mut bytes = [0; VRF_PROOF_LEN];
+        bytes[..32].copy_from_slice(&self.output.to_bytes());
+        bytes[32..].copy_from_slice(&self.proof.to_bytes());
+        bytes
+    }
+
+    /// Decode a proof produced by [`VrfProof::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VrfError> {
+        if bytes.len() != VRF_PROOF_LEN {
+            return Err(VrfError::Malformed);
+        }
+        Ok(VrfProof {
+            output: VRFPreOut::from_bytes(&bytes[..32]).map_err(|_| VrfError::Malformed)?,
+            proof: VRFProof::from_bytes(&bytes[32..]).map_err(|_| VrfError::Malformed)?,
+        })
+    }
+}
+
+/// Evaluate the VRF on `input`, returning the shuffle seed and the proof to publish
+pub fn vrf_prove(keypair: &Keypair, input: &[u8]) -> (Seed, VrfProof) {
+    let context = signing_context(CONTEXT);
+    let (inout, proof, _) = keypair.vrf_sign(context.bytes(input));
+
+    let seed: [u8; 32] = inout.make_bytes(SEED_LABEL);
+    let proof = VrfProof {
+        output: inout.to_preout(),
+        proof,
+    };
+    (Seed::new(seed.to_vec()), proof)
+}
+
+/// Check a proof against the operator's public key and `input`, returning the shuffle seed
+pub fn vrf_verify(public: &PublicKey, input: &[u8], proof: &VrfProof) -> Result<Seed, VrfError> {
+    let context = signing_context(CONTEXT);
+    let (inout, _) = public.vrf_verify(context.bytes(input), &proof.output, &proof.proof)?;
+
+    let seed: [u8; 32] = inout.make_bytes(SEED_LABEL);
+    Ok(Seed::new(seed.to_vec()))
+}
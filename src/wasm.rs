@@ -0,0 +1,209 @@
+//! # wasm
+//! [`wasm_bindgen`] exports of the seeded and predictive shuffles, so that draws can be checked
+//! client-side by re-running them in the browser.
+//!
+//! Permutations come back as a `Uint32Array` of `size` items, and predictions come back aligned
+//! with `positions`, as in [`crate::ordered`]. Seeds are passed as a `Uint8Array` of the raw seed
+//! bytes, so they give the same results as the Rust functions of the same name on any target.
+//! Positions that are out of range or repeated and batch sizes that are zero or larger than the
+//! size throw an `Error` instead of panicking, by the same rules as the Rust builder and records.
+//!
+//! The `wasm32-unknown-unknown` target has no source of entropy by default, so the functions
+//! that do not take a seed are only exported when the `js` feature is enabled.
+//!
+//! ```text
+//! cargo rustc --lib --crate-type cdylib --release --target wasm32-unknown-unknown --features wasm
+//! wasm-bindgen --target web --out-dir pkg \
+//!     target/wasm32-unknown-unknown/release/predictive_shuffle.wasm
+//! ```
+//!
+//! ```js
+//! import init, { cryptoPredictiveShuffleFromSeed } from "./pkg/predictive_shuffle.js";
+//!
+//! await init();
+//! const seed = new TextEncoder().encode("seed phrase");
+//! const winners = cryptoPredictiveShuffleFromSeed(100, new Uint32Array([1, 5]), seed);
+//! ```
+
+use crate::{constant_time, ordered};
+use wasm_bindgen::prelude::*;
+
+fn check_positions(size: usize, positions: &[u32]) -> Result<Vec<usize>, JsError> {
+    let positions: Vec<usize> = positions
+        .iter()
+        .map(|&position| position as usize)
+        .collect();
+    crate::check_positions(size, &positions).map_err(|position| {
+        JsError::new(&format!(
+            "position {} is out of range or repeated for the size {}",
+            position, size
+        ))
+    })?;
+    Ok(positions)
+}
+
+fn check_batch(size: usize, batch: usize) -> Result<(), JsError> {
+    crate::check_batch(size, batch).map_err(|batch| {
+        JsError::new(&format!(
+            "batch size {} is zero or larger than the size {}",
+            batch, size
+        ))
+    })
+}
+
+fn to_u32(items: Vec<usize>) -> Vec<u32> {
+    items.into_iter().map(|item| item as u32).collect()
+}
+
+/// Run a predictive `*_into` function over checked positions
+fn predict<F: FnOnce(&[usize], &mut [usize])>(
+    size: usize,
+    positions: &[u32],
+    f: F,
+) -> Result<Vec<u32>, JsError> {
+    let positions = check_positions(size, positions)?;
+    let mut out = vec![0; positions.len()];
+    f(&positions, &mut out);
+    Ok(to_u32(out))
+}
+
+/// [`crate::gen_fastrand_shuffle_from_seed_ref`]
+#[wasm_bindgen(js_name = fastrandShuffleFromSeed)]
+pub fn gen_fastrand_shuffle_from_seed(size: usize, seed: &[u8]) -> Vec<u32> {
+    to_u32(crate::gen_fastrand_shuffle_from_seed_ref(size, seed))
+}
+
+/// [`crate::gen_modern_shuffle_from_seed_ref`]
+#[wasm_bindgen(js_name = modernShuffleFromSeed)]
+pub fn gen_modern_shuffle_from_seed(size: usize, seed: &[u8]) -> Vec<u32> {
+    to_u32(crate::gen_modern_shuffle_from_seed_ref(size, seed))
+}
+
+/// [`crate::gen_crypto_modern_shuffle_from_seed_ref`]
+#[wasm_bindgen(js_name = cryptoModernShuffleFromSeed)]
+pub fn gen_crypto_modern_shuffle_from_seed(size: usize, seed: &[u8]) -> Vec<u32> {
+    to_u32(crate::gen_crypto_modern_shuffle_from_seed_ref(size, seed))
+}
+
+/// [`crate::constant_time::gen_constant_time_shuffle_from_seed`]
+#[wasm_bindgen(js_name = constantTimeShuffleFromSeed)]
+pub fn gen_constant_time_shuffle_from_seed(size: usize, seed: &[u8]) -> Vec<u32> {
+    to_u32(constant_time::gen_constant_time_shuffle_from_seed(
+        size, seed,
+    ))
+}
+
+/// [`crate::ordered::gen_predictive_shuffle_from_seed_into`]
+#[wasm_bindgen(js_name = predictiveShuffleFromSeed)]
+pub fn gen_predictive_shuffle_from_seed(
+    size: usize,
+    positions: &[u32],
+    seed: &[u8],
+) -> Result<Vec<u32>, JsError> {
+    predict(size, positions, |positions, out| {
+        ordered::gen_predictive_shuffle_from_seed_into(size, positions, seed, out)
+    })
+}
+
+/// [`crate::ordered::gen_crypto_predictive_shuffle_from_seed_into`]
+#[wasm_bindgen(js_name = cryptoPredictiveShuffleFromSeed)]
+pub fn gen_crypto_predictive_shuffle_from_seed(
+    size: usize,
+    positions: &[u32],
+    seed: &[u8],
+) -> Result<Vec<u32>, JsError> {
+    predict(size, positions, |positions, out| {
+        ordered::gen_crypto_predictive_shuffle_from_seed_into(size, positions, seed, out)
+    })
+}
+
+/// [`crate::ordered::gen_batch_predictive_shuffle_from_seed_into`]
+#[wasm_bindgen(js_name = batchPredictiveShuffleFromSeed)]
+pub fn gen_batch_predictive_shuffle_from_seed(
+    size: usize,
+    batch: usize,
+    positions: &[u32],
+    seed: &[u8],
+) -> Result<Vec<u32>, JsError> {
+    check_batch(size, batch)?;
+    predict(size, positions, |positions, out| {
+        ordered::gen_batch_predictive_shuffle_from_seed_into(size, batch, positions, seed, out)
+    })
+}
+
+/// [`crate::ordered::gen_crypto_batch_predictive_shuffle_from_seed_into`]
+#[wasm_bindgen(js_name = cryptoBatchPredictiveShuffleFromSeed)]
+pub fn gen_crypto_batch_predictive_shuffle_from_seed(
+    size: usize,
+    batch: usize,
+    positions: &[u32],
+    seed: &[u8],
+) -> Result<Vec<u32>, JsError> {
+    check_batch(size, batch)?;
+    predict(size, positions, |positions, out| {
+        ordered::gen_crypto_batch_predictive_shuffle_from_seed_into(
+            size, batch, positions, seed, out,
+        )
+    })
+}
+
+/// [`crate::constant_time::gen_constant_time_predictive_shuffle_from_seed`], which does not
+/// check positions so that its running time does not depend on them
+#[wasm_bindgen(js_name = constantTimePredictiveShuffleFromSeed)]
+pub fn gen_constant_time_predictive_shuffle_from_seed(
+    size: usize,
+    positions: &[u32],
+    seed: &[u8],
+) -> Vec<u32> {
+    let positions: Vec<usize> = positions
+        .iter()
+        .map(|&position| position as usize)
+        .collect();
+    to_u32(constant_time::gen_constant_time_predictive_shuffle_from_seed(size, &positions, seed))
+}
+
+/// [`crate::ordered::gen_predictive_shuffle_into`]
+#[cfg(entropy)]
+#[wasm_bindgen(js_name = predictiveShuffle)]
+pub fn gen_predictive_shuffle(size: usize, positions: &[u32]) -> Result<Vec<u32>, JsError> {
+    predict(size, positions, |positions, out| {
+        ordered::gen_predictive_shuffle_into(size, positions, out)
+    })
+}
+
+/// [`crate::ordered::gen_crypto_predictive_shuffle_into`]
+#[cfg(entropy)]
+#[wasm_bindgen(js_name = cryptoPredictiveShuffle)]
+pub fn gen_crypto_predictive_shuffle(size: usize, positions: &[u32]) -> Result<Vec<u32>, JsError> {
+    predict(size, positions, |positions, out| {
+        ordered::gen_crypto_predictive_shuffle_into(size, positions, out)
+    })
+}
+
+/// [`crate::ordered::gen_batch_predictive_shuffle_into`]
+#[cfg(entropy)]
+#[wasm_bindgen(js_name = batchPredictiveShuffle)]
+pub fn gen_batch_predictive_shuffle(
+    size: usize,
+    batch: usize,
+    positions: &[u32],
+) -> Result<Vec<u32>, JsError> {
+    check_batch(size, batch)?;
+    predict(size, positions, |positions, out| {
+        ordered::gen_batch_predictive_shuffle_into(size, batch, positions, out)
+    })
+}
+
+/// [`crate::ordered::gen_crypto_batch_predictive_shuffle_into`]
+#[cfg(entropy)]
+#[wasm_bindgen(js_name = cryptoBatchPredictiveShuffle)]
+pub fn gen_crypto_batch_predictive_shuffle(
+    size: usize,
+    batch: usize,
+    positions: &[u32],
+) -> Result<Vec<u32>, JsError> {
+    check_batch(size, batch)?;
+    predict(size, positions, |positions, out| {
+        ordered::gen_crypto_batch_predictive_shuffle_into(size, batch, positions, out)
+    })
+}
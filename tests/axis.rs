@@ -0,0 +1,103 @@
+#[cfg(all(test, feature = "ndarray", feature = "chacha", feature = "fastrand"))]
+mod tests {
+    use ndarray::{array, Array2, Array3, Axis};
+    use predictive_shuffle::axis::*;
+    use predictive_shuffle::co_shuffle::CoShuffleError;
+    use predictive_shuffle::*;
+
+    #[test]
+    fn shuffle_axis_matches_vec_test() {
+        let seed = b"seed phrase";
+        let array = Array2::from_shape_fn((10, 3), |(row, column)| row * 3 + column);
+
+        let rows = array.modern_shuffle_axis_from_seed_ref(Axis(0), seed);
+        let order = gen_modern_shuffle_from_seed_ref(10, seed);
+        for (row, &original) in rows.rows().into_iter().zip(&order) {
+            assert_eq!(row, array.row(original));
+        }
+
+        let columns = array.crypto_modern_shuffle_axis_from_seed_ref(Axis(1), seed);
+        let order = gen_crypto_modern_shuffle_from_seed_ref(3, seed);
+        for (column, &original) in columns.columns().into_iter().zip(&order) {
+            assert_eq!(column, array.column(original));
+        }
+    }
+
+    #[test]
+    fn predictive_shuffle_axis_test() {
+        let seed = b"seed phrase";
+        let array = Array3::from_shape_fn((4, 100, 2), |(_, column, _)| column);
+
+        // every prediction points at the subview the shuffle with the same seed moves there
+        let shuffled = array.modern_shuffle_axis_from_seed_ref(Axis(1), seed);
+        let new_positions = array.predictive_shuffle_axis_from_seed_ref(Axis(1), 0..100, seed);
+        for (position, new_position) in new_positions {
+            assert_eq!(
+                shuffled.index_axis(Axis(1), new_position),
+                array.index_axis(Axis(1), position)
+            );
+        }
+        let shuffled = array.crypto_modern_shuffle_axis_from_seed_ref(Axis(1), seed);
+        let new_positions =
+            array.crypto_predictive_shuffle_axis_from_seed_ref(Axis(1), 0..100, seed);
+        for (position, new_position) in new_positions {
+            assert_eq!(
+                shuffled.index_axis(Axis(1), new_position),
+                array.index_axis(Axis(1), position)
+            );
+        }
+
+        // the reported case: row 1 of a 100-row array under "seed phrase"
+        let array = Array2::from_shape_fn((100, 1), |(row, _)| row);
+        let new_positions = array.predictive_shuffle_axis_from_seed_ref(Axis(0), [1], seed);
+        let shuffled = array.modern_shuffle_axis_from_seed_ref(Axis(0), seed);
+        assert_eq!(shuffled[[new_positions[&1], 0]], 1);
+    }
+
+    #[test]
+    fn co_shuffle_axis_test() {
+        let seed = b"seed phrase";
+        let mut features = Array2::from_shape_fn((50, 4), |(row, _)| row as f32);
+        let mut labels = ndarray::Array1::from_iter(0..50u64);
+        crypto_co_shuffle_axis_from_seed_ref(&mut [&mut features, &mut labels], Axis(0), seed)
+            .unwrap();
+
+        let order = gen_crypto_modern_shuffle_from_seed_ref(50, seed);
+        assert_eq!(
+            labels.to_vec(),
+            order.iter().map(|&i| i as u64).collect::<Vec<_>>()
+        );
+        for (row, &label) in features.rows().into_iter().zip(&labels) {
+            assert!(row.iter().all(|&value| value == label as f32));
+        }
+
+        let mut features = Array2::from_shape_fn((50, 4), |(row, _)| row as f32);
+        let mut labels = ndarray::Array1::from_iter(0..50u64);
+        co_shuffle_axis(&mut [&mut features, &mut labels], Axis(0)).unwrap();
+        for (row, &label) in features.rows().into_iter().zip(&labels) {
+            assert!(row.iter().all(|&value| value == label as f32));
+        }
+    }
+
+    #[test]
+    fn co_shuffle_axis_error_test() {
+        let seed = b"seed phrase";
+        let mut features = array![[0, 1], [2, 3], [4, 5]];
+        let mut labels = array![0, 1];
+        assert_eq!(
+            co_shuffle_axis_from_seed_ref(&mut [&mut features, &mut labels], Axis(0), seed),
+            Err(CoShuffleError::LengthMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            co_shuffle_axis_from_seed_ref(&mut [&mut features, &mut labels], Axis(1), seed),
+            Err(CoShuffleError::AxisOutOfBounds(1))
+        );
+
+        // nothing is shuffled on error
+        assert_eq!(features, array![[0, 1], [2, 3], [4, 5]]);
+        assert_eq!(labels, array![0, 1]);
+    }
+}
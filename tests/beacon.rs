@@ -0,0 +1,128 @@
+#[cfg(all(test, feature = "beacon", feature = "chacha"))]
+mod tests {
+    use predictive_shuffle::beacon::{BeaconError, BeaconRound, BeaconSource, NistKey};
+    use predictive_shuffle::transcript::{verify_transcript, Transcript, TranscriptError};
+
+    const DRAND: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/drand_round.json"
+    );
+    const NIST: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/nist_record.xml"
+    );
+    const NIST_SIGNED: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/nist_signed_record.xml"
+    );
+    const NIST_MODULUS: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/nist_signing_key.modulus"
+    );
+
+    fn nist_key() -> NistKey {
+        let modulus = std::fs::read_to_string(NIST_MODULUS).unwrap();
+        NistKey::from_hex(modulus.trim(), "010001").unwrap()
+    }
+
+    #[test]
+    fn drand_fixture_test() {
+        let round = BeaconRound::from_file(DRAND).unwrap();
+        assert_eq!(round.source, BeaconSource::Drand);
+        assert_eq!(round.round, 1337);
+        assert_eq!(round.randomness.len(), 32);
+        assert_eq!(round.to_string().parse::<BeaconRound>().unwrap(), round);
+    }
+
+    #[test]
+    fn nist_fixture_test() {
+        let round = BeaconRound::from_file(NIST).unwrap();
+        assert_eq!(round.source, BeaconSource::Nist);
+        assert_eq!(round.round, 1447873020);
+        assert_eq!(round.randomness.len(), 64);
+        assert_ne!(round.seed(), BeaconRound::from_file(DRAND).unwrap().seed());
+    }
+
+    #[test]
+    fn drand_json_escape_test() {
+        let json = std::fs::read_to_string(DRAND).unwrap();
+        let escaped = json.replacen("\"randomness\": \"44", "\"randomness\": \"\\u00344", 1);
+        assert_ne!(escaped, json);
+        assert_eq!(
+            BeaconRound::from_drand_json(&escaped).unwrap(),
+            BeaconRound::from_file(DRAND).unwrap()
+        );
+        assert!(matches!(
+            BeaconRound::from_drand_json("{\"round\": \"1337\"}"),
+            Err(BeaconError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn nist_signature_test() {
+        let key = nist_key();
+        let round = BeaconRound::from_nist_file_verified(NIST_SIGNED, &key).unwrap();
+        assert_eq!(round, BeaconRound::from_file(NIST_SIGNED).unwrap());
+        assert_eq!(round.round, 1700000040);
+
+        // a record signed by another key
+        assert!(matches!(
+            BeaconRound::from_nist_file_verified(NIST, &key),
+            Err(BeaconError::Signature)
+        ));
+
+        // a signed field changed after signing
+        let xml = std::fs::read_to_string(NIST_SIGNED).unwrap();
+        let tampered = xml.replace("<statusCode>0<", "<statusCode>1<");
+        assert!(matches!(
+            BeaconRound::from_nist_xml_verified(&tampered, &key),
+            Err(BeaconError::Signature)
+        ));
+    }
+
+    #[test]
+    fn seed_is_bound_to_round_test() {
+        let round = BeaconRound::from_file(DRAND).unwrap();
+        let mut replayed = round.clone();
+        replayed.round += 1;
+        assert!(replayed.check_randomness().is_ok());
+        assert_ne!(round.seed(), replayed.seed());
+    }
+
+    #[test]
+    fn tampered_fixture_test() {
+        let json = std::fs::read_to_string(DRAND).unwrap();
+        let tampered = json.replacen("\"randomness\": \"44", "\"randomness\": \"45", 1);
+        assert!(matches!(
+            BeaconRound::from_drand_json(&tampered),
+            Err(BeaconError::Verification)
+        ));
+        assert!(matches!(
+            BeaconRound::from_drand_json("{\"round\": 1}"),
+            Err(BeaconError::Parse(_))
+        ));
+        assert!(matches!(
+            BeaconRound::from_file("missing.json"),
+            Err(BeaconError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn beacon_transcript_test() {
+        let round = BeaconRound::from_file(NIST).unwrap();
+        let entrants: Vec<String> = (0..50).map(|i| format!("entrant {}", i)).collect();
+        let transcript = Transcript::from_beacon(&round, &entrants, vec![0, 1, 2]).unwrap();
+
+        let parsed: Transcript = transcript.to_string().parse().unwrap();
+        assert_eq!(parsed, transcript);
+        assert_eq!(parsed.beacon, Some(round.clone()));
+        assert_eq!(verify_transcript(&parsed, &entrants), Ok(()));
+
+        let mut other_round = parsed;
+        other_round.beacon.as_mut().unwrap().round += 1;
+        assert_eq!(
+            verify_transcript(&other_round, &entrants),
+            Err(TranscriptError::BeaconMismatch)
+        );
+    }
+}
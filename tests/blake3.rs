@@ -0,0 +1,35 @@
+#[cfg(all(test, feature = "blake3"))]
+mod tests {
+    use predictive_shuffle::*;
+
+    #[test]
+    #[cfg(not(feature = "sha2"))]
+    fn blake3_seed_test() {
+        let seed = b"seed phrase";
+        assert_eq!(
+            hash(seed),
+            b"2ae3eb72a2ecb818b4729f074e73d60295c4e6d15492ad54a2a8929fedeb15f2"
+        );
+
+        #[cfg(feature = "fastrand")]
+        assert_eq!(
+            gen_modern_shuffle_from_seed_ref(10, seed),
+            [3, 2, 0, 1, 7, 5, 4, 8, 6, 9]
+        );
+        #[cfg(feature = "chacha")]
+        assert_eq!(
+            gen_crypto_modern_shuffle_from_seed_ref(10, seed),
+            [1, 4, 6, 9, 5, 2, 7, 8, 3, 0]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn blake3_with_sha2_test() {
+        // SHA-256 stays the seed hasher, so enabling `blake3` alongside it changes nothing
+        assert_eq!(
+            hash(b"seed phrase"),
+            b"4856f8b92c5ea96baa3937fcd569a407def699f750fd58302548eecd914901c5"
+        );
+    }
+}
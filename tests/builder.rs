@@ -0,0 +1,176 @@
+#[cfg(all(test, feature = "std", feature = "chacha", feature = "fastrand"))]
+mod tests {
+    use predictive_shuffle::builder::*;
+    use predictive_shuffle::constant_time::*;
+    use predictive_shuffle::ordered::*;
+    use predictive_shuffle::*;
+
+    #[test]
+    fn builder_matches_functions_test() {
+        let seed = b"seed phrase";
+        let positions = [99, 1, 5];
+        let builder = ShuffleBuilder::new(100).seed(seed);
+
+        assert_eq!(
+            builder.permutation(),
+            Ok(gen_modern_shuffle_from_seed_ref(100, seed))
+        );
+        assert_eq!(
+            builder.clone().backend(Backend::ChaCha).permutation(),
+            Ok(gen_crypto_modern_shuffle_from_seed_ref(100, seed))
+        );
+        assert_eq!(
+            builder.clone().backend(Backend::ConstantTime).permutation(),
+            Ok(gen_constant_time_shuffle_from_seed(100, seed))
+        );
+
+        let mut out = [0; 3];
+        gen_predictive_shuffle_from_seed_into(100, &positions, seed, &mut out);
+        let prediction = builder.predict(&positions).unwrap();
+        assert_eq!(prediction.as_aligned(), Some(&out[..]));
+
+        let prediction = builder
+            .clone()
+            .backend(Backend::ChaCha)
+            .batch(4)
+            .format(Format::Ordered)
+            .predict(&positions)
+            .unwrap();
+        assert_eq!(
+            prediction.as_ordered(),
+            Some(&gen_crypto_batch_predictive_shuffle_from_seed_ordered(
+                100, 4, &positions, seed
+            ))
+        );
+
+        let prediction = builder
+            .clone()
+            .backend(Backend::ConstantTime)
+            .format(Format::HashMap)
+            .predict(&positions)
+            .unwrap();
+        let new_positions = gen_constant_time_predictive_shuffle_from_seed(100, &positions, seed);
+        assert_eq!(
+            prediction.as_hash_map(),
+            Some(&positions.iter().copied().zip(new_positions).collect())
+        );
+    }
+
+    #[test]
+    fn builder_version_test() {
+        let positions = [99, 1, 5];
+        for backend in [Backend::Fastrand, Backend::ChaCha] {
+            let builder = ShuffleBuilder::new(100)
+                .backend(backend)
+                .version(Version::V2)
+                .seed(b"seed phrase");
+            let permutation = builder.permutation().unwrap();
+            let prediction = builder.predict(&positions).unwrap();
+            for (&position, &new_position) in positions.iter().zip(prediction.as_aligned().unwrap())
+            {
+                assert_eq!(permutation[new_position], position);
+            }
+        }
+
+        let mut shuffler = Shuffler::<CryptoRng>::from_seed(b"seed phrase");
+        let builder = ShuffleBuilder::new(100)
+            .backend(Backend::ChaCha)
+            .version(Version::V2)
+            .seed(b"seed phrase");
+        assert_eq!(builder.permutation(), Ok(shuffler.gen(100)));
+    }
+
+    #[test]
+    fn builder_shuffle_test() {
+        let seed = b"seed phrase";
+        let data: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+        let shuffled = ShuffleBuilder::from_data(data.clone())
+            .backend(Backend::ChaCha)
+            .seed(seed)
+            .shuffle()
+            .unwrap();
+        assert_eq!(
+            shuffled,
+            data.clone().crypto_modern_shuffle_from_seed_ref(seed)
+        );
+
+        let builder = ShuffleBuilder::new(10).seed(seed);
+        assert_eq!(builder.clone().shuffle(), builder.permutation());
+
+        // `new` only records the size, so any size can be configured
+        assert_eq!(ShuffleBuilder::new(usize::MAX).size(), usize::MAX);
+    }
+
+    #[test]
+    fn builder_entropy_test() {
+        let mut permutation = ShuffleBuilder::new(100)
+            .version(Version::V2)
+            .permutation()
+            .unwrap();
+        permutation.sort();
+        assert_eq!(permutation, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn builder_error_test() {
+        let builder = ShuffleBuilder::new(10).seed(b"seed phrase");
+        assert_eq!(
+            builder.predict(&[1, 1]),
+            Err(BuilderError::InvalidPosition(1))
+        );
+        assert_eq!(
+            builder.predict(&[10]),
+            Err(BuilderError::InvalidPosition(10))
+        );
+        assert_eq!(
+            builder.clone().batch(0).predict(&[1]),
+            Err(BuilderError::InvalidBatch(0))
+        );
+        assert_eq!(
+            builder.clone().batch(11).predict(&[1]),
+            Err(BuilderError::InvalidBatch(11))
+        );
+        assert_eq!(
+            builder.clone().batch(4).permutation(),
+            Err(BuilderError::UnsupportedBatch)
+        );
+        assert_eq!(
+            builder.clone().version(Version::V2).batch(4).predict(&[1]),
+            Err(BuilderError::UnsupportedBatch)
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .backend(Backend::ConstantTime)
+                .batch(4)
+                .predict(&[1]),
+            Err(BuilderError::UnsupportedBatch)
+        );
+        assert_eq!(
+            builder
+                .backend(Backend::ConstantTime)
+                .version(Version::V2)
+                .permutation(),
+            Err(BuilderError::UnsupportedVersion)
+        );
+    }
+
+    #[test]
+    fn builder_large_batch_test() {
+        for backend in [Backend::Fastrand, Backend::ChaCha] {
+            for batch in 6..=10 {
+                let prediction = ShuffleBuilder::new(10)
+                    .backend(backend)
+                    .seed(b"x")
+                    .batch(batch)
+                    .predict(&[1, 5])
+                    .unwrap();
+                let mut to = prediction.as_aligned().unwrap().to_vec();
+                to.sort();
+                to.dedup();
+                assert_eq!(to.len(), 2);
+                assert!(to.iter().all(|&to| to < 10));
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(all(test, feature = "std", feature = "chacha", feature = "fastrand"))]
+mod tests {
+    use predictive_shuffle::canonical::*;
+    use predictive_shuffle::*;
+    use std::collections::HashMap;
+
+    fn map(size: u32) -> HashMap<String, u32> {
+        (0..size).map(|i| (format!("key {:02}", i), i)).collect()
+    }
+
+    #[test]
+    fn canonical_stable_test() {
+        // every map has its own random hasher, so only the canonical order is shared
+        let seed = b"seed phrase";
+        let first = map(50);
+        let keys = shuffle_keys_from_seed(&first, seed);
+        let crypto_keys = crypto_shuffle_keys_from_seed(&first, seed);
+        for _ in 0..10 {
+            let other = map(50);
+            assert_eq!(shuffle_keys_from_seed(&other, seed), keys);
+            assert_eq!(crypto_shuffle_keys_from_seed(&other, seed), crypto_keys);
+        }
+
+        let sorted: Vec<String> = (0..50).map(|i| format!("key {:02}", i)).collect();
+        let expected = sorted.clone().modern_shuffle_from_seed_ref(seed);
+        assert_eq!(keys, expected.iter().collect::<Vec<_>>());
+        let expected = sorted.clone().crypto_modern_shuffle_from_seed_ref(seed);
+        assert_eq!(crypto_keys, expected.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn canonical_seeded_test() {
+        use std::collections::HashSet;
+
+        // pinned, so the order also holds across processes and releases
+        let set: HashSet<u32> = (0..10).collect();
+        assert_eq!(
+            shuffle_set_from_seed(&set, b"seed phrase"),
+            [&4, &9, &6, &1, &2, &3, &0, &7, &5, &8]
+        );
+        assert_eq!(
+            crypto_shuffle_set_from_seed(&set, b"seed phrase"),
+            [&3, &2, &0, &5, &6, &1, &9, &7, &8, &4]
+        );
+    }
+
+    #[test]
+    fn canonical_entries_test() {
+        let seed = b"seed phrase";
+        let map = map(20);
+        let entries = shuffle_entries_from_seed(&map, seed);
+        let keys: Vec<&String> = entries.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, shuffle_keys_from_seed(&map, seed));
+        for (key, value) in &entries {
+            assert_eq!(map[*key], **value);
+        }
+
+        let entries = crypto_shuffle_entries_from_seed(&map, seed);
+        let keys: Vec<&String> = entries.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, crypto_shuffle_keys_from_seed(&map, seed));
+    }
+
+    #[test]
+    fn canonical_by_key_test() {
+        let seed = b"seed phrase";
+        let forwards: Vec<(u32, &str)> = vec![(2, "b"), (0, "z"), (1, "a")];
+        let backwards: Vec<(u32, &str)> = forwards.iter().rev().copied().collect();
+        assert_eq!(
+            shuffle_canonical_by_key_from_seed(forwards.clone(), |item| item.0, seed),
+            shuffle_canonical_by_key_from_seed(backwards.clone(), |item| item.0, seed)
+        );
+        assert_eq!(
+            crypto_shuffle_canonical_by_key_from_seed(forwards, |item| item.1, seed),
+            crypto_shuffle_canonical_by_key_from_seed(backwards, |item| item.1, seed)
+        );
+
+        // items with equal keys are ordered by themselves
+        let forwards: Vec<(u32, &str)> = vec![(0, "b"), (0, "z"), (0, "a"), (1, "c")];
+        let backwards: Vec<(u32, &str)> = forwards.iter().rev().copied().collect();
+        assert_eq!(
+            shuffle_canonical_by_key_from_seed(forwards.clone(), |item| item.0, seed),
+            shuffle_canonical_by_key_from_seed(backwards.clone(), |item| item.0, seed)
+        );
+        assert_eq!(
+            crypto_shuffle_canonical_by_key_from_seed(forwards.clone(), |item| item.0, seed),
+            crypto_shuffle_canonical_by_key_from_seed(backwards, |item| item.0, seed)
+        );
+        assert_eq!(
+            shuffle_canonical_by_key_from_seed(forwards.clone(), |item| item.0, seed),
+            shuffle_canonical_from_seed(forwards, seed)
+        );
+        assert_eq!(
+            shuffle_canonical_from_seed(Vec::<u32>::new(), seed),
+            Vec::<u32>::new()
+        );
+    }
+}
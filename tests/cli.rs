@@ -0,0 +1,252 @@
+#[cfg(all(test, feature = "cli"))]
+mod tests {
+    use predictive_shuffle::record::ShuffleRecord;
+    use predictive_shuffle::transcript::{Reveal, Transcript};
+    use predictive_shuffle::*;
+    use std::io::Write;
+    use std::process::{Command, Output, Stdio};
+
+    fn cli(args: &[&str], stdin: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_predictive-shuffle"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    fn stdout(args: &[&str], stdin: &str) -> String {
+        let output = cli(args, stdin);
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    fn numbers(output: &str) -> Vec<usize> {
+        output.lines().map(|line| line.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn shuffle_test() {
+        let input = "alice\nbob\ncarol\ndave\neve\n";
+        let lines: Vec<String> = input.lines().map(str::to_string).collect();
+
+        let output = stdout(&["shuffle", "--seed", "seed phrase"], input);
+        let expected = lines
+            .clone()
+            .crypto_modern_shuffle_from_seed_ref(b"seed phrase");
+        assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+
+        let output = stdout(
+            &["shuffle", "-", "--seed", "seed phrase", "--algo", "modern"],
+            input,
+        );
+        let expected = lines.clone().modern_shuffle_from_seed_ref(b"seed phrase");
+        assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+
+        let output = stdout(
+            &[
+                "shuffle",
+                "--seed",
+                "seed phrase",
+                "--algo",
+                "fastrand",
+                "--json",
+            ],
+            input,
+        );
+        let mut expected = lines.clone();
+        expected.fastrand_shuffle_from_seed_ref(b"seed phrase");
+        assert_eq!(
+            serde_json::from_str::<Vec<String>>(&output).unwrap(),
+            expected
+        );
+
+        let output = stdout(
+            &[
+                "shuffle",
+                "--seed",
+                "seed phrase",
+                "--algo",
+                "constant-time",
+            ],
+            input,
+        );
+        let permutation = constant_time::gen_constant_time_shuffle_from_seed(5, b"seed phrase");
+        let expected: Vec<&str> = permutation.iter().map(|&i| lines[i].as_str()).collect();
+        assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+
+        let mut output: Vec<&str> = Vec::new();
+        let shuffled = stdout(&["shuffle"], input);
+        output.extend(shuffled.lines());
+        output.sort();
+        assert_eq!(output, input.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn gen_and_predict_test() {
+        let output = stdout(&["gen", "--size", "100", "--seed", "seed phrase"], "");
+        assert_eq!(
+            numbers(&output),
+            gen_crypto_modern_shuffle_from_seed_ref(100, b"seed phrase")
+        );
+
+        let args = [
+            "gen",
+            "--size",
+            "100",
+            "--seed",
+            "seed phrase",
+            "--algo",
+            "modern",
+        ];
+        assert_eq!(
+            numbers(&stdout(&args, "")),
+            gen_modern_shuffle_from_seed_ref(100, b"seed phrase")
+        );
+
+        let args = [
+            "predict",
+            "--size",
+            "100",
+            "--positions",
+            "9,1,5",
+            "--seed",
+            "seed phrase",
+        ];
+        let expected = gen_crypto_predictive_shuffle_from_seed_ref(100, [9, 1, 5], b"seed phrase");
+        let output: Vec<String> = stdout(&args, "").lines().map(str::to_string).collect();
+        let mut lines: Vec<String> = expected
+            .iter()
+            .map(|(from, to)| format!("{} {}", from, to))
+            .collect();
+        lines.sort_by_key(|line| line.split(' ').next().unwrap().parse::<usize>().unwrap());
+        assert_eq!(output, lines);
+
+        let args = [
+            "predict",
+            "--size",
+            "100",
+            "--positions",
+            "1,5",
+            "--batch",
+            "4",
+            "--algo",
+            "modern",
+            "--seed",
+            "seed phrase",
+            "--json",
+        ];
+        let record: ShuffleRecord = serde_json::from_str(&stdout(&args, "")).unwrap();
+        assert_eq!(
+            std::collections::HashMap::from(record.result.positions().unwrap().clone()),
+            gen_batch_predictive_shuffle_from_seed_ref(100, 4, [1, 5], b"seed phrase")
+        );
+
+        let output = cli(
+            &[
+                "predict",
+                "--size",
+                "10",
+                "--positions",
+                "1",
+                "--algo",
+                "fastrand",
+            ],
+            "",
+        );
+        assert!(!output.status.success());
+        let output = cli(&["predict", "--size", "10", "--positions", "10"], "");
+        assert!(!output.status.success());
+
+        let args = [
+            "predict",
+            "--size",
+            "10",
+            "--positions",
+            "1",
+            "--batch",
+            "20",
+            "--seed",
+            "x",
+        ];
+        let output = cli(&args, "");
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "error: batch size 20 is larger than the size\n"
+        );
+
+        // a batch above half the size used to panic in the crypto batch draw
+        let args = [
+            "predict",
+            "--size",
+            "10",
+            "--positions",
+            "1",
+            "--batch",
+            "6",
+            "--seed",
+            "x",
+        ];
+        let new_positions = gen_crypto_batch_predictive_shuffle_from_seed_ref(10, 6, [1], b"x");
+        assert_eq!(stdout(&args, ""), format!("1 {}\n", new_positions[&1]));
+
+        let args = [
+            "predict",
+            "--size",
+            "10",
+            "--positions",
+            "1",
+            "--batch",
+            "0",
+            "--seed",
+            "x",
+        ];
+        let output = cli(&args, "");
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "error: batch algorithms need a non-zero batch size\n"
+        );
+    }
+
+    #[test]
+    fn verify_test() {
+        let record = stdout(
+            &["gen", "--size", "10", "--seed", "seed phrase", "--json"],
+            "",
+        );
+        assert_eq!(stdout(&["verify"], &record), "ok\n");
+        assert_eq!(stdout(&["verify", "--json"], &record), "{\"valid\":true}\n");
+
+        let tampered = record.replace("\"seed\":\"", "\"seed\":\"00");
+        let output = cli(&["verify", "--json"], &tampered);
+        assert!(!output.status.success());
+        let outcome: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(outcome["valid"], false);
+
+        let entrants = ["alice", "bob", "carol", "dave"];
+        let reveal = Reveal::new(b"secret seed".to_vec());
+        let transcript: Transcript = Transcript::new(&reveal, &entrants, vec![0, 1]).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entrants.txt");
+        std::fs::write(&path, entrants.join("\n")).unwrap();
+        let path = path.to_str().unwrap();
+
+        let transcript = transcript.to_string();
+        assert_eq!(stdout(&["verify", "--entrants", path], &transcript), "ok\n");
+        std::fs::write(path, "alice\nbob\ncarol\n").unwrap();
+        assert!(!cli(&["verify", "--entrants", path], &transcript)
+            .status
+            .success());
+        assert!(!cli(&["verify"], &transcript).status.success());
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(all(test, feature = "chacha", feature = "fastrand"))]
+mod tests {
+    use predictive_shuffle::co_shuffle::*;
+    use predictive_shuffle::constant_time::*;
+    use predictive_shuffle::*;
+
+    #[test]
+    fn permute_test() {
+        for size in [0, 1, 2, 10, 257] {
+            let permutation = gen_crypto_modern_shuffle_from_seed_ref(size, b"seed phrase");
+            let mut items: Vec<String> = (0..size).map(|i| i.to_string()).collect();
+            items.permute(&permutation);
+            let expected: Vec<String> = permutation.iter().map(|i| i.to_string()).collect();
+            assert_eq!(items, expected);
+        }
+    }
+
+    #[test]
+    fn co_shuffle_matches_shuffle_test() {
+        let seed = b"seed phrase";
+        let mut ids: Vec<u64> = (0..100).collect();
+        let mut labels: Vec<String> = (0..100).map(|i| format!("label {}", i)).collect();
+        let mut flags = [false; 100];
+        flags[7] = true;
+
+        crypto_co_shuffle_from_seed_ref(&mut [&mut ids, &mut labels, &mut flags], seed).unwrap();
+        let expected = (0..100u64)
+            .collect::<Vec<_>>()
+            .crypto_modern_shuffle_from_seed_ref(seed);
+        assert_eq!(ids, expected);
+        for ((id, label), flag) in ids.iter().zip(&labels).zip(flags) {
+            assert_eq!(label, &format!("label {}", id));
+            assert_eq!(flag, *id == 7);
+        }
+
+        let mut ids: Vec<u64> = (0..100).collect();
+        let mut slice: Vec<u64> = (0..100).collect();
+        let mut slice = slice.as_mut_slice();
+        co_shuffle_from_seed_ref(&mut [&mut ids, &mut slice], seed).unwrap();
+        assert_eq!(
+            ids,
+            gen_modern_shuffle_from_seed_ref(100, seed)
+                .iter()
+                .map(|&i| i as u64)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(ids, slice);
+
+        let mut ids: Vec<usize> = (0..100).collect();
+        constant_time_co_shuffle_from_seed_ref(&mut [&mut ids], seed).unwrap();
+        assert_eq!(ids, gen_constant_time_shuffle_from_seed(100, seed));
+    }
+
+    #[test]
+    #[should_panic(expected = "permutation repeats index 1")]
+    fn permute_repeated_index_test() {
+        let mut items = [0, 1, 2];
+        items.permute(&[1, 1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn co_shuffle_entropy_test() {
+        let mut ids: Vec<u64> = (0..100).collect();
+        let mut labels: Vec<u64> = (0..100).collect();
+        co_shuffle(&mut [&mut ids, &mut labels]).unwrap();
+        assert_eq!(ids, labels);
+        crypto_co_shuffle(&mut [&mut ids, &mut labels]).unwrap();
+        assert_eq!(ids, labels);
+        constant_time_co_shuffle(&mut [&mut ids, &mut labels]).unwrap();
+        assert_eq!(ids, labels);
+
+        ids.sort();
+        assert_eq!(ids, (0..100).collect::<Vec<_>>());
+        assert_eq!(co_shuffle(&mut []), Ok(()));
+    }
+
+    #[test]
+    fn co_shuffle_length_mismatch_test() {
+        let mut ids: Vec<u64> = (0..10).collect();
+        let mut labels: Vec<u8> = (0..10).collect();
+        let mut short: Vec<u8> = (0..9).collect();
+        assert_eq!(
+            crypto_co_shuffle_from_seed_ref(
+                &mut [&mut ids, &mut labels, &mut short],
+                b"seed phrase"
+            ),
+            Err(CoShuffleError::LengthMismatch {
+                expected: 10,
+                found: 9
+            })
+        );
+
+        // nothing is shuffled on error
+        assert_eq!(ids, (0..10).collect::<Vec<_>>());
+        assert_eq!(labels, (0..10).collect::<Vec<_>>());
+        assert_eq!(short, (0..9).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(all(test, feature = "std", feature = "chacha", feature = "fastrand"))]
+mod tests {
+    use predictive_shuffle::constant_time::*;
+    use predictive_shuffle::gen_crypto_predictive_shuffle_from_seed_ref;
+    use std::hint::black_box;
+    use std::time::Instant;
+
+    #[test]
+    fn constant_time_shuffle_test() {
+        for size in [0, 1, 2, 3, 52, 100] {
+            let seed = b"seed phrase".to_vec();
+            let shuffled_vec = gen_constant_time_shuffle_from_seed(size, &seed);
+            assert_eq!(
+                shuffled_vec,
+                gen_constant_time_shuffle_from_seed(size, &seed)
+            );
+
+            let mut sorted = shuffled_vec.clone();
+            sorted.sort();
+            assert_eq!(sorted, (0..size).collect::<Vec<_>>());
+
+            let positions: Vec<usize> = (0..size).collect();
+            let new_positions =
+                gen_constant_time_predictive_shuffle_from_seed(size, &positions, seed);
+            for (position, new_position) in positions.into_iter().zip(new_positions) {
+                assert_eq!(shuffled_vec[new_position], position);
+            }
+        }
+
+        let mut deck: Vec<u8> = (0..52).collect();
+        constant_time_shuffle_from_seed(&mut deck, b"seed phrase");
+        let expected = gen_constant_time_shuffle_from_seed(52, b"seed phrase");
+        assert_eq!(deck, expected.iter().map(|i| *i as u8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn constant_time_uniformity_test() {
+        // each of the 6 orderings of 3 items should come up about 1/6 of the time
+        let mut counts = [0usize; 6];
+        for round in 0..6_000u32 {
+            let shuffled_vec = gen_constant_time_shuffle_from_seed(3, round.to_be_bytes());
+            let rank = shuffled_vec[0] * 2 + (shuffled_vec[1] > shuffled_vec[2]) as usize;
+            counts[rank] += 1;
+        }
+        for count in counts {
+            assert!((850..1_150).contains(&count), "{:?}", counts);
+        }
+    }
+
+    /// Welch's t-statistic between the timings of two input classes, in the style of dudect:
+    /// samples from both classes are interleaved at random, and the slowest 5% are cropped
+    fn timing_t<F: FnMut(bool)>(samples: usize, mut run: F) -> f64 {
+        let mut rng = fastrand::Rng::with_seed(7);
+        let mut timings: Vec<(bool, f64)> = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            let class = rng.bool();
+            let start = Instant::now();
+            run(class);
+            timings.push((class, start.elapsed().as_nanos() as f64));
+        }
+
+        let mut sorted: Vec<f64> = timings.iter().map(|(_, t)| *t).collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let cutoff = sorted[sorted.len() * 95 / 100];
+
+        let stats = |class: bool| {
+            let values: Vec<f64> = timings
+                .iter()
+                .filter(|(c, t)| *c == class && *t <= cutoff)
+                .map(|(_, t)| *t)
+                .collect();
+            let n = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / n;
+            let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            (mean, var, n)
+        };
+        let (mean_a, var_a, n_a) = stats(true);
+        let (mean_b, var_b, n_b) = stats(false);
+        (mean_a - mean_b) / (var_a / n_a + var_b / n_b).sqrt()
+    }
+
+    #[test]
+    #[ignore = "wall-clock timing, run on an idle machine with --release -- --ignored"]
+    fn constant_time_predictive_timing_test() {
+        let size = 256;
+        let seed = b"seed phrase".to_vec();
+
+        // one position per class: with the seed fixed, the branching variant stops once its
+        // tracked item is placed, which is at the first step for one item and the last for another
+        let placed = gen_crypto_predictive_shuffle_from_seed_ref(size, 0..size, &seed);
+        let first = *placed.iter().max_by_key(|(_, to)| **to).unwrap().0;
+        let last = *placed.iter().min_by_key(|(_, to)| **to).unwrap().0;
+
+        // the harness must detect the data-dependent early exit of the branching variant
+        let leaky = timing_t(2_000, |class| {
+            let position = if class { first } else { last };
+            black_box(gen_crypto_predictive_shuffle_from_seed_ref(
+                size,
+                [position],
+                &seed,
+            ));
+        });
+        assert!(leaky.abs() > 10.0, "t = {}", leaky);
+
+        let t = timing_t(2_000, |class| {
+            let position = if class { first } else { last };
+            black_box(gen_constant_time_predictive_shuffle_from_seed(
+                size,
+                &[position],
+                &seed,
+            ));
+        });
+        assert!(t.abs() < 10.0, "t = {}", t);
+    }
+}
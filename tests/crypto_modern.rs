@@ -0,0 +1,16 @@
+#[cfg(all(test, feature = "chacha"))]
+mod tests {
+    #[test]
+    #[allow(deprecated)]
+    fn crypto_modern_shuffle_size_test() {
+        for size in [0, 1, 9, 10, 11, 100] {
+            let seed = b"seed phrase".to_vec();
+            let shuffled_vec = predictive_shuffle::gen_crypto_modern_shuffle_from_seed(size, seed);
+            assert_eq!(shuffled_vec.len(), size);
+
+            let mut sorted = shuffled_vec;
+            sorted.sort();
+            assert_eq!(sorted, (0..size).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(all(test, feature = "external"))]
+mod tests {
+    use predictive_shuffle::external::{ExternalShuffle, RecordFormat};
+    use std::io::ErrorKind;
+
+    fn lines(size: usize) -> Vec<u8> {
+        (0..size)
+            .flat_map(|i| format!("record {}\n", i).into_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn external_shuffle_is_permutation_test() {
+        let input = lines(5_000);
+        let seed = b"seed phrase".to_vec();
+        let mut output = vec![];
+        let records = ExternalShuffle::new(RecordFormat::Lines)
+            .memory_budget(4 * 1024)
+            .fan_out(4)
+            .shuffle_from_seed(input.as_slice(), &mut output, seed)
+            .unwrap();
+        assert_eq!(records, 5_000);
+        assert_ne!(output, input);
+
+        let mut sorted_input: Vec<&[u8]> = input.split(|b| *b == b'\n').collect();
+        let mut sorted_output: Vec<&[u8]> = output.split(|b| *b == b'\n').collect();
+        sorted_input.sort();
+        sorted_output.sort();
+        assert_eq!(sorted_input, sorted_output);
+    }
+
+    #[test]
+    fn external_small_budget_test() {
+        // too small for 64 buffers of 512 bytes, so fewer buckets and smaller buffers are used
+        let input = lines(2_000);
+        let mut output = vec![];
+        let records = ExternalShuffle::new(RecordFormat::Lines)
+            .memory_budget(1024)
+            .shuffle_from_seed(input.as_slice(), &mut output, b"seed phrase")
+            .unwrap();
+        assert_eq!(records, 2_000);
+
+        let mut sorted_input: Vec<&[u8]> = input.split(|b| *b == b'\n').collect();
+        let mut sorted_output: Vec<&[u8]> = output.split(|b| *b == b'\n').collect();
+        sorted_input.sort();
+        sorted_output.sort();
+        assert_eq!(sorted_input, sorted_output);
+    }
+
+    #[test]
+    fn external_shuffle_from_seed_test() {
+        let input = lines(1_000);
+        let shuffle = ExternalShuffle::new(RecordFormat::Lines).memory_budget(2 * 1024);
+
+        let mut first = vec![];
+        let mut second = vec![];
+        let mut other = vec![];
+        shuffle
+            .shuffle_from_seed(input.as_slice(), &mut first, b"seed phrase")
+            .unwrap();
+        shuffle
+            .shuffle_from_seed(input.as_slice(), &mut second, b"seed phrase")
+            .unwrap();
+        shuffle
+            .shuffle_from_seed(input.as_slice(), &mut other, b"other phrase")
+            .unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn external_fixed_records_test() {
+        let input: Vec<u8> = (0..=255).collect();
+        let mut output = vec![];
+        ExternalShuffle::new(RecordFormat::Fixed(4))
+            .shuffle_from_seed(input.as_slice(), &mut output, b"seed phrase")
+            .unwrap();
+        let mut chunks: Vec<&[u8]> = output.chunks(4).collect();
+        chunks.sort();
+        assert_eq!(chunks, input.chunks(4).collect::<Vec<_>>());
+
+        let error = ExternalShuffle::new(RecordFormat::Fixed(3))
+            .shuffle_from_seed(input.as_slice(), &mut vec![], b"seed phrase")
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,84 @@
+/* Exercises the C ABI, printing every result on its own line for tests/ffi.rs to compare. */
+#include <stdio.h>
+#include <string.h>
+
+#include "predictive_shuffle.h"
+
+static const uint8_t SEED[] = "seed phrase";
+static const size_t SEED_LEN = sizeof(SEED) - 1;
+static const size_t POSITIONS[] = {1, 5, 99};
+static const size_t POSITIONS_LEN = 3;
+
+static int check(PsStatus status, PsStatus expected, const char *call) {
+    if (status != expected) {
+        fprintf(stderr, "%s: %s\n", call, ps_status_message(status));
+        return 1;
+    }
+    return 0;
+}
+
+static void print(const size_t *items, size_t len) {
+    for (size_t i = 0; i < len; i++) {
+        printf(i == 0 ? "%zu" : " %zu", items[i]);
+    }
+    printf("\n");
+}
+
+typedef PsStatus (*Shuffle)(size_t, const uint8_t *, size_t, size_t *);
+
+static int shuffle(Shuffle f, const char *call) {
+    size_t out[10];
+    if (check(f(10, SEED, SEED_LEN, out), PS_STATUS_OK, call)) {
+        return 1;
+    }
+    print(out, 10);
+    return 0;
+}
+
+int main(void) {
+    size_t out[3];
+    int failed = 0;
+
+    failed |= shuffle(ps_gen_fastrand_shuffle_from_seed, "fastrand");
+    failed |= shuffle(ps_gen_modern_shuffle_from_seed, "modern");
+    failed |= shuffle(ps_gen_crypto_modern_shuffle_from_seed, "crypto_modern");
+    failed |= shuffle(ps_gen_constant_time_shuffle_from_seed, "constant_time");
+
+    failed |= check(ps_gen_predictive_shuffle_from_seed(100, POSITIONS, POSITIONS_LEN, SEED,
+                                                        SEED_LEN, out),
+                    PS_STATUS_OK, "predictive");
+    print(out, POSITIONS_LEN);
+    failed |= check(ps_gen_crypto_predictive_shuffle_from_seed(100, POSITIONS, POSITIONS_LEN, SEED,
+                                                               SEED_LEN, out),
+                    PS_STATUS_OK, "crypto_predictive");
+    print(out, POSITIONS_LEN);
+    failed |= check(ps_gen_batch_predictive_shuffle_from_seed(100, 4, POSITIONS, POSITIONS_LEN,
+                                                              SEED, SEED_LEN, out),
+                    PS_STATUS_OK, "batch_predictive");
+    print(out, POSITIONS_LEN);
+    failed |= check(ps_gen_crypto_batch_predictive_shuffle_from_seed(100, 4, POSITIONS,
+                                                                     POSITIONS_LEN, SEED,
+                                                                     SEED_LEN, out),
+                    PS_STATUS_OK, "crypto_batch_predictive");
+    print(out, POSITIONS_LEN);
+    failed |= check(ps_gen_constant_time_predictive_shuffle_from_seed(100, POSITIONS,
+                                                                      POSITIONS_LEN, SEED,
+                                                                      SEED_LEN, out),
+                    PS_STATUS_OK, "constant_time_predictive");
+    print(out, POSITIONS_LEN);
+
+    /* entropy seeded calls can only be checked for success */
+    failed |= check(ps_gen_crypto_predictive_shuffle(100, POSITIONS, POSITIONS_LEN, out),
+                    PS_STATUS_OK, "crypto_predictive entropy");
+
+    /* errors are reported, never raised */
+    failed |= check(ps_gen_predictive_shuffle(10, POSITIONS, POSITIONS_LEN, out),
+                    PS_STATUS_INVALID_POSITION, "invalid position");
+    failed |= check(ps_gen_batch_predictive_shuffle(100, 0, POSITIONS, POSITIONS_LEN, out),
+                    PS_STATUS_INVALID_BATCH, "invalid batch");
+    failed |= check(ps_gen_modern_shuffle_from_seed(10, NULL, SEED_LEN, out),
+                    PS_STATUS_NULL_POINTER, "null seed");
+    failed |= strcmp(ps_status_message(PS_STATUS_OK), "ok") != 0;
+
+    return failed;
+}
@@ -0,0 +1,210 @@
+#[cfg(all(test, feature = "ffi"))]
+mod tests {
+    use predictive_shuffle::constant_time::*;
+    use predictive_shuffle::ffi::*;
+    use predictive_shuffle::ordered::*;
+    use predictive_shuffle::*;
+    use std::ffi::CStr;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+    use std::ptr;
+
+    const HEADER: &str = "include/predictive_shuffle.h";
+
+    fn manifest_dir() -> &'static Path {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    /// Build the staticlib with the C ABI into its own target directory, returning its path
+    fn staticlib() -> PathBuf {
+        let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+        let status = Command::new(env!("CARGO"))
+            .args([
+                "rustc",
+                "--lib",
+                "--crate-type",
+                "staticlib",
+                "--features",
+                "ffi",
+            ])
+            .arg("--manifest-path")
+            .arg(manifest_dir().join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        target_dir.join("debug/libpredictive_shuffle.a")
+    }
+
+    #[test]
+    fn header_test() {
+        let mut generated = Vec::new();
+        cbindgen::generate_with_config(
+            manifest_dir(),
+            cbindgen::Config::from_file(manifest_dir().join("cbindgen.toml")).unwrap(),
+        )
+        .unwrap()
+        .write(&mut generated);
+
+        // set PREDICTIVE_SHUFFLE_UPDATE_HEADER after changing src/ffi.rs to regenerate the header
+        let path = manifest_dir().join(HEADER);
+        if std::env::var_os("PREDICTIVE_SHUFFLE_UPDATE_HEADER").is_some() {
+            std::fs::write(&path, &generated).unwrap();
+        }
+        let header = std::fs::read(&path).unwrap();
+        assert!(
+            header == generated,
+            "{} is out of date with src/ffi.rs",
+            HEADER
+        );
+    }
+
+    #[test]
+    fn ffi_test() {
+        let seed = b"seed phrase";
+        let positions = [99, 1, 5];
+        let mut out = vec![0; 100];
+
+        unsafe {
+            let status = ps_gen_modern_shuffle_from_seed(100, seed.as_ptr(), 11, out.as_mut_ptr());
+            assert_eq!(status, PsStatus::Ok);
+            assert_eq!(out, gen_modern_shuffle_from_seed_ref(100, seed));
+
+            let status =
+                ps_gen_crypto_modern_shuffle_from_seed(100, seed.as_ptr(), 11, out.as_mut_ptr());
+            assert_eq!(status, PsStatus::Ok);
+            assert_eq!(out, gen_crypto_modern_shuffle_from_seed_ref(100, seed));
+
+            let mut out = [0; 3];
+            let status = ps_gen_crypto_batch_predictive_shuffle_from_seed(
+                100,
+                4,
+                positions.as_ptr(),
+                3,
+                seed.as_ptr(),
+                11,
+                out.as_mut_ptr(),
+            );
+            assert_eq!(status, PsStatus::Ok);
+            let mut expected = [0; 3];
+            gen_crypto_batch_predictive_shuffle_from_seed_into(
+                100,
+                4,
+                &positions,
+                seed,
+                &mut expected,
+            );
+            assert_eq!(out, expected);
+
+            let status = ps_gen_predictive_shuffle(100, [1, 100].as_ptr(), 2, out.as_mut_ptr());
+            assert_eq!(status, PsStatus::InvalidPosition);
+            let status =
+                ps_gen_batch_predictive_shuffle(100, 0, positions.as_ptr(), 3, out.as_mut_ptr());
+            assert_eq!(status, PsStatus::InvalidBatch);
+            let status = ps_gen_batch_predictive_shuffle(10, 11, [1].as_ptr(), 1, out.as_mut_ptr());
+            assert_eq!(status, PsStatus::InvalidBatch);
+            let status = ps_gen_predictive_shuffle(100, [1, 1].as_ptr(), 2, out.as_mut_ptr());
+            assert_eq!(status, PsStatus::InvalidPosition);
+
+            // a batch above half the size used to panic into `PsStatus::Internal`
+            let status = ps_gen_crypto_batch_predictive_shuffle_from_seed(
+                10,
+                6,
+                [1, 5].as_ptr(),
+                2,
+                seed.as_ptr(),
+                11,
+                out.as_mut_ptr(),
+            );
+            assert_eq!(status, PsStatus::Ok);
+            let mut expected = [0; 2];
+            gen_crypto_batch_predictive_shuffle_from_seed_into(10, 6, &[1, 5], seed, &mut expected);
+            assert_eq!(out[..2], expected);
+            let status = ps_gen_modern_shuffle_from_seed(100, ptr::null(), 11, out.as_mut_ptr());
+            assert_eq!(status, PsStatus::NullPointer);
+            let status = ps_gen_modern_shuffle_from_seed(0, ptr::null(), 0, ptr::null_mut());
+            assert_eq!(status, PsStatus::Ok);
+
+            let status = ps_gen_constant_time_predictive_shuffle_from_seed(
+                100,
+                [1, 100].as_ptr(),
+                2,
+                seed.as_ptr(),
+                11,
+                out.as_mut_ptr(),
+            );
+            assert_eq!(status, PsStatus::Ok);
+            assert_eq!(
+                out[..2],
+                gen_constant_time_predictive_shuffle_from_seed(100, &[1, 100], seed)
+            );
+
+            let message = CStr::from_ptr(ps_status_message(PsStatus::InvalidBatch));
+            assert_eq!(
+                message.to_str().unwrap(),
+                "batch size is zero or larger than the size"
+            );
+        }
+    }
+
+    /// Compile `tests/ffi.c` against the header and the staticlib, and compare its output with
+    /// the Rust functions
+    #[test]
+    fn c_program_test() {
+        let lib = staticlib();
+        let exe = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi_test");
+        let cc = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+        let link = ["-lpthread", "-ldl", "-lm"];
+        let status = Command::new(cc)
+            .arg(manifest_dir().join("tests/ffi.c"))
+            .arg("-I")
+            .arg(manifest_dir().join("include"))
+            .arg(&lib)
+            .args(link)
+            .arg("-o")
+            .arg(&exe)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let output = Command::new(&exe).output().unwrap();
+        assert!(output.status.success(), "{:?}", output);
+
+        let seed = b"seed phrase";
+        let positions = [1, 5, 99];
+        let join = |vec: &[usize]| {
+            vec.iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut predicted = [0; 3];
+        let mut lines = vec![
+            join(&gen_fastrand_shuffle_from_seed_ref(10, seed)),
+            join(&gen_modern_shuffle_from_seed_ref(10, seed)),
+            join(&gen_crypto_modern_shuffle_from_seed_ref(10, seed)),
+            join(&gen_constant_time_shuffle_from_seed(10, seed)),
+        ];
+        gen_predictive_shuffle_from_seed_into(100, &positions, seed, &mut predicted);
+        lines.push(join(&predicted));
+        gen_crypto_predictive_shuffle_from_seed_into(100, &positions, seed, &mut predicted);
+        lines.push(join(&predicted));
+        gen_batch_predictive_shuffle_from_seed_into(100, 4, &positions, seed, &mut predicted);
+        lines.push(join(&predicted));
+        gen_crypto_batch_predictive_shuffle_from_seed_into(
+            100,
+            4,
+            &positions,
+            seed,
+            &mut predicted,
+        );
+        lines.push(join(&predicted));
+        lines.push(join(&gen_constant_time_predictive_shuffle_from_seed(
+            100, &positions, seed,
+        )));
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(stdout.lines().collect::<Vec<_>>(), lines);
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(all(
+    test,
+    feature = "std",
+    feature = "sha2",
+    feature = "chacha",
+    feature = "fastrand"
+))]
+mod tests {
+    use predictive_shuffle::gen_crypto_modern_shuffle_from_seed_ref;
+    use predictive_shuffle::keyed::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn keyed_shuffle_test() {
+        let key = ServerKey::new("2023-01", b"server secret".to_vec());
+        let other = ServerKey::new("2023-02", b"other secret".to_vec());
+        let seed = b"seed phrase".to_vec();
+
+        let keyed = gen_keyed_crypto_modern_shuffle_from_seed(&key, 100, &seed, b"draw 1");
+        assert_eq!(keyed.key_id, "2023-01");
+        assert_eq!(
+            keyed,
+            gen_keyed_crypto_modern_shuffle_from_seed(&key, 100, &seed, b"draw 1")
+        );
+
+        // knowing the seed alone is not enough to recompute the shuffle
+        assert_ne!(
+            keyed.value,
+            gen_crypto_modern_shuffle_from_seed_ref(100, &seed)
+        );
+        assert_ne!(
+            keyed.value,
+            gen_keyed_crypto_modern_shuffle_from_seed(&other, 100, &seed, b"draw 1").value
+        );
+        assert_ne!(
+            keyed.value,
+            gen_keyed_crypto_modern_shuffle_from_seed(&key, 100, &seed, b"draw 2").value
+        );
+
+        // the seed length keeps the seed and context from running into each other
+        assert_ne!(key.derive_seed(b"ab", b"c"), key.derive_seed(b"a", b"bc"));
+
+        // the crypto variants seed from the HMAC output like the plain functions do
+        let derived = key.derive_seed(&seed, b"draw 1");
+        assert_eq!(
+            keyed.value,
+            gen_crypto_modern_shuffle_from_seed_ref(100, &derived)
+        );
+
+        let mut vec: Vec<usize> = (0..100).collect();
+        let shuffled_vec = vec.keyed_crypto_modern_shuffle_from_seed(&key, &seed, b"draw 1");
+        assert_eq!(shuffled_vec, keyed);
+
+        let positions: Vec<usize> = vec![1, 5, 99];
+        let mut vec: Vec<usize> = (0..100).collect();
+        let new_positions =
+            vec.keyed_crypto_predictive_shuffle_from_seed(&key, &positions, &seed, b"draw 1");
+        assert_eq!(
+            new_positions,
+            gen_keyed_crypto_predictive_shuffle_from_seed(&key, 100, positions, seed, b"draw 1")
+        );
+    }
+
+    #[test]
+    fn keyed_full_hmac_test() {
+        let key = ServerKey::new("2023-01", b"server secret".to_vec());
+        let seed = b"seed phrase".to_vec();
+
+        // find two contexts whose HMAC outputs share their first 4 bytes
+        let mut seen = HashMap::new();
+        let (first, second) = (0u32..)
+            .find_map(|i| {
+                let context = i.to_be_bytes();
+                let derived = key.derive_seed(&seed, &context);
+                let prefix: [u8; 4] = derived.as_bytes()[..4].try_into().unwrap();
+                seen.insert(prefix, context).map(|other| (other, context))
+            })
+            .unwrap();
+        assert_ne!(
+            key.derive_seed(&seed, &first),
+            key.derive_seed(&seed, &second)
+        );
+
+        assert_ne!(
+            gen_keyed_crypto_modern_shuffle_from_seed(&key, 100, &seed, &first),
+            gen_keyed_crypto_modern_shuffle_from_seed(&key, 100, &seed, &second)
+        );
+        assert_ne!(
+            gen_keyed_constant_time_shuffle_from_seed(&key, 100, &seed, &first),
+            gen_keyed_constant_time_shuffle_from_seed(&key, 100, &seed, &second)
+        );
+        assert_ne!(
+            gen_keyed_modern_shuffle_from_seed(&key, 100, &seed, &first),
+            gen_keyed_modern_shuffle_from_seed(&key, 100, seed, &second)
+        );
+    }
+
+    #[test]
+    fn keyring_rotation_test() {
+        let mut keyring = Keyring::new(ServerKey::new("2023-01", b"first secret".to_vec()));
+        let seed = b"seed phrase".to_vec();
+        let first = gen_keyed_modern_shuffle_from_seed(keyring.current(), 10, &seed, b"");
+
+        keyring
+            .rotate(ServerKey::new("2023-02", b"second secret".to_vec()))
+            .unwrap();
+        assert_eq!(keyring.current().id(), "2023-02");
+        assert_eq!(keyring.ids(), vec!["2023-01", "2023-02"]);
+        assert_eq!(
+            keyring.rotate(ServerKey::new("2023-01", b"again".to_vec())),
+            Err(KeyedError::DuplicateKey("2023-01".to_string()))
+        );
+
+        let second = gen_keyed_modern_shuffle_from_seed(keyring.current(), 10, &seed, b"");
+        assert_eq!(second.key_id, "2023-02");
+
+        let key = keyring.get(&first.key_id).unwrap();
+        assert_eq!(
+            gen_keyed_modern_shuffle_from_seed(key, 10, seed, b""),
+            first
+        );
+
+        assert_eq!(
+            keyring.retire("2023-02").unwrap_err(),
+            KeyedError::CurrentKey("2023-02".to_string())
+        );
+        keyring.retire("2023-01").unwrap();
+        assert_eq!(
+            keyring.get("2023-01").unwrap_err(),
+            KeyedError::UnknownKey("2023-01".to_string())
+        );
+    }
+
+    #[test]
+    fn server_key_debug_test() {
+        let key = ServerKey::new("2023-01", b"server secret".to_vec());
+        assert_eq!(format!("{:?}", key), r#"ServerKey { id: "2023-01", .. }"#);
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(all(test, feature = "lehmer", feature = "chacha", feature = "fastrand"))]
+mod tests {
+    use predictive_shuffle::gen_modern_shuffle_from_seed_ref;
+    use predictive_shuffle::lehmer::*;
+
+    #[test]
+    fn rank_roundtrip_test() {
+        for size in [0, 1, 2, 10, MAX_U128_SIZE] {
+            let seed = b"seed phrase".to_vec();
+            let shuffled_vec = gen_modern_shuffle_from_seed_ref(size, seed);
+            let rank = rank_permutation(&shuffled_vec).unwrap();
+            assert_eq!(unrank_permutation(size, rank).unwrap(), shuffled_vec);
+
+            let big_rank = rank_permutation_big(&shuffled_vec).unwrap();
+            assert_eq!(big_rank, rank.into());
+        }
+    }
+
+    #[test]
+    fn rank_is_lexicographic_test() {
+        let permutations = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        for (rank, permutation) in permutations.iter().enumerate() {
+            assert_eq!(rank_permutation(permutation), Ok(rank as u128));
+        }
+        assert_eq!(
+            rank_permutation(&(0..34).rev().collect::<Vec<_>>()),
+            Ok(factorial(34).unwrap() - 1)
+        );
+    }
+
+    #[test]
+    fn rank_errors_test() {
+        assert_eq!(
+            rank_permutation(&[0, 0, 1]),
+            Err(LehmerError::NotAPermutation)
+        );
+        assert_eq!(
+            rank_permutation(&[0, 3, 1]),
+            Err(LehmerError::NotAPermutation)
+        );
+        assert_eq!(
+            rank_permutation(&(0..35).collect::<Vec<_>>()),
+            Err(LehmerError::TooLarge(35))
+        );
+        assert_eq!(unrank_permutation(3, 6), Err(LehmerError::RankOutOfRange));
+        assert_eq!(
+            unrank_permutation_big(3, &6u8.into()),
+            Err(LehmerError::RankOutOfRange)
+        );
+    }
+
+    #[test]
+    fn crypto_rank_from_seed_test() {
+        let seed = b"seed phrase".to_vec();
+        let rank = gen_crypto_rank_big_from_seed(52, &seed);
+        assert!(rank < factorial_big(52));
+
+        let deck = unrank_permutation_big(52, &rank).unwrap();
+        assert_eq!(rank_permutation_big(&deck), Ok(rank));
+        assert!(gen_crypto_rank_from_seed(MAX_U128_SIZE, seed).unwrap() < factorial(34).unwrap());
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(all(test, feature = "std", feature = "sha2", feature = "chacha"))]
+mod tests {
+    use predictive_shuffle::multiparty::{MultiPartyError, SeedCeremony};
+    use predictive_shuffle::transcript::Reveal;
+    use predictive_shuffle::{Seed, Shuffle};
+
+    fn reveal(party: &str) -> Reveal {
+        Reveal::with_nonce(party.as_bytes().to_vec(), vec![1; 32])
+    }
+
+    fn ceremony(order: &[&str]) -> SeedCeremony {
+        let mut ceremony = SeedCeremony::new();
+        for party in order {
+            ceremony.commit(*party, reveal(party).commitment()).unwrap();
+        }
+        for party in order {
+            ceremony.reveal(*party, reveal(party)).unwrap();
+        }
+        ceremony
+    }
+
+    #[test]
+    fn canonical_order_test() {
+        let seed = ceremony(&["alice", "bob", "carol"]).seed().unwrap();
+        assert_eq!(
+            ceremony(&["carol", "alice", "bob"]).seed(),
+            Ok(seed.clone())
+        );
+        assert_ne!(ceremony(&["alice", "bob"]).seed(), Ok(seed.clone()));
+
+        let mut vec: Vec<usize> = (0..100).collect();
+        let shuffled = vec.crypto_predictive_shuffle_from_seed_ref([1, 5], &seed);
+        let expected = vec.crypto_predictive_shuffle_from_seed_ref([1, 5], seed.into_vec());
+        assert_eq!(shuffled, expected);
+    }
+
+    #[test]
+    fn missing_reveal_test() {
+        let mut ceremony = SeedCeremony::new();
+        ceremony
+            .commit("alice", reveal("alice").commitment())
+            .unwrap();
+        ceremony.commit("bob", reveal("bob").commitment()).unwrap();
+        assert_eq!(
+            ceremony.seed(),
+            Err(MultiPartyError::MissingReveals(vec![
+                "alice".into(),
+                "bob".into()
+            ]))
+        );
+
+        ceremony.reveal("alice", reveal("alice")).unwrap();
+        assert_eq!(ceremony.missing(), vec!["bob"]);
+        assert_eq!(
+            ceremony.seed(),
+            Err(MultiPartyError::MissingReveals(vec!["bob".into()]))
+        );
+        assert_eq!(SeedCeremony::new().seed(), Err(MultiPartyError::NoParties));
+    }
+
+    #[test]
+    fn mismatched_reveal_test() {
+        let mut ceremony = SeedCeremony::new();
+        ceremony
+            .commit("alice", reveal("alice").commitment())
+            .unwrap();
+        assert_eq!(
+            ceremony.commit("alice", reveal("alice").commitment()),
+            Err(MultiPartyError::DuplicateCommitment("alice".into()))
+        );
+        ceremony.commit("bob", reveal("bob").commitment()).unwrap();
+
+        assert_eq!(
+            ceremony.reveal("bob", reveal("mallory")),
+            Err(MultiPartyError::RevealMismatch("bob".into()))
+        );
+        assert_eq!(
+            ceremony.reveal("mallory", reveal("mallory")),
+            Err(MultiPartyError::UnknownParty("mallory".into()))
+        );
+        ceremony.reveal("alice", reveal("alice")).unwrap();
+        assert_eq!(
+            ceremony.reveal("alice", reveal("alice")),
+            Err(MultiPartyError::DuplicateReveal("alice".into()))
+        );
+        assert_eq!(
+            ceremony.commit("mallory", reveal("mallory").commitment()),
+            Err(MultiPartyError::CommitmentsClosed("mallory".into()))
+        );
+        assert_eq!(Seed::from(b"x".as_slice()).as_bytes(), b"x");
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(all(test, feature = "sha2", feature = "chacha", feature = "fastrand"))]
+mod tests {
+    use predictive_shuffle::constant_time::*;
+    use predictive_shuffle::ordered::*;
+    use predictive_shuffle::*;
+    use std::path::Path;
+    use std::process::Command;
+
+    const TARGET: &str = "thumbv7em-none-eabihf";
+
+    #[test]
+    fn no_std_seeded_test() {
+        // these hold with and without the `std` feature
+        let seed = b"seed phrase";
+        assert_eq!(
+            gen_fastrand_shuffle_from_seed_ref(10, seed),
+            [1, 7, 5, 8, 2, 6, 0, 4, 9, 3]
+        );
+        assert_eq!(
+            gen_modern_shuffle_from_seed_ref(10, seed),
+            [4, 9, 6, 1, 2, 3, 0, 7, 5, 8]
+        );
+        assert_eq!(
+            gen_crypto_modern_shuffle_from_seed_ref(10, seed),
+            [3, 2, 0, 5, 6, 1, 9, 7, 8, 4]
+        );
+        assert_eq!(
+            gen_constant_time_shuffle_from_seed(10, seed),
+            [6, 8, 0, 7, 4, 9, 3, 2, 1, 5]
+        );
+
+        let mut items: Vec<usize> = (0..10).collect();
+        assert_eq!(
+            items.modern_shuffle_from_seed_ref(seed),
+            [4, 9, 6, 1, 2, 3, 0, 7, 5, 8]
+        );
+
+        let positions = [9, 1, 5];
+        let mut out = [0; 3];
+        gen_predictive_shuffle_from_seed_into(10, &positions, seed, &mut out);
+        assert_eq!(out, [8, 6, 1]);
+        gen_crypto_batch_predictive_shuffle_from_seed_into(10, 4, &positions, seed, &mut out);
+        assert_eq!(out, [8, 2, 4]);
+        assert_eq!(
+            gen_crypto_predictive_shuffle_from_seed_ordered(10, &positions, seed)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [(1, 5), (5, 7), (9, 1)]
+        );
+    }
+
+    /// Build the library without `std` for a bare-metal target, into its own target directory
+    #[test]
+    fn no_std_build_test() {
+        let status = Command::new(env!("CARGO"))
+            .args([
+                "rustc",
+                "--lib",
+                "--crate-type",
+                "rlib",
+                "--no-default-features",
+            ])
+            .args(["--features", "sha2,chacha,fastrand"])
+            .args(["--target", TARGET])
+            .arg("--manifest-path")
+            .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(Path::new(env!("CARGO_TARGET_TMPDIR")).join("no_std"))
+            .status()
+            .unwrap();
+        assert!(
+            status.success(),
+            "building for {} failed, is the target installed? (rustup target add {})",
+            TARGET,
+            TARGET
+        );
+    }
+}
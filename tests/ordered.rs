@@ -0,0 +1,123 @@
+#[cfg(all(test, feature = "std", feature = "chacha", feature = "fastrand"))]
+mod tests {
+    use predictive_shuffle::ordered::*;
+    use predictive_shuffle::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn ordered(map: HashMap<usize, usize>) -> BTreeMap<usize, usize> {
+        map.into_iter().collect()
+    }
+
+    #[test]
+    fn ordered_matches_hashmap_test() {
+        let size = 100;
+        let batch = 4;
+        let seed = b"seed phrase".to_vec();
+        let positions: Vec<usize> = vec![99, 1, 5, 42, 0];
+        let mut out = vec![0; positions.len()];
+
+        let expected = gen_predictive_shuffle_from_seed_ref(size, positions.clone(), seed.clone());
+        gen_predictive_shuffle_from_seed_into(size, &positions, seed.clone(), &mut out);
+        let aligned: Vec<usize> = positions.iter().map(|p| expected[p]).collect();
+        assert_eq!(out, aligned);
+        assert_eq!(
+            gen_predictive_shuffle_from_seed_ordered(size, &positions, seed.clone()),
+            ordered(expected)
+        );
+
+        let expected =
+            gen_crypto_predictive_shuffle_from_seed_ref(size, positions.clone(), seed.clone());
+        gen_crypto_predictive_shuffle_from_seed_into(size, &positions, seed.clone(), &mut out);
+        let aligned: Vec<usize> = positions.iter().map(|p| expected[p]).collect();
+        assert_eq!(out, aligned);
+        assert_eq!(
+            gen_crypto_predictive_shuffle_from_seed_ordered(size, &positions, seed.clone()),
+            ordered(expected)
+        );
+
+        let expected = gen_batch_predictive_shuffle_from_seed_ref(
+            size,
+            batch,
+            positions.clone(),
+            seed.clone(),
+        );
+        gen_batch_predictive_shuffle_from_seed_into(
+            size,
+            batch,
+            &positions,
+            seed.clone(),
+            &mut out,
+        );
+        let aligned: Vec<usize> = positions.iter().map(|p| expected[p]).collect();
+        assert_eq!(out, aligned);
+        assert_eq!(
+            gen_batch_predictive_shuffle_from_seed_ordered(size, batch, &positions, seed.clone()),
+            ordered(expected)
+        );
+
+        let expected = gen_crypto_batch_predictive_shuffle_from_seed_ref(
+            size,
+            batch,
+            positions.clone(),
+            seed.clone(),
+        );
+        gen_crypto_batch_predictive_shuffle_from_seed_into(
+            size,
+            batch,
+            &positions,
+            seed.clone(),
+            &mut out,
+        );
+        let aligned: Vec<usize> = positions.iter().map(|p| expected[p]).collect();
+        assert_eq!(out, aligned);
+        assert_eq!(
+            gen_crypto_batch_predictive_shuffle_from_seed_ordered(size, batch, &positions, seed),
+            ordered(expected)
+        );
+    }
+
+    #[test]
+    fn ordered_entropy_test() {
+        let size = 100;
+        let positions: Vec<usize> = (0..size).collect();
+        let mut out = vec![0; size];
+
+        gen_predictive_shuffle_into(size, &positions, &mut out);
+        let mut sorted = out.clone();
+        sorted.sort();
+        assert_eq!(sorted, positions);
+
+        gen_crypto_predictive_shuffle_into(size, &positions, &mut out);
+        let mut sorted = out.clone();
+        sorted.sort();
+        assert_eq!(sorted, positions);
+
+        let new_positions = gen_batch_predictive_shuffle_ordered(size, 4, &[1, 5]);
+        assert_eq!(
+            new_positions.keys().copied().collect::<Vec<_>>(),
+            vec![1, 5]
+        );
+
+        let new_positions = gen_crypto_batch_predictive_shuffle_ordered(size, 4, &[5, 1]);
+        assert_eq!(
+            new_positions.keys().copied().collect::<Vec<_>>(),
+            vec![1, 5]
+        );
+    }
+
+    #[test]
+    fn ordered_repeated_position_test() {
+        let seed = b"seed phrase".to_vec();
+        let mut out = vec![0; 3];
+        gen_predictive_shuffle_from_seed_into(100, &[1, 5, 1], seed.clone(), &mut out);
+        let expected = gen_predictive_shuffle_from_seed_ref(100, vec![1, 5], seed);
+        assert_eq!(out, vec![100, expected[&5], expected[&1]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out must be aligned with positions")]
+    fn ordered_length_mismatch_test() {
+        let mut out = vec![0; 1];
+        gen_predictive_shuffle_into(100, &[1, 5], &mut out);
+    }
+}
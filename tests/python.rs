@@ -0,0 +1,196 @@
+#[cfg(all(test, feature = "python"))]
+mod tests {
+    use predictive_shuffle::python::init;
+    use predictive_shuffle::*;
+    use pyo3::exceptions::{PyIndexError, PyValueError};
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+    use std::collections::HashMap;
+    use std::ffi::CString;
+
+    /// Run `f` with the module imported as `ps` into fresh globals
+    fn with_module<F: FnOnce(&Bound<'_, PyDict>)>(f: F) {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = pyo3::wrap_pymodule!(init)(py);
+            let globals = PyDict::new(py);
+            globals.set_item("ps", module).unwrap();
+            f(&globals);
+        })
+    }
+
+    fn eval<'py>(globals: &Bound<'py, PyDict>, code: &str) -> PyResult<Bound<'py, PyAny>> {
+        let code = CString::new(code).unwrap();
+        globals.py().eval(&code, Some(globals), None)
+    }
+
+    fn extract<'py, T: for<'a> FromPyObject<'a, 'py>>(
+        globals: &Bound<'py, PyDict>,
+        code: &str,
+    ) -> T {
+        match eval(globals, code).unwrap().extract() {
+            Ok(value) => value,
+            Err(_) => panic!("cannot extract the result of {}", code),
+        }
+    }
+
+    #[test]
+    fn permutation_test() {
+        let seed = b"seed phrase";
+        with_module(|globals| {
+            let permutation: Vec<usize> = extract(
+                globals,
+                r#"list(ps.gen_fastrand_shuffle_from_seed(100, b"seed phrase"))"#,
+            );
+            assert_eq!(permutation, gen_fastrand_shuffle_from_seed_ref(100, seed));
+            let permutation: Vec<usize> = extract(
+                globals,
+                r#"list(ps.gen_modern_shuffle_from_seed(100, b"seed phrase"))"#,
+            );
+            assert_eq!(permutation, gen_modern_shuffle_from_seed_ref(100, seed));
+            let permutation: Vec<usize> = extract(
+                globals,
+                r#"ps.gen_crypto_modern_shuffle_from_seed(100, b"seed phrase").to_list()"#,
+            );
+            assert_eq!(
+                permutation,
+                gen_crypto_modern_shuffle_from_seed_ref(100, seed)
+            );
+
+            let mut sorted: Vec<usize> =
+                extract(globals, "sorted(ps.gen_crypto_modern_shuffle(10))");
+            sorted.dedup();
+            assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+
+            // applying a permutation matches the `Shuffle` methods
+            let names = vec!["alice", "bob", "carol", "dave", "eve"];
+            let shuffled: Vec<String> = extract(
+                globals,
+                r#"ps.gen_modern_shuffle_from_seed(5, b"seed phrase").apply(["alice", "bob", "carol", "dave", "eve"])"#,
+            );
+            assert_eq!(shuffled, names.clone().modern_shuffle_from_seed_ref(seed));
+
+            let last: usize = extract(globals, "ps.Permutation([2, 0, 1])[-1]");
+            assert_eq!(last, 1);
+            let len: usize = extract(globals, "len(ps.Permutation([2, 0, 1]))");
+            assert_eq!(len, 3);
+            let equal: bool = extract(globals, "ps.Permutation([1, 0]) == ps.Permutation([1, 0])");
+            assert!(equal);
+            let repr: String = extract(globals, "repr(ps.Permutation([1, 0]))");
+            assert_eq!(repr, "Permutation([1, 0])");
+
+            let e = eval(globals, "ps.Permutation([2, 0, 1])[3]").unwrap_err();
+            assert!(e.is_instance_of::<PyIndexError>(globals.py()));
+            let e = eval(globals, "ps.Permutation([0, 0])").unwrap_err();
+            assert!(e.is_instance_of::<PyValueError>(globals.py()));
+            let e = eval(globals, "ps.Permutation([1, 0]).apply([1])").unwrap_err();
+            assert!(e.is_instance_of::<PyValueError>(globals.py()));
+        });
+    }
+
+    #[test]
+    fn predictive_test() {
+        let seed = b"seed phrase";
+        let positions = [99, 1, 5];
+        with_module(|globals| {
+            let new_positions: HashMap<usize, usize> = extract(
+                globals,
+                r#"ps.gen_predictive_shuffle_from_seed(100, [99, 1, 5], b"seed phrase")"#,
+            );
+            assert_eq!(
+                new_positions,
+                gen_predictive_shuffle_from_seed_ref(100, positions, seed)
+            );
+
+            // the `Shuffle` method over a vec of the same length gives the same answer
+            let mut vec: Vec<usize> = (0..100).collect();
+            assert_eq!(
+                new_positions,
+                vec.predictive_shuffle_from_seed_ref(positions, seed)
+            );
+
+            let new_positions: HashMap<usize, usize> = extract(
+                globals,
+                r#"ps.gen_crypto_predictive_shuffle_from_seed(100, (99, 1, 5), b"seed phrase")"#,
+            );
+            assert_eq!(
+                new_positions,
+                gen_crypto_predictive_shuffle_from_seed_ref(100, positions, seed)
+            );
+            let new_positions: HashMap<usize, usize> = extract(
+                globals,
+                r#"ps.gen_batch_predictive_shuffle_from_seed(100, 4, [99, 1, 5], b"seed phrase")"#,
+            );
+            assert_eq!(
+                new_positions,
+                gen_batch_predictive_shuffle_from_seed_ref(100, 4, positions, seed)
+            );
+            let new_positions: HashMap<usize, usize> = extract(
+                globals,
+                r#"ps.gen_crypto_batch_predictive_shuffle_from_seed(100, 4, [99, 1, 5], b"seed phrase")"#,
+            );
+            assert_eq!(
+                new_positions,
+                gen_crypto_batch_predictive_shuffle_from_seed_ref(100, 4, positions, seed)
+            );
+
+            let keys: Vec<usize> = extract(
+                globals,
+                "sorted(ps.gen_crypto_predictive_shuffle(100, [1, 5]))",
+            );
+            assert_eq!(keys, vec![1, 5]);
+
+            let e = eval(globals, "ps.gen_predictive_shuffle(100, [100])").unwrap_err();
+            assert!(e.is_instance_of::<PyValueError>(globals.py()));
+            let e = eval(globals, "ps.gen_batch_predictive_shuffle(100, 0, [1])").unwrap_err();
+            assert!(e.is_instance_of::<PyValueError>(globals.py()));
+            let e = eval(globals, "ps.gen_predictive_shuffle(100, [1, 1])").unwrap_err();
+            assert!(e.is_instance_of::<PyValueError>(globals.py()));
+
+            // a batch above half the size used to raise a `PanicException`
+            let new_positions: HashMap<usize, usize> = extract(
+                globals,
+                r#"ps.gen_crypto_batch_predictive_shuffle_from_seed(10, 6, [1, 5], b"seed phrase")"#,
+            );
+            assert_eq!(
+                new_positions,
+                gen_crypto_batch_predictive_shuffle_from_seed_ref(10, 6, [1, 5], seed)
+            );
+        });
+    }
+
+    #[test]
+    fn numpy_test() {
+        with_module(|globals| {
+            if eval(globals, "__import__('numpy')").is_err() {
+                eprintln!("numpy is not installed, skipping");
+                return;
+            }
+            let new_positions: HashMap<usize, usize> = extract(
+                globals,
+                r#"ps.gen_crypto_predictive_shuffle_from_seed(100, __import__('numpy').array([99, 1, 5]), b"seed phrase")"#,
+            );
+            assert_eq!(
+                new_positions,
+                gen_crypto_predictive_shuffle_from_seed_ref(100, [99, 1, 5], b"seed phrase")
+            );
+
+            let permutation: Vec<usize> = extract(
+                globals,
+                r#"__import__('numpy').asarray(ps.gen_crypto_modern_shuffle_from_seed(10, b"seed phrase"), dtype='int64').tolist()"#,
+            );
+            assert_eq!(
+                permutation,
+                gen_crypto_modern_shuffle_from_seed_ref(10, b"seed phrase")
+            );
+            let permutation: Vec<usize> = extract(
+                globals,
+                r#"ps.gen_modern_shuffle_from_seed(10, b"seed phrase").to_numpy().tolist()"#,
+            );
+            assert_eq!(
+                permutation,
+                gen_modern_shuffle_from_seed_ref(10, b"seed phrase")
+            );
+        });
+    }
+}
@@ -0,0 +1,205 @@
+#[cfg(all(
+    test,
+    feature = "std",
+    feature = "sha2",
+    feature = "chacha",
+    feature = "fastrand"
+))]
+mod tests {
+    use predictive_shuffle::keyed::{
+        gen_keyed_crypto_predictive_shuffle_from_seed, Keyring, ServerKey,
+    };
+    use predictive_shuffle::record::*;
+    use predictive_shuffle::{
+        gen_crypto_modern_shuffle_from_seed_ref, gen_predictive_shuffle_from_seed_ref,
+    };
+    use std::collections::HashMap;
+
+    fn request(algorithm: Algorithm) -> ShuffleRequest {
+        ShuffleRequest {
+            algorithm,
+            size: 100,
+            batch: algorithm.is_batch().then_some(4),
+            positions: if algorithm.is_predictive() {
+                vec![99, 1, 5]
+            } else {
+                vec![]
+            },
+            seed: SeedDescriptor::Seed(b"seed phrase".to_vec()),
+        }
+    }
+
+    #[test]
+    fn record_test() {
+        for algorithm in Algorithm::ALL {
+            assert_eq!(algorithm.as_str().parse::<Algorithm>(), Ok(algorithm));
+
+            let record = ShuffleRecord::new(request(algorithm)).unwrap();
+            assert!(record.verify().is_ok());
+            match &record.result {
+                ShuffleResult::Permutation(permutation) => {
+                    assert!(!algorithm.is_predictive());
+                    assert_eq!(permutation.len(), 100);
+                }
+                ShuffleResult::Positions(positions) => {
+                    assert!(algorithm.is_predictive());
+                    let from: Vec<usize> = positions.iter().map(|(from, _)| from).collect();
+                    assert_eq!(from, vec![1, 5, 99]);
+                }
+            }
+        }
+
+        let record = ShuffleRecord::new(request(Algorithm::CryptoModernShuffle)).unwrap();
+        assert_eq!(
+            record.result.permutation().unwrap().as_slice(),
+            gen_crypto_modern_shuffle_from_seed_ref(100, b"seed phrase")
+        );
+
+        let record = ShuffleRecord::new(request(Algorithm::PredictiveShuffle)).unwrap();
+        let expected = gen_predictive_shuffle_from_seed_ref(100, vec![99, 1, 5], b"seed phrase");
+        let positions = record.result.positions().unwrap();
+        assert_eq!(HashMap::from(positions.clone()), expected);
+        assert_eq!(positions.get(5), expected.get(&5).copied());
+        assert_eq!(positions.get(6), None);
+    }
+
+    #[test]
+    fn record_error_test() {
+        assert_eq!(
+            "riffle_shuffle".parse::<Algorithm>(),
+            Err(RecordError::UnknownAlgorithm("riffle_shuffle".to_string()))
+        );
+
+        let mut batch = request(Algorithm::BatchPredictiveShuffle);
+        batch.batch = None;
+        assert_eq!(batch.run(), Err(RecordError::MissingBatch));
+        batch.batch = Some(101);
+        assert_eq!(batch.run(), Err(RecordError::InvalidBatch(101)));
+
+        let mut size = request(Algorithm::PredictiveShuffle);
+        size.size = usize::MAX;
+        assert_eq!(size.run(), Err(RecordError::InvalidSize(usize::MAX)));
+
+        let mut positions = request(Algorithm::PredictiveShuffle);
+        positions.positions = vec![1, 100];
+        assert_eq!(positions.run(), Err(RecordError::InvalidPosition(100)));
+        positions.positions = vec![1, 1];
+        assert_eq!(positions.run(), Err(RecordError::InvalidPosition(1)));
+
+        let mut entropy = request(Algorithm::CryptoModernShuffle);
+        entropy.seed = SeedDescriptor::Entropy;
+        let record = ShuffleRecord::new(entropy).unwrap();
+        assert_eq!(record.verify(), Err(RecordError::NotReproducible));
+
+        let mut record = ShuffleRecord::new(request(Algorithm::ModernShuffle)).unwrap();
+        record.result = ShuffleResult::Permutation((0..100).collect::<Vec<_>>().into());
+        assert_eq!(record.verify(), Err(RecordError::ResultMismatch));
+    }
+
+    #[test]
+    fn large_batch_test() {
+        // batches above half the size leave the crypto batch draw a single slot to reuse
+        for algorithm in [
+            Algorithm::BatchPredictiveShuffle,
+            Algorithm::CryptoBatchPredictiveShuffle,
+        ] {
+            for batch in 6..=10 {
+                let request = ShuffleRequest {
+                    algorithm,
+                    size: 10,
+                    batch: Some(batch),
+                    positions: (0..10).collect(),
+                    seed: SeedDescriptor::Seed(b"seed phrase".to_vec()),
+                };
+                let record = ShuffleRecord::new(request).unwrap();
+                assert!(record.verify().is_ok());
+                let mut to: Vec<usize> = record
+                    .result
+                    .positions()
+                    .unwrap()
+                    .iter()
+                    .map(|(_, to)| to)
+                    .collect();
+                to.sort();
+                assert_eq!(to, (0..10).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn keyed_record_test() {
+        let keyring = Keyring::new(ServerKey::new("2023-01", b"server secret".to_vec()));
+        let mut request = request(Algorithm::CryptoPredictiveShuffle);
+        request.seed = SeedDescriptor::Keyed {
+            key_id: "2023-01".to_string(),
+            seed: b"seed phrase".to_vec(),
+            context: b"draw 1".to_vec(),
+        };
+        assert_eq!(request.run(), Err(RecordError::KeyRequired));
+
+        let record = ShuffleRecord::new_keyed(request, &keyring).unwrap();
+        assert!(record.verify_keyed(&keyring).is_ok());
+
+        // keyed requests give the result of the keyed function
+        let keyed = gen_keyed_crypto_predictive_shuffle_from_seed(
+            keyring.current(),
+            100,
+            record.request.positions.clone(),
+            b"seed phrase",
+            b"draw 1",
+        );
+        let positions = record.result.positions().unwrap();
+        for (from, to) in keyed.value {
+            assert_eq!(positions.get(from), Some(to));
+        }
+
+        let other = Keyring::new(ServerKey::new("2023-02", b"other secret".to_vec()));
+        assert!(matches!(
+            record.verify_keyed(&other),
+            Err(RecordError::Keyed(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn record_serde_test() {
+        for algorithm in Algorithm::ALL {
+            let record = ShuffleRecord::new(request(algorithm)).unwrap();
+            let json = serde_json::to_string(&record).unwrap();
+            let reloaded: ShuffleRecord = serde_json::from_str(&json).unwrap();
+            assert_eq!(reloaded, record);
+            assert_eq!(serde_json::to_string(&reloaded).unwrap(), json);
+            assert!(reloaded.verify().is_ok());
+        }
+
+        let record = ShuffleRecord {
+            request: ShuffleRequest {
+                algorithm: Algorithm::CryptoBatchPredictiveShuffle,
+                size: 10,
+                batch: Some(2),
+                positions: vec![1, 5],
+                seed: SeedDescriptor::Keyed {
+                    key_id: "2023-01".to_string(),
+                    seed: b"ab".to_vec(),
+                    context: vec![],
+                },
+            },
+            result: ShuffleResult::Positions(HashMap::from([(5, 2), (1, 7)]).into()),
+        };
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            concat!(
+                r#"{"request":{"algorithm":"crypto_batch_predictive_shuffle","size":10,"batch":2,"#,
+                r#""positions":[1,5],"seed":{"keyed":{"key_id":"2023-01","seed":"6162","context":""}}},"#,
+                r#""result":{"positions":[[1,7],[5,2]]}}"#
+            )
+        );
+
+        let json = r#"{"algorithm":"modern_shuffle","size":3,"seed":{"seed":"zz"}}"#;
+        assert!(serde_json::from_str::<ShuffleRequest>(json).is_err());
+        let json = r#"{"algorithm":"modern_shuffle","size":3,"seed":"entropy"}"#;
+        let request: ShuffleRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.seed, SeedDescriptor::Entropy);
+        assert!(request.positions.is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(all(test, feature = "chacha", feature = "fastrand"))]
+mod tests {
+    use predictive_shuffle::reservoir::ReservoirSampling;
+    use std::collections::HashSet;
+
+    #[test]
+    fn reservoir_sample_test() {
+        let seed = b"seed phrase".to_vec();
+        let sample = (0..10_000).reservoir_sample_from_seed(10, &seed);
+        assert_eq!(sample.len(), 10);
+
+        let unique: HashSet<i32> = sample.as_slice().iter().copied().collect();
+        assert_eq!(unique.len(), 10);
+
+        let again = (0..10_000).reservoir_sample_from_seed(10, seed);
+        assert_eq!(sample.into_vec(), again.into_vec());
+    }
+
+    #[test]
+    fn reservoir_short_stream_test() {
+        let seed = b"seed phrase".to_vec();
+        let sample = (0..3).crypto_reservoir_sample_from_seed(10, seed);
+        let mut shuffled = sample.into_shuffled();
+        shuffled.sort();
+        assert_eq!(shuffled, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn reservoir_uniformity_test() {
+        // every item of a 20 item stream should be picked about a quarter of the time
+        let mut counts = [0usize; 20];
+        for round in 0..4_000u32 {
+            let seed = round.to_be_bytes().to_vec();
+            for item in (0..20).reservoir_sample_from_seed(5, seed).into_vec() {
+                counts[item] += 1;
+            }
+        }
+        for count in counts {
+            assert!((800..1_200).contains(&count), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn weighted_reservoir_sample_test() {
+        let seed = b"seed phrase".to_vec();
+        let mut heavy = 0;
+        for round in 0..1_000u32 {
+            let mut seed = seed.clone();
+            seed.extend(round.to_be_bytes());
+            let sample = (0..100)
+                .crypto_weighted_reservoir_sample_from_seed(1, seed, |item| {
+                    if *item == 42 {
+                        100.0
+                    } else if *item % 2 == 0 {
+                        0.0
+                    } else {
+                        1.0
+                    }
+                })
+                .into_vec();
+            assert_eq!(sample.len(), 1);
+            assert!(sample[0] == 42 || sample[0] % 2 == 1);
+            if sample[0] == 42 {
+                heavy += 1;
+            }
+        }
+        // item 42 carries 100 of the 150 total weight
+        assert!((600..730).contains(&heavy), "{}", heavy);
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(all(test, feature = "chacha", feature = "fastrand"))]
+mod tests {
+    use predictive_shuffle::ordered::*;
+    use predictive_shuffle::shuffler::ShuffleRng;
+    use predictive_shuffle::{CryptoRng, Shuffler};
+
+    fn consistency<R: ShuffleRng>(seed: &[u8])
+    where
+        R::State: Clone,
+    {
+        let positions = [99, 1, 5, 42, 0];
+        let mut shuffler = Shuffler::<R>::from_seed(seed);
+        let state = shuffler.state();
+
+        // predict, gen, shuffle and sample agree from the same state
+        let predicted = shuffler.predict(100, &positions);
+        let shuffled = Shuffler::<R>::from_state(state.clone()).gen(100);
+        for (&position, &new_position) in positions.iter().zip(&predicted) {
+            assert_eq!(shuffled[new_position], position);
+        }
+
+        let mut items: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        Shuffler::<R>::from_state(state.clone()).shuffle(&mut items);
+        let expected: Vec<String> = shuffled.iter().map(|i| i.to_string()).collect();
+        assert_eq!(items, expected);
+
+        let sample = Shuffler::<R>::from_state(state).sample(100, 10);
+        let tail: Vec<usize> = shuffled.iter().rev().take(10).copied().collect();
+        assert_eq!(sample, tail);
+    }
+
+    #[test]
+    fn shuffler_matches_functions_test() {
+        let seed = b"seed phrase";
+        let positions = [99, 1, 5];
+        let mut out = [0; 3];
+
+        gen_predictive_shuffle_from_seed_into(100, &positions, seed, &mut out);
+        let mut shuffler = Shuffler::<fastrand::Rng>::from_seed(seed);
+        assert_eq!(shuffler.predict(100, &positions), out);
+
+        // a ChaCha20 shuffler draws like the fastrand functions, so it does not reproduce the
+        // `crypto_*` functions for the same seed
+        let mut shuffler = Shuffler::<CryptoRng>::from_seed(seed);
+        let predicted = shuffler.predict(100, &positions);
+        #[cfg(feature = "sha2")]
+        assert_eq!(predicted, [89, 3, 81]);
+        gen_crypto_predictive_shuffle_from_seed_into(100, &positions, seed, &mut out);
+        assert_ne!(predicted, out);
+    }
+
+    #[test]
+    fn shuffler_consistency_test() {
+        consistency::<fastrand::Rng>(b"seed phrase");
+        consistency::<CryptoRng>(b"seed phrase");
+    }
+
+    #[test]
+    fn shuffler_stream_test() {
+        // later draws continue the stream rather than repeat it
+        let mut shuffler = Shuffler::<CryptoRng>::from_seed(b"seed phrase");
+        let first = shuffler.gen(100);
+        let second = shuffler.gen(100);
+        assert_ne!(first, second);
+
+        let mut sorted = second.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn shuffler_state_test() {
+        let mut shuffler = Shuffler::<CryptoRng>::from_seed(b"seed phrase");
+        shuffler.predict(100, &[1, 5]);
+        shuffler.sample(1000, 3);
+
+        let state = shuffler.state();
+        let expected = (shuffler.gen(50), shuffler.sample(50, 5));
+        let mut resumed = Shuffler::<CryptoRng>::from_state(state);
+        assert_eq!((resumed.gen(50), resumed.sample(50, 5)), expected);
+
+        let mut shuffler = Shuffler::<fastrand::Rng>::from_seed(b"seed phrase");
+        shuffler.gen(7);
+        let state = shuffler.state();
+        let expected = shuffler.predict(1000, &[3, 999]);
+        let mut resumed = Shuffler::<fastrand::Rng>::from_state(state);
+        assert_eq!(resumed.predict(1000, &[3, 999]), expected);
+    }
+
+    #[test]
+    fn shuffler_sample_test() {
+        let mut shuffler = Shuffler::<fastrand::Rng>::from_seed(b"seed phrase");
+        assert_eq!(shuffler.sample(10, 0), Vec::<usize>::new());
+
+        let mut sample = shuffler.sample(10, 10);
+        sample.sort();
+        assert_eq!(sample, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample 11 items out of 10")]
+    fn shuffler_sample_too_many_test() {
+        Shuffler::<fastrand::Rng>::from_seed(b"seed phrase").sample(10, 11);
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(all(
+    test,
+    any(feature = "arrow", feature = "polars"),
+    feature = "chacha",
+    feature = "fastrand"
+))]
+mod tests {
+    use predictive_shuffle::table::*;
+    use predictive_shuffle::*;
+
+    #[test]
+    fn take_index_predict_test() {
+        let seed = b"seed phrase";
+        let index = TakeIndex::modern_from_seed_ref(100, seed);
+        assert_eq!(
+            index.as_slice(),
+            gen_modern_shuffle_from_seed_ref(100, seed)
+        );
+
+        let new_positions = index.predict([99, 1, 5]);
+        for (row, new_position) in new_positions {
+            assert_eq!(index.as_slice()[new_position], row);
+        }
+        assert!(TakeIndex::crypto_modern_from_seed_ref(0, seed).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "arrow")]
+    fn record_batch_test() {
+        use arrow_array::{Array, ArrayRef, Int64Array, RecordBatch, StringArray};
+        use std::sync::Arc;
+
+        let seed = b"seed phrase";
+        let ids: ArrayRef = Arc::new(Int64Array::from((0..50).collect::<Vec<i64>>()));
+        let names: ArrayRef = Arc::new(StringArray::from(
+            (0..50).map(|i| format!("row {}", i)).collect::<Vec<_>>(),
+        ));
+        let batch = RecordBatch::try_from_iter([("id", ids), ("name", names)]).unwrap();
+
+        let shuffled = crypto_shuffle_record_batch_from_seed_ref(&batch, seed).unwrap();
+        let order = gen_crypto_modern_shuffle_from_seed_ref(50, seed);
+        let ids = shuffled
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        let names = shuffled
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        for (k, &row) in order.iter().enumerate() {
+            assert_eq!(ids.value(k), row as i64);
+            assert_eq!(names.value(k), format!("row {}", row));
+        }
+        assert_eq!(ids.len(), 50);
+
+        let index = TakeIndex::modern_from_seed_ref(49, seed);
+        assert!(index.take_record_batch(&batch).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "polars")]
+    fn data_frame_test() {
+        use polars::prelude::*;
+
+        let seed = b"seed phrase";
+        let df = DataFrame::new(vec![
+            Column::new("id".into(), (0..50).collect::<Vec<i64>>()),
+            Column::new(
+                "name".into(),
+                (0..50).map(|i| format!("row {}", i)).collect::<Vec<_>>(),
+            ),
+        ])
+        .unwrap();
+
+        let index = TakeIndex::modern_from_seed_ref(df.height(), seed);
+        let shuffled = shuffle_data_frame_from_seed_ref(&df, seed).unwrap();
+        let ids: Vec<i64> = shuffled
+            .column("id")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        let expected: Vec<i64> = index.as_slice().iter().map(|&i| i as i64).collect();
+        assert_eq!(ids, expected);
+
+        let new_positions = index.predict([7]);
+        let name = shuffled
+            .column("name")
+            .unwrap()
+            .str()
+            .unwrap()
+            .get(new_positions[&7]);
+        assert_eq!(name, Some("row 7"));
+
+        let index = TakeIndex::crypto_modern_from_seed_ref(51, seed);
+        assert!(index.take_data_frame(&df).is_err());
+    }
+}
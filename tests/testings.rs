@@ -1,8 +1,8 @@
-#[cfg(test)]
-
+#[cfg(all(test, feature = "std", feature = "chacha", feature = "fastrand"))]
+#[allow(deprecated)]
 mod tests {
-    use predictive_shuffle::Shuffle;
-    use std::collections::HashSet;
+    use predictive_shuffle::*;
+    use std::collections::{HashMap, HashSet};
 
     #[test]
     fn predictive_test() {
@@ -46,4 +46,211 @@ mod tests {
         let shuffled_items = vec.crypto_batch_predictive_shuffle_from_seed(batch, positions, seed);
         println!("{:?}", shuffled_items);
     }
+
+    #[test]
+    fn borrowed_matches_owned_test() {
+        let seed = b"seed phrase";
+        let positions = vec![1, 5, 42];
+        let batch = 4;
+
+        let mut vec: Vec<usize> = (0..100).collect();
+        let mut owned = vec.clone();
+        vec.fastrand_shuffle_from_seed_ref(seed);
+        owned.fastrand_shuffle_from_seed(seed.to_vec());
+        assert_eq!(vec, owned);
+
+        let vec: Vec<usize> = (0..100).collect();
+        assert_eq!(
+            vec.clone().modern_shuffle_from_seed_ref(seed),
+            vec.clone().modern_shuffle_from_seed(seed.to_vec())
+        );
+        assert_eq!(
+            vec.clone().crypto_modern_shuffle_from_seed_ref(seed),
+            vec.clone().crypto_modern_shuffle_from_seed(seed.to_vec())
+        );
+        assert_eq!(
+            vec.clone()
+                .predictive_shuffle_from_seed_ref(&positions, seed),
+            vec.clone()
+                .predictive_shuffle_from_seed(positions.clone(), seed.to_vec())
+        );
+        assert_eq!(
+            vec.clone()
+                .crypto_predictive_shuffle_from_seed_ref(positions.clone(), seed),
+            vec.clone()
+                .crypto_predictive_shuffle_from_seed(positions.clone(), seed.to_vec())
+        );
+        assert_eq!(
+            vec.clone()
+                .batch_predictive_shuffle_from_seed_ref(batch, &positions, seed),
+            vec.clone()
+                .batch_predictive_shuffle_from_seed(batch, positions.clone(), seed.to_vec())
+        );
+        assert_eq!(
+            vec.clone().crypto_batch_predictive_shuffle_from_seed_ref(
+                batch,
+                positions.iter(),
+                seed
+            ),
+            vec.clone().crypto_batch_predictive_shuffle_from_seed(
+                batch,
+                positions.clone(),
+                seed.to_vec()
+            )
+        );
+
+        assert_eq!(
+            gen_fastrand_shuffle_from_seed_ref(100, seed),
+            gen_fastrand_shuffle_from_seed(100, seed.to_vec())
+        );
+        assert_eq!(
+            gen_modern_shuffle_from_seed_ref(100, seed),
+            gen_modern_shuffle_from_seed(100, seed.to_vec())
+        );
+        assert_eq!(
+            gen_crypto_modern_shuffle_from_seed_ref(100, seed),
+            gen_crypto_modern_shuffle_from_seed(100, seed.to_vec())
+        );
+        assert_eq!(
+            gen_predictive_shuffle_from_seed_ref(100, &positions, seed),
+            gen_predictive_shuffle_from_seed(100, positions.clone(), seed.to_vec())
+        );
+        assert_eq!(
+            gen_crypto_predictive_shuffle_from_seed_ref(100, 1..3, seed),
+            gen_crypto_predictive_shuffle_from_seed(100, vec![1, 2], seed.to_vec())
+        );
+        assert_eq!(
+            gen_batch_predictive_shuffle_from_seed_ref(100, batch, &positions, seed),
+            gen_batch_predictive_shuffle_from_seed(100, batch, positions.clone(), seed.to_vec())
+        );
+        assert_eq!(
+            gen_crypto_batch_predictive_shuffle_from_seed_ref(100, batch, &positions, seed),
+            gen_crypto_batch_predictive_shuffle_from_seed(
+                100,
+                batch,
+                positions.clone(),
+                seed.to_vec()
+            )
+        );
+
+        let new_positions = gen_predictive_shuffle_ref(100, &positions);
+        assert_eq!(new_positions.len(), positions.len());
+        let new_positions = vec
+            .clone()
+            .crypto_batch_predictive_shuffle_ref(batch, &positions);
+        assert_eq!(new_positions.len(), positions.len());
+    }
+
+    #[test]
+    fn shuffle_trait_object_test() {
+        let seed = b"seed phrase";
+        let positions = vec![1, 5];
+
+        // the non-generic methods stay callable through `dyn Shuffle`
+        let mut vec: Vec<usize> = (0..100).collect();
+        let shuffle: &mut dyn Shuffle<usize> = &mut vec;
+        let new_positions = shuffle.predictive_shuffle_from_seed(positions.clone(), seed.to_vec());
+        assert_eq!(
+            new_positions,
+            gen_predictive_shuffle_from_seed_ref(100, &positions, seed)
+        );
+    }
+
+    /// Implements only the owned methods, as an implementation written before the `*_ref`
+    /// methods existed does
+    struct Deck(Vec<usize>);
+
+    impl Shuffle<usize> for Deck {
+        fn fastrand_shuffle(&mut self) {
+            self.0.fastrand_shuffle()
+        }
+        fn fastrand_shuffle_from_seed(&mut self, seed: Vec<u8>) {
+            self.0.fastrand_shuffle_from_seed(seed)
+        }
+        fn modern_shuffle(&mut self) -> Vec<usize> {
+            self.0.modern_shuffle()
+        }
+        fn modern_shuffle_from_seed(&mut self, seed: Vec<u8>) -> Vec<usize> {
+            self.0.modern_shuffle_from_seed(seed)
+        }
+        fn crypto_modern_shuffle(&mut self) -> Vec<usize> {
+            self.0.crypto_modern_shuffle()
+        }
+        fn crypto_modern_shuffle_from_seed(&mut self, seed: Vec<u8>) -> Vec<usize> {
+            self.0.crypto_modern_shuffle_from_seed(seed)
+        }
+        fn predictive_shuffle(&mut self, positions: Vec<usize>) -> HashMap<usize, usize> {
+            self.0.predictive_shuffle(positions)
+        }
+        fn predictive_shuffle_from_seed(
+            &mut self,
+            positions: Vec<usize>,
+            seed: Vec<u8>,
+        ) -> HashMap<usize, usize> {
+            self.0.predictive_shuffle_from_seed(positions, seed)
+        }
+        fn crypto_predictive_shuffle(&mut self, positions: Vec<usize>) -> HashMap<usize, usize> {
+            self.0.crypto_predictive_shuffle(positions)
+        }
+        fn crypto_predictive_shuffle_from_seed(
+            &mut self,
+            positions: Vec<usize>,
+            seed: Vec<u8>,
+        ) -> HashMap<usize, usize> {
+            self.0.crypto_predictive_shuffle_from_seed(positions, seed)
+        }
+        fn batch_predictive_shuffle(
+            &mut self,
+            batch: usize,
+            positions: Vec<usize>,
+        ) -> HashMap<usize, usize> {
+            self.0.batch_predictive_shuffle(batch, positions)
+        }
+        fn batch_predictive_shuffle_from_seed(
+            &mut self,
+            batch: usize,
+            positions: Vec<usize>,
+            seed: Vec<u8>,
+        ) -> HashMap<usize, usize> {
+            self.0
+                .batch_predictive_shuffle_from_seed(batch, positions, seed)
+        }
+        fn crypto_batch_predictive_shuffle(
+            &mut self,
+            batch: usize,
+            positions: Vec<usize>,
+        ) -> HashMap<usize, usize> {
+            self.0.crypto_batch_predictive_shuffle(batch, positions)
+        }
+        fn crypto_batch_predictive_shuffle_from_seed(
+            &mut self,
+            batch: usize,
+            positions: Vec<usize>,
+            seed: Vec<u8>,
+        ) -> HashMap<usize, usize> {
+            self.0
+                .crypto_batch_predictive_shuffle_from_seed(batch, positions, seed)
+        }
+    }
+
+    #[test]
+    fn owned_only_implementation_test() {
+        let seed = b"seed phrase";
+        let positions = [1, 5];
+        let mut deck = Deck((0..100).collect());
+        let mut vec: Vec<usize> = (0..100).collect();
+
+        assert_eq!(
+            deck.predictive_shuffle_from_seed_ref(positions, seed),
+            vec.predictive_shuffle_from_seed_ref(positions, seed)
+        );
+        assert_eq!(
+            deck.crypto_batch_predictive_shuffle_from_seed_ref(4, positions, seed),
+            vec.crypto_batch_predictive_shuffle_from_seed_ref(4, positions, seed)
+        );
+        assert_eq!(
+            deck.crypto_modern_shuffle_from_seed_ref(seed),
+            vec.crypto_modern_shuffle_from_seed_ref(seed)
+        );
+    }
 }
@@ -0,0 +1,107 @@
+#[cfg(all(test, feature = "std", feature = "sha2", feature = "chacha"))]
+mod tests {
+    use predictive_shuffle::transcript::*;
+    use predictive_shuffle::{CryptoRng, Shuffler};
+
+    fn entrants() -> Vec<String> {
+        (0..100).map(|i| format!("entrant {}", i)).collect()
+    }
+
+    #[test]
+    fn transcript_roundtrip_test() {
+        let reveal = Reveal::with_nonce(b"seed phrase".to_vec(), vec![7; NONCE_LEN]);
+        let transcript = Transcript::new(&reveal, &entrants(), vec![1, 5]).unwrap();
+        assert_eq!(transcript.result, vec![(1, 3), (5, 81)]);
+
+        let text = transcript.to_string();
+        let parsed: Transcript = text.parse().unwrap();
+        assert_eq!(parsed, transcript);
+        assert_eq!(parsed.to_string(), text);
+        assert_eq!(verify_transcript(&parsed, &entrants()), Ok(()));
+    }
+
+    #[test]
+    fn transcript_all_positions_test() {
+        let reveal = Reveal::new(b"seed phrase".to_vec());
+        let positions: Vec<usize> = (0..100).collect();
+        let transcript = Transcript::new(&reveal, &entrants(), positions.clone()).unwrap();
+
+        let mut drawn: Vec<usize> = transcript.result.iter().map(|(_, to)| *to).collect();
+        drawn.sort();
+        assert_eq!(drawn, positions);
+
+        // the documented recomputation route gives the same result
+        let seed = &transcript.reveal.seed;
+        let drawn = Shuffler::<CryptoRng>::from_seed(seed).predict(100, &positions);
+        let result: Vec<(usize, usize)> = positions.into_iter().zip(drawn).collect();
+        assert_eq!(result, transcript.result);
+    }
+
+    #[test]
+    fn transcript_unbiased_draw_test() {
+        // the draw is a plain Fisher-Yates, as predicted by a ChaCha20 `Shuffler`
+        let reveal = Reveal::with_nonce(b"seed phrase".to_vec(), vec![]);
+        let transcript = Transcript::new(&reveal, &entrants(), vec![1, 5]).unwrap();
+        let mut shuffler = Shuffler::<CryptoRng>::from_seed(b"seed phrase");
+        assert_eq!(shuffler.predict(100, &[1, 5]), [3, 81]);
+
+        // entrants can keep their own slot, which a Sattolo shuffle never allows
+        let kept = (0..100u32)
+            .filter(|seed| {
+                let reveal = Reveal::with_nonce(seed.to_be_bytes().to_vec(), vec![]);
+                let transcript = Transcript::new(&reveal, &entrants()[..2], vec![0, 1]).unwrap();
+                transcript.result == [(0, 0), (1, 1)]
+            })
+            .count();
+        assert!(kept > 0 && kept < 100);
+        assert_eq!(transcript.version, ALGORITHM_VERSION);
+    }
+
+    #[test]
+    fn transcript_tampering_test() {
+        let reveal = Reveal::new(b"seed phrase".to_vec());
+        let transcript = Transcript::new(&reveal, &entrants(), vec![3, 4, 5]).unwrap();
+
+        let mut other_seed = transcript.clone();
+        other_seed.reveal.seed = b"other phrase".to_vec();
+        assert_eq!(
+            verify_transcript(&other_seed, &entrants()),
+            Err(TranscriptError::CommitmentMismatch)
+        );
+
+        let mut other_result = transcript.clone();
+        other_result.result[0].1 += 1;
+        assert_eq!(
+            verify_transcript(&other_result, &entrants()),
+            Err(TranscriptError::ResultMismatch)
+        );
+
+        let mut fewer = entrants();
+        fewer.pop();
+        assert_eq!(
+            verify_transcript(&transcript, &fewer),
+            Err(TranscriptError::EntrantsMismatch)
+        );
+
+        let mut other_version = transcript;
+        other_version.version = "v0".to_string();
+        assert!(matches!(
+            verify_transcript(&other_version, &entrants()),
+            Err(TranscriptError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn transcript_invalid_positions_test() {
+        let reveal = Reveal::new(b"seed phrase".to_vec());
+        assert_eq!(
+            Transcript::new(&reveal, &entrants(), vec![1, 1]),
+            Err(TranscriptError::InvalidPosition(1))
+        );
+        assert_eq!(
+            Transcript::new(&reveal, &entrants(), vec![100]),
+            Err(TranscriptError::InvalidPosition(100))
+        );
+        assert!("not a transcript".parse::<Transcript>().is_err());
+    }
+}
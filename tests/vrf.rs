@@ -0,0 +1,70 @@
+#[cfg(all(test, feature = "vrf"))]
+mod tests {
+    use predictive_shuffle::vrf::*;
+    use predictive_shuffle::{
+        gen_crypto_modern_shuffle_from_seed_ref, gen_crypto_predictive_shuffle_from_seed_ref, hash,
+        CryptoRng, Seed,
+    };
+
+    fn keypair(byte: u8) -> Keypair {
+        MiniSecretKey::from_bytes(&[byte; 32])
+            .unwrap()
+            .expand_to_keypair(MiniSecretKey::ED25519_MODE)
+    }
+
+    #[test]
+    fn vrf_roundtrip_test() {
+        let keypair = keypair(1);
+        let (seed, proof) = vrf_prove(&keypair, b"draw 1");
+        let proof = VrfProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(
+            vrf_verify(&keypair.public, b"draw 1", &proof),
+            Ok(seed.clone())
+        );
+
+        // the seed is a function of the key and input only
+        let (again, _) = vrf_prove(&keypair, b"draw 1");
+        assert_eq!(again, seed);
+
+        // verifiers recompute the draw with the plain seeded functions
+        let new_positions = gen_crypto_predictive_shuffle_from_seed_ref(100, [1, 5], &seed);
+        let verified = proven(&keypair, b"draw 1");
+        assert_eq!(
+            gen_crypto_predictive_shuffle_from_seed_ref(100, [1, 5], &verified),
+            new_positions
+        );
+        let shuffled = gen_crypto_modern_shuffle_from_seed_ref(100, &verified);
+        assert_eq!(shuffled.len(), 100);
+    }
+
+    fn proven(keypair: &Keypair, input: &[u8]) -> Seed {
+        let (_, proof) = vrf_prove(keypair, input);
+        vrf_verify(&keypair.public, input, &proof).unwrap()
+    }
+
+    #[test]
+    fn vrf_full_key_test() {
+        // the whole digest of the seed keys the generator of the `crypto_*` functions
+        let (seed, _) = vrf_prove(&keypair(1), b"draw 1");
+        let digest = hash(seed.as_bytes());
+        let mut key = [0; 32];
+        for (byte, hex) in key.iter_mut().zip(digest.chunks(2)) {
+            *byte = u8::from_str_radix(std::str::from_utf8(hex).unwrap(), 16).unwrap();
+        }
+        assert_eq!(CryptoRng::from_seed_ref(seed.as_bytes()).state().seed, key);
+    }
+
+    #[test]
+    fn vrf_rejects_forgeries_test() {
+        let (_, proof) = vrf_prove(&keypair(1), b"draw 1");
+        assert_eq!(
+            vrf_verify(&keypair(1).public, b"draw 2", &proof),
+            Err(VrfError::InvalidProof)
+        );
+        assert_eq!(
+            vrf_verify(&keypair(2).public, b"draw 1", &proof),
+            Err(VrfError::InvalidProof)
+        );
+        assert_eq!(VrfProof::from_bytes(&[0; 10]), Err(VrfError::Malformed));
+    }
+}
@@ -0,0 +1,338 @@
+#[cfg(all(test, feature = "wasm"))]
+mod tests {
+    use predictive_shuffle::constant_time::*;
+    use predictive_shuffle::ordered::*;
+    use predictive_shuffle::*;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+    use wasmi::{Caller, Engine, Extern, Instance, Linker, Memory, Module, Store, Val};
+
+    const TARGET: &str = "wasm32-unknown-unknown";
+
+    /// Build the crate for `wasm32-unknown-unknown` into its own target directory, so that the
+    /// artifacts this test links against are left alone
+    fn build() -> PathBuf {
+        let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("wasm");
+        let status = Command::new(env!("CARGO"))
+            .args([
+                "rustc",
+                "--lib",
+                "--crate-type",
+                "cdylib",
+                "--features",
+                "wasm",
+            ])
+            .args(["--target", TARGET])
+            .arg("--manifest-path")
+            .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .status()
+            .unwrap();
+        assert!(
+            status.success(),
+            "building for {} failed, is the target installed? (rustup target add {})",
+            TARGET,
+            TARGET
+        );
+        target_dir
+            .join(TARGET)
+            .join("debug/predictive_shuffle.wasm")
+    }
+
+    /// Messages of the JavaScript errors created by the module
+    #[derive(Default)]
+    struct Host {
+        errors: Vec<String>,
+    }
+
+    fn memory(caller: &Caller<'_, Host>) -> Memory {
+        caller
+            .get_export("memory")
+            .and_then(Extern::into_memory)
+            .unwrap()
+    }
+
+    /// The module without the JavaScript glue of `wasm-bindgen --target web`, calling the raw
+    /// exports and standing in for the few imports it needs
+    struct Wasm {
+        store: Store<Host>,
+        instance: Instance,
+        module: Module,
+    }
+
+    /// Result of an export returning a `Uint32Array`, or the message of the `Error` it threw
+    type Output = Result<Vec<usize>, String>;
+
+    impl Wasm {
+        fn new(path: &Path) -> Self {
+            let engine = Engine::default();
+            let module = Module::new(&engine, &std::fs::read(path).unwrap()).unwrap();
+            let mut store = Store::new(&engine, Host::default());
+            let mut linker = <Linker<Host>>::new(&engine);
+
+            for import in module.imports() {
+                let ty = import.ty().func().unwrap().clone();
+                let name = import.name().to_string();
+                linker
+                    .func_new(
+                        import.module(),
+                        import.name(),
+                        ty,
+                        move |mut caller, params, results| {
+                            if name.starts_with("__wbg_Error_") {
+                                // `new Error(message)`, as an index into the errors
+                                let (ptr, len) =
+                                    (params[0].i32().unwrap(), params[1].i32().unwrap());
+                                let mut message = vec![0; len as usize];
+                                memory(&caller)
+                                    .read(&caller, ptr as usize, &mut message)
+                                    .unwrap();
+                                let errors = &mut caller.data_mut().errors;
+                                errors.push(String::from_utf8(message).unwrap());
+                                results[0] = Val::I32(errors.len() as i32 - 1);
+                                Ok(())
+                            } else if name == "__wbindgen_describe" || name.contains("drop_ref") {
+                                Ok(())
+                            } else {
+                                Err(wasmi::Error::new(format!("unexpected call to {}", name)))
+                            }
+                        },
+                    )
+                    .unwrap();
+            }
+            let instance = linker
+                .instantiate(&mut store, &module)
+                .unwrap()
+                .start(&mut store)
+                .unwrap();
+            Wasm {
+                store,
+                instance,
+                module,
+            }
+        }
+
+        /// Call an export by its `js_name`, which the raw module suffixes with a hash
+        fn call(&mut self, js_name: &str, params: &[Val], results: &mut [Val]) {
+            let export = self
+                .module
+                .exports()
+                .map(|export| export.name().to_string())
+                .find(|name| {
+                    name == js_name
+                        || name
+                            .strip_prefix(js_name)
+                            .is_some_and(|hash| hash.starts_with('_'))
+                })
+                .unwrap_or_else(|| panic!("{} is not exported", js_name));
+            let func = self.instance.get_func(&self.store, &export).unwrap();
+            func.call(&mut self.store, params, results).unwrap();
+        }
+
+        fn memory(&self) -> Memory {
+            self.instance.get_memory(&self.store, "memory").unwrap()
+        }
+
+        fn read_u32s(&self, ptr: u32, len: usize) -> Vec<u32> {
+            let mut bytes = vec![0; len * 4];
+            self.memory()
+                .read(&self.store, ptr as usize, &mut bytes)
+                .unwrap();
+            bytes
+                .chunks_exact(4)
+                .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+                .collect()
+        }
+
+        fn alloc(&mut self, size: usize, align: usize) -> u32 {
+            let mut ptr = [Val::I32(0)];
+            self.call(
+                "__wbindgen_malloc",
+                &[Val::I32(size as i32), Val::I32(align as i32)],
+                &mut ptr,
+            );
+            ptr[0].i32().unwrap() as u32
+        }
+
+        /// Copy `bytes` into the module, giving the pointer and length it takes slices as
+        fn pass(&mut self, bytes: &[u8], align: usize) -> [Val; 2] {
+            let ptr = self.alloc(bytes.len(), align);
+            self.memory()
+                .write(&mut self.store, ptr as usize, bytes)
+                .unwrap();
+            [Val::I32(ptr as i32), Val::I32((bytes.len() / align) as i32)]
+        }
+
+        fn pass_u32s(&mut self, items: &[usize]) -> [Val; 2] {
+            let bytes: Vec<u8> = items
+                .iter()
+                .flat_map(|&item| (item as u32).to_le_bytes())
+                .collect();
+            self.pass(&bytes, 4)
+        }
+
+        /// Call an export returning a `Vec<u32>` or a `Result<Vec<u32>, JsError>`, whose results
+        /// are written through a pointer passed as the first argument
+        fn call_ret(&mut self, js_name: &str, args: &[Val]) -> Output {
+            let [ret, _] = self.pass(&[0; 16], 4);
+            let ret = ret.i32().unwrap() as u32;
+            let mut params = vec![Val::I32(ret as i32)];
+            params.extend_from_slice(args);
+            self.call(js_name, &params, &mut []);
+
+            let ret = self.read_u32s(ret, 4);
+            if ret[3] == 1 {
+                return Err(self.store.data().errors[ret[2] as usize].clone());
+            }
+            let items = self.read_u32s(ret[0], ret[1] as usize);
+            self.call(
+                "__wbindgen_free",
+                &[
+                    Val::I32(ret[0] as i32),
+                    Val::I32(ret[1] as i32 * 4),
+                    Val::I32(4),
+                ],
+                &mut [],
+            );
+            Ok(items.into_iter().map(|item| item as usize).collect())
+        }
+
+        fn permutation(&mut self, js_name: &str, size: usize, seed: &[u8]) -> Output {
+            let mut args = vec![Val::I32(size as i32)];
+            args.extend(self.pass(seed, 1));
+            self.call_ret(js_name, &args)
+        }
+
+        fn predict(
+            &mut self,
+            js_name: &str,
+            size: usize,
+            batch: Option<usize>,
+            positions: &[usize],
+            seed: &[u8],
+        ) -> Output {
+            let mut args = vec![Val::I32(size as i32)];
+            args.extend(batch.map(|batch| Val::I32(batch as i32)));
+            args.extend(self.pass_u32s(positions));
+            args.extend(self.pass(seed, 1));
+            self.call_ret(js_name, &args)
+        }
+    }
+
+    #[test]
+    fn wasm_test() {
+        let mut wasm = Wasm::new(&build());
+        let seed = b"seed phrase";
+        let positions = [999, 1, 5];
+
+        // draws are made the same way on wasm32, so seeded results match native ones
+        assert_eq!(
+            wasm.permutation("fastrandShuffleFromSeed", 1000, seed),
+            Ok(gen_fastrand_shuffle_from_seed_ref(1000, seed))
+        );
+        assert_eq!(
+            wasm.permutation("modernShuffleFromSeed", 1000, seed),
+            Ok(gen_modern_shuffle_from_seed_ref(1000, seed))
+        );
+        assert_eq!(
+            wasm.permutation("cryptoModernShuffleFromSeed", 1000, seed),
+            Ok(gen_crypto_modern_shuffle_from_seed_ref(1000, seed))
+        );
+        assert_eq!(
+            wasm.permutation("constantTimeShuffleFromSeed", 100, seed),
+            Ok(gen_constant_time_shuffle_from_seed(100, seed))
+        );
+        assert_eq!(
+            wasm.permutation("modernShuffleFromSeed", 0, b""),
+            Ok(vec![])
+        );
+
+        let mut expected = [0; 3];
+        gen_predictive_shuffle_from_seed_into(1000, &positions, seed, &mut expected);
+        assert_eq!(
+            wasm.predict("predictiveShuffleFromSeed", 1000, None, &positions, seed),
+            Ok(expected.to_vec())
+        );
+        gen_crypto_predictive_shuffle_from_seed_into(1000, &positions, seed, &mut expected);
+        assert_eq!(
+            wasm.predict(
+                "cryptoPredictiveShuffleFromSeed",
+                1000,
+                None,
+                &positions,
+                seed
+            ),
+            Ok(expected.to_vec())
+        );
+        gen_batch_predictive_shuffle_from_seed_into(1000, 7, &positions, seed, &mut expected);
+        assert_eq!(
+            wasm.predict(
+                "batchPredictiveShuffleFromSeed",
+                1000,
+                Some(7),
+                &positions,
+                seed
+            ),
+            Ok(expected.to_vec())
+        );
+        gen_crypto_batch_predictive_shuffle_from_seed_into(
+            1000,
+            7,
+            &positions,
+            seed,
+            &mut expected,
+        );
+        assert_eq!(
+            wasm.predict(
+                "cryptoBatchPredictiveShuffleFromSeed",
+                1000,
+                Some(7),
+                &positions,
+                seed
+            ),
+            Ok(expected.to_vec())
+        );
+        assert_eq!(
+            wasm.predict(
+                "constantTimePredictiveShuffleFromSeed",
+                100,
+                None,
+                &[1, 5],
+                seed
+            ),
+            Ok(gen_constant_time_predictive_shuffle_from_seed(
+                100,
+                &[1, 5],
+                seed
+            ))
+        );
+
+        assert_eq!(
+            wasm.predict("predictiveShuffleFromSeed", 100, None, &[1, 100], seed),
+            Err("position 100 is out of range or repeated for the size 100".to_string())
+        );
+        assert_eq!(
+            wasm.predict("predictiveShuffleFromSeed", 100, None, &[1, 1], seed),
+            Err("position 1 is out of range or repeated for the size 100".to_string())
+        );
+        assert_eq!(
+            wasm.predict("batchPredictiveShuffleFromSeed", 10, Some(11), &[1], seed),
+            Err("batch size 11 is zero or larger than the size 10".to_string())
+        );
+
+        // a batch above half the size used to panic, which aborts the instance
+        let mut expected = [0; 2];
+        gen_crypto_batch_predictive_shuffle_from_seed_into(10, 6, &[1, 5], seed, &mut expected);
+        assert_eq!(
+            wasm.predict(
+                "cryptoBatchPredictiveShuffleFromSeed",
+                10,
+                Some(6),
+                &[1, 5],
+                seed
+            ),
+            Ok(expected.to_vec())
+        );
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(all(
+    test,
+    feature = "zeroize",
+    feature = "std",
+    feature = "sha2",
+    feature = "chacha",
+    feature = "fastrand"
+))]
+mod tests {
+    use predictive_shuffle::transcript::Reveal;
+    #[allow(deprecated)]
+    use predictive_shuffle::{
+        gen_crypto_modern_shuffle_from_seed, gen_crypto_predictive_shuffle_from_seed, CryptoRng,
+        Seed, Shuffle,
+    };
+    use rand::RngCore;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    const SNAPSHOT_LEN: usize = 1024;
+
+    /// System allocator that copies out the contents of one watched block as it is freed
+    struct Watcher;
+
+    static WATCHED: AtomicUsize = AtomicUsize::new(0);
+    static SNAPSHOT_BUF: Mutex<[u8; SNAPSHOT_LEN]> = Mutex::new([0; SNAPSHOT_LEN]);
+    static SNAPSHOT_SIZE: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+    // only one block can be watched at a time
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    unsafe impl GlobalAlloc for Watcher {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            if WATCHED
+                .compare_exchange(ptr as usize, 0, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                let size = layout.size().min(SNAPSHOT_LEN);
+                let mut buf = SNAPSHOT_BUF.lock().unwrap();
+                buf[..size].copy_from_slice(std::slice::from_raw_parts(ptr, size));
+                SNAPSHOT_SIZE.store(size, Ordering::SeqCst);
+            }
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Watcher = Watcher;
+
+    /// Run `f` on a heap value and return the bytes of its block at the moment it was freed
+    fn freed_contents<V, F: FnOnce(V)>(value: V, ptr: *const u8, f: F) -> Vec<u8> {
+        SNAPSHOT_SIZE.store(usize::MAX, Ordering::SeqCst);
+        WATCHED.store(ptr as usize, Ordering::SeqCst);
+        f(value);
+        WATCHED.store(0, Ordering::SeqCst);
+
+        let size = SNAPSHOT_SIZE.load(Ordering::SeqCst);
+        assert_ne!(size, usize::MAX, "watched block was not freed");
+        let contents = SNAPSHOT_BUF.lock().unwrap()[..size].to_vec();
+        contents
+    }
+
+    fn seed_wiped_by<F: FnOnce(Vec<u8>)>(f: F) -> bool {
+        let seed = b"seed phrase".to_vec();
+        let ptr = seed.as_ptr();
+        freed_contents(seed, ptr, f).iter().all(|&byte| byte == 0)
+    }
+
+    #[test]
+    fn seed_is_wiped_on_drop() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+
+        assert!(seed_wiped_by(|bytes| drop(Seed::new(bytes))));
+        assert!(seed_wiped_by(|bytes| drop(Reveal::with_nonce(
+            bytes,
+            vec![1; 32]
+        ))));
+        // the control: a plain vector is not wiped
+        assert!(!seed_wiped_by(drop));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn crypto_functions_wipe_their_seed() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+
+        assert!(seed_wiped_by(|seed| {
+            let mut vec: Vec<usize> = (0..10).collect();
+            vec.crypto_modern_shuffle_from_seed(seed);
+        }));
+        assert!(seed_wiped_by(|seed| {
+            let mut vec: Vec<usize> = (0..10).collect();
+            vec.crypto_predictive_shuffle_from_seed(vec![1, 5], seed);
+        }));
+        assert!(seed_wiped_by(|seed| {
+            gen_crypto_modern_shuffle_from_seed(10, seed);
+        }));
+        assert!(seed_wiped_by(|seed| {
+            gen_crypto_predictive_shuffle_from_seed(10, vec![1, 5], seed);
+        }));
+    }
+
+    #[test]
+    fn rng_state_is_wiped_on_drop() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut rng = Box::new(CryptoRng::from_seed(b"seed phrase".to_vec()));
+        let output = rng.next_u64().to_ne_bytes();
+        let key = rng.get_seed();
+        let ptr = &*rng as *const CryptoRng as *const u8;
+
+        // the control: the live state holds the key and the buffered output
+        let live = unsafe { std::slice::from_raw_parts(ptr, std::mem::size_of::<CryptoRng>()) };
+        assert!(live.windows(key.len()).any(|window| window == key));
+        assert!(live.windows(output.len()).any(|window| window == output));
+
+        let contents = freed_contents(rng, ptr, drop);
+        assert!(!contents.windows(key.len()).any(|window| window == key));
+        assert!(!contents
+            .windows(output.len())
+            .any(|window| window == output));
+    }
+}